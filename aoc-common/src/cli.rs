@@ -0,0 +1,330 @@
+use std::env;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::input::Input;
+use crate::params;
+use crate::report::{Format, Record, Reporter};
+use crate::solution::{self, Part, Runner, Solution, Solver};
+
+pub const OPTIONS_USAGE: &str = "[--part 1|2] [--format text|json|csv] [--input <path> | <path> | - | --example] [--param <name>=<value>]... [--config <path>]";
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    Usage(String),
+    #[error("Cannot read {0}: {1}")]
+    Input(String, io::Error),
+    #[error("Day {0}: {1}")]
+    Solution(u8, solution::Error),
+    #[error("Cannot write output: {0}")]
+    Output(io::Error),
+    #[error("Day {0}: {1} part(s) failed")]
+    PartsFailed(u8, usize),
+    #[error("{0} day(s) failed")]
+    DaysFailed(usize),
+    #[error("{0} answer(s) do not match")]
+    VerifyFailed(usize),
+    #[error("{0} phase(s) are more than {1}% slower than the baseline")]
+    Regressions(usize, f64),
+    #[error("{0}:{1}")]
+    Config(String, crate::ParseError),
+}
+
+impl Error {
+    /// Usage errors exit with 2, everything else with 1.
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Error::Usage(_) => ExitCode::from(2),
+            _ => ExitCode::FAILURE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The registered input file for the runner, stdin for a single day binary.
+    Default,
+    Stdin,
+    File(PathBuf),
+    Example,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub parts: Vec<Part>,
+    pub source: Source,
+    pub format: Format,
+    /// `--param name=value` overrides, applied after the config file.
+    pub params: Vec<(String, String)>,
+    pub config: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { parts: Part::ALL.to_vec(), source: Source::Default, format: Format::default(), params: Vec::new(), config: None }
+    }
+}
+
+pub fn parse_part(s: &str) -> Result<Part, Error> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(Error::Usage(format!("Unknown part {}", s))),
+    }
+}
+
+impl Options {
+    pub fn parse<I: Iterator<Item = String>>(args: I) -> Result<Self, Error> {
+        Self::parse_with(args, |options, path| options.set_source(Source::File(path.into())))
+    }
+
+    /// Like `parse`, but every bare path is collected instead of being the only input,
+    /// for binaries that read several files.
+    pub fn parse_files<I: Iterator<Item = String>>(args: I) -> Result<(Self, Vec<PathBuf>), Error> {
+        let mut files = Vec::new();
+        let options = Self::parse_with(args, |_, path| {
+            files.push(path.into());
+            Ok(())
+        })?;
+        Ok((options, files))
+    }
+
+    fn parse_with<I, F>(mut args: I, mut path: F) -> Result<Self, Error>
+    where I: Iterator<Item = String>, F: FnMut(&mut Self, &str) -> Result<(), Error> {
+        let mut options = Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    let part = args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)))?;
+                    options.parts = vec![parse_part(&part)?];
+                },
+                "--format" | "-f" => {
+                    let format = args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)))?;
+                    options.format = format.parse().map_err(Error::Usage)?;
+                },
+                "--input" | "-i" => {
+                    let path = args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)))?;
+                    let source = if path == "-" { Source::Stdin } else { Source::File(path.into()) };
+                    options.set_source(source)?;
+                },
+                "--param" | "-P" => {
+                    let pair = args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)))?;
+                    options.params.push(params::parse_pair(&pair).map_err(Error::Usage)?);
+                },
+                "--config" | "-c" => {
+                    let path = args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)))?;
+                    options.config = Some(path.into());
+                },
+                "--example" | "-e" => options.set_source(Source::Example)?,
+                "-" => options.set_source(Source::Stdin)?,
+                flag if flag.starts_with('-') => return Err(Error::Usage(format!("Unknown argument {}", flag))),
+                file => path(&mut options, file)?,
+            }
+        }
+        Ok(options)
+    }
+
+    fn set_source(&mut self, source: Source) -> Result<(), Error> {
+        if self.source != Source::Default {
+            return Err(Error::Usage("Only one input source can be given".to_string()));
+        }
+        self.source = source;
+        Ok(())
+    }
+}
+
+/// Parameters of `day`: the config file section first, then the command line overrides.
+pub fn read_params(options: &Options, day: u8) -> Result<Vec<(String, String)>, Error> {
+    let mut pairs = match &options.config {
+        Some(path) => {
+            let name = path.display().to_string();
+            let input = Input::read_file(path).map_err(|e| Error::Input(name.clone(), e))?;
+            params::parse_config(&input.lines(), day).map_err(|e| Error::Config(name, e))?
+        },
+        None => Vec::new(),
+    };
+    pairs.extend(options.params.iter().cloned());
+    Ok(pairs)
+}
+
+/// Reads the input for `source`, `default` is used for `Source::Default` (stdin when `None`).
+pub fn read_source(source: &Source, runner: &dyn Runner, default: Option<&Path>) -> Result<Input, Error> {
+    let read_path = |path: &Path| Input::read_file(path).map_err(|e| Error::Input(path.display().to_string(), e));
+    match (source, default) {
+        (Source::Example, _) => Ok(Input::from(runner.example())),
+        (Source::File(path), _) => read_path(path),
+        (Source::Default, Some(path)) => read_path(path),
+        (Source::Stdin, _) | (Source::Default, None) => Input::read_stdin().map_err(|e| Error::Input("stdin".to_string(), e)),
+    }
+}
+
+/// Parses the input once and reports the answer and timings for every requested part.
+/// A failing part is reported on stderr and does not stop the others.
+pub fn run_parts<W: Write>(runner: &dyn Runner, input: &Input, parts: &[Part], params: &[(String, String)], reporter: &mut Reporter<W>) -> Result<(), Error> {
+    let day = runner.day();
+    let start = Instant::now();
+    let parsed = runner.parse(input);
+    let parse_time = start.elapsed();
+    let input = match parsed {
+        Ok(input) => input,
+        Err(error) => {
+            if reporter.format() != Format::Text {
+                for &part in parts {
+                    let answer = Err(error.to_string());
+                    let record = Record { day, part, answer, parse_time, solve_time: Duration::ZERO };
+                    reporter.write(&record).map_err(Error::Output)?;
+                }
+            }
+            return Err(Error::Solution(day, error));
+        }
+    };
+    let mut failed = 0;
+    for &part in parts {
+        let start = Instant::now();
+        let answer = runner.solve_with(input.as_ref(), part, params);
+        let solve_time = start.elapsed();
+        if let Err(error) = &answer {
+            eprintln!("{}", Error::Solution(day, error.clone()));
+            failed += 1;
+        }
+        let answer = answer.map_err(|e| e.to_string());
+        reporter.write(&Record { day, part, answer, parse_time, solve_time }).map_err(Error::Output)?;
+    }
+    if failed > 0 { Err(Error::PartsFailed(day, failed)) } else { Ok(()) }
+}
+
+/// Like `run_parts` without writing anything: one record per part, a failed parse fails every part.
+pub fn solve_parts(runner: &dyn Runner, input: &Input, parts: &[Part], params: &[(String, String)]) -> Vec<Record> {
+    let day = runner.day();
+    let start = Instant::now();
+    let parsed = runner.parse(input);
+    let parse_time = start.elapsed();
+    let input = match parsed {
+        Ok(input) => input,
+        Err(error) => return parts.iter()
+            .map(|&part| Record { day, part, answer: Err(error.to_string()), parse_time, solve_time: Duration::ZERO })
+            .collect(),
+    };
+    parts.iter().map(|&part| {
+        let start = Instant::now();
+        let answer = runner.solve_with(input.as_ref(), part, params).map_err(|e| e.to_string());
+        Record { day, part, answer, parse_time, solve_time: start.elapsed() }
+    }).collect()
+}
+
+/// Entry point shared by the per-day binaries.
+pub fn day_main<S>() -> ExitCode
+where S: Solution, S::Input: 'static {
+    let mut args = env::args();
+    let name = args.next().unwrap_or_default();
+    let runner = Solver::<S>::new();
+    let result = Options::parse(args).and_then(|options| {
+        let params = read_params(&options, S::DAY)?;
+        let input = read_source(&options.source, &runner, None)?;
+        let mut reporter = Reporter::new(options.format, io::stdout().lock());
+        run_parts(&runner, &input, &options.parts, &params, &mut reporter)
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            if let Error::Usage(_) = error {
+                eprintln!("usage: {} {}", name, OPTIONS_USAGE);
+            }
+            error.exit_code()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Count;
+
+    impl Solution for Count {
+        const DAY: u8 = 0;
+        const EXAMPLE: &'static str = "";
+        type Input = usize;
+        type Answer1 = usize;
+        type Answer2 = usize;
+        type Error = &'static str;
+        type Params = ();
+
+        fn parse<S: AsRef<str>>(lines: &[S]) -> Result<usize, Self::Error> {
+            Ok(lines.len())
+        }
+        fn part1(input: &usize) -> Result<usize, Self::Error> {
+            Ok(*input)
+        }
+        fn part2(_: &usize) -> Result<usize, Self::Error> {
+            Err("no part 2")
+        }
+    }
+
+    fn parse(s: &str) -> Result<Options, Error> {
+        Options::parse(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Options::default(), parse("").unwrap());
+        let options = parse("--part 2 --input data.txt").unwrap();
+        assert_eq!(vec![Part::Two], options.parts);
+        assert_eq!(Source::File("data.txt".into()), options.source);
+        assert_eq!(Source::Stdin, parse("-").unwrap().source);
+        assert_eq!(Source::Stdin, parse("--input -").unwrap().source);
+        assert_eq!(Source::Example, parse("--example -p 1").unwrap().source);
+        assert_eq!(Source::File("input".into()), parse("input").unwrap().source);
+        assert_eq!(Format::Json, parse("--format json").unwrap().format);
+        assert_eq!(Format::Csv, parse("-f csv -p 2").unwrap().format);
+        let options = parse("--param rounds=5 -P name=me --config variants.txt").unwrap();
+        let pairs = vec![("rounds".to_string(), "5".to_string()), ("name".to_string(), "me".to_string())];
+        assert_eq!((pairs, Some(PathBuf::from("variants.txt"))), (options.params, options.config));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(parse("--part"), Err(Error::Usage(_))));
+        assert!(matches!(parse("--part 3"), Err(Error::Usage(_))));
+        assert!(matches!(parse("--example -"), Err(Error::Usage(_))));
+        assert!(matches!(parse("--verbose"), Err(Error::Usage(_))));
+        assert!(matches!(parse("--format xml"), Err(Error::Usage(_))));
+        assert!(matches!(parse("--param rounds"), Err(Error::Usage(_))));
+        assert!(matches!(parse("a b"), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_files() {
+        let (options, files) = Options::parse_files("a -P top=5 b".split_whitespace().map(String::from)).unwrap();
+        assert_eq!(vec![PathBuf::from("a"), PathBuf::from("b")], files);
+        assert_eq!((Source::Default, 1), (options.source, options.params.len()));
+        assert!(matches!(Options::parse_files("- --example".split_whitespace().map(String::from)), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_run_parts() {
+        let runner = Solver::<Count>::new();
+        let mut reporter = Reporter::new(Format::Csv, Vec::new());
+        assert!(matches!(run_parts(&runner, &Input::from("a\nb\n"), &Part::ALL, &[], &mut reporter), Err(Error::PartsFailed(0, 1))));
+        let out = String::from_utf8(reporter.into_inner()).unwrap();
+        let rows: Vec<Vec<&str>> = out.lines().map(|l| l.split(',').collect()).collect();
+        assert_eq!(3, rows.len());
+        assert_eq!(&["0", "1", "ok", "2"], &rows[1][..4]);
+        assert_eq!(&["0", "2", "error", "Part 2 error: no part 2"], &rows[2][..4]);
+    }
+
+    #[test]
+    fn test_solve_parts() {
+        let runner = Solver::<Count>::new();
+        let records = solve_parts(&runner, &Input::from("a"), &Part::ALL, &[]);
+        assert_eq!(vec![Ok("1".to_string()), Err("Part 2 error: no part 2".to_string())],
+            records.iter().map(|r| r.answer.clone()).collect::<Vec<_>>());
+        let params = vec![("rounds".to_string(), "1".to_string())];
+        let records = solve_parts(&runner, &Input::default(), &[Part::One], &params);
+        assert_eq!(Err("Unknown parameter rounds".to_string()), records[0].answer);
+    }
+}
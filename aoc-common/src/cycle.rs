@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Sub;
+
+use num_traits::{CheckedAdd, CheckedMul};
+
+/// A repeating simulation: the state at `start + length` equals the state at `start`,
+/// and every full cycle changes the tracked metric by `delta`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<M> {
+    pub start: usize,
+    pub length: usize,
+    pub delta: M,
+    /// Metric for every step up to `start + length`.
+    metrics: Vec<M>,
+}
+
+impl<M> Cycle<M>
+where M: Copy + CheckedAdd + CheckedMul + TryFrom<usize> {
+    /// Metric at any step, `None` when the number of cycles or the metric does not fit `M`.
+    pub fn extrapolate(&self, step: usize) -> Option<M> {
+        if step < self.metrics.len() {
+            return Some(self.metrics[step]);
+        }
+        let cycles = (step - self.start) / self.length;
+        let offset = (step - self.start) % self.length;
+        let growth = self.delta.checked_mul(&M::try_from(cycles).ok()?)?;
+        self.metrics[self.start + offset].checked_add(&growth)
+    }
+}
+
+/// Remembers the step of every state, the first repeated state closes the cycle.
+#[derive(Debug, Clone)]
+pub struct CycleDetector<S, M> {
+    seen: HashMap<S, usize>,
+    metrics: Vec<M>,
+}
+
+impl<S: Eq + Hash, M: Copy + Sub<Output = M>> CycleDetector<S, M> {
+    pub fn new() -> Self {
+        Self { seen: HashMap::new(), metrics: Vec::new() }
+    }
+
+    /// Records the state and the metric of the next step, starting from step 0.
+    pub fn push(&mut self, state: S, metric: M) -> Option<Cycle<M>> {
+        let step = self.metrics.len();
+        self.metrics.push(metric);
+        let start = *self.seen.entry(state).or_insert(step);
+        (start != step).then(|| Cycle {
+            start,
+            length: step - start,
+            delta: metric - self.metrics[start],
+            metrics: self.metrics.clone(),
+        })
+    }
+
+    /// Number of recorded steps.
+    pub fn len(&self) -> usize {
+        self.metrics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty()
+    }
+}
+
+impl<S: Eq + Hash, M: Copy + Sub<Output = M>> Default for CycleDetector<S, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle() {
+        // states 0 1 2 3 4 2 3 4 ..., the metric grows by the state
+        let mut detector = CycleDetector::new();
+        let (mut state, mut total) = (0u64, 0u64);
+        let cycle = loop {
+            if let Some(cycle) = detector.push(state, total) {
+                break cycle;
+            }
+            state = if state == 4 { 2 } else { state + 1 };
+            total += state;
+        };
+        assert_eq!((2, 3, 9), (cycle.start, cycle.length, cycle.delta));
+        assert_eq!(6, detector.len());
+        let mut expected = vec![];
+        let (mut state, mut total) = (0u64, 0u64);
+        for _ in 0..20 {
+            expected.push(total);
+            state = if state == 4 { 2 } else { state + 1 };
+            total += state;
+        }
+        let extrapolated: Vec<u64> = (0..20).map(|step| cycle.extrapolate(step).unwrap()).collect();
+        assert_eq!(expected, extrapolated);
+        assert_eq!(Some(3 + 9 * 333_333_333_333), cycle.extrapolate(1_000_000_000_001));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut detector = CycleDetector::new();
+        assert_eq!(None, detector.push('a', 0u8));
+        let cycle = detector.push('a', 1u8).unwrap();
+        assert_eq!((0, 1, 1), (cycle.start, cycle.length, cycle.delta));
+        assert_eq!(Some(200), cycle.extrapolate(200));
+        assert_eq!(None, cycle.extrapolate(300));
+    }
+
+    #[test]
+    fn test_huge_step() {
+        // 2 per step: the number of cycles fits a u64 but the metric does not
+        let mut detector = CycleDetector::new();
+        assert_eq!(None, detector.push(0, 0u64));
+        let cycle = detector.push(0, 2u64).unwrap();
+        assert_eq!(Some(u64::MAX - 1), cycle.extrapolate(usize::MAX / 2));
+        assert_eq!(None, cycle.extrapolate(usize::MAX));
+        let mut detector = CycleDetector::new();
+        assert_eq!(None, detector.push(0, 5u64));
+        let cycle = detector.push(0, 6u64).unwrap();
+        assert_eq!(Some(u64::MAX), cycle.extrapolate(usize::MAX - 5));
+        assert_eq!(None, cycle.extrapolate(usize::MAX));
+    }
+}
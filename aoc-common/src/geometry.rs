@@ -0,0 +1,318 @@
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or a vector on the plane. Screen orientation is used by `Direction`:
+/// `x` grows to the right and `y` grows down, like columns and rows of a `Grid`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> T
+    where T: Copy + Ord + Add<Output = T> + Sub<Output = T> {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Self) -> T
+    where T: Copy + Ord + Add<Output = T> + Sub<Output = T> {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+}
+
+macro_rules! impl_point {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                $(self.$field += other.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                $(self.$field -= other.$field;)+
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+            fn mul(self, k: T) -> Self {
+                Self { $($field: self.$field * k),+ }
+            }
+        }
+    };
+}
+
+impl_point!(Point2 { x, y });
+impl_point!(Point3 { x, y, z });
+
+macro_rules! impl_signed {
+    ($($t:ty),+) => {
+        $(
+            impl Point2<$t> {
+                /// Component-wise signum: one step towards the direction of the vector.
+                pub fn signum(self) -> Self {
+                    Self { x: self.x.signum(), y: self.y.signum() }
+                }
+
+                /// Chessboard distance, neighbours including diagonals are at distance 1.
+                pub fn chebyshev(self, other: Self) -> $t {
+                    (self.x - other.x).abs().max((self.y - other.y).abs())
+                }
+            }
+
+            impl Point3<$t> {
+                pub fn signum(self) -> Self {
+                    Self { x: self.x.signum(), y: self.y.signum(), z: self.z.signum() }
+                }
+            }
+        )+
+    };
+}
+
+impl_signed!(i32, i64, isize);
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(p: Point3<T>) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// One of the four axis directions, in clockwise order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Unit step in screen orientation, `Up` is `(0, -1)`.
+    pub fn delta(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+}
+
+/// Accepts `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` and `^`/`>`/`v`/`<`.
+impl TryFrom<char> for Direction {
+    type Error = char;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' => Ok(Direction::Up),
+            'R' | 'E' | '>' => Ok(Direction::Right),
+            'D' | 'S' | 'v' => Ok(Direction::Down),
+            'L' | 'W' | '<' => Ok(Direction::Left),
+            c => Err(c),
+        }
+    }
+}
+
+/// The eight compass directions, clockwise from north; north is `Up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Compass {
+    pub const ALL: [Compass; 8] = [Compass::N, Compass::NE, Compass::E, Compass::SE, Compass::S, Compass::SW, Compass::W, Compass::NW];
+
+    /// Rotates by 45 degrees counterclockwise.
+    pub fn rotate_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Rotates by 45 degrees clockwise.
+    pub fn rotate_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn delta(self) -> Point2 {
+        let (x, y) = match self {
+            Compass::N => (0, -1),
+            Compass::NE => (1, -1),
+            Compass::E => (1, 0),
+            Compass::SE => (1, 1),
+            Compass::S => (0, 1),
+            Compass::SW => (-1, 1),
+            Compass::W => (-1, 0),
+            Compass::NW => (-1, -1),
+        };
+        Point2::new(x, y)
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::Up => Compass::N,
+            Direction::Right => Compass::E,
+            Direction::Down => Compass::S,
+            Direction::Left => Compass::W,
+        }
+    }
+}
+
+impl TryFrom<Compass> for Direction {
+    type Error = Compass;
+    fn try_from(c: Compass) -> Result<Self, Self::Error> {
+        match c {
+            Compass::N => Ok(Direction::Up),
+            Compass::E => Ok(Direction::Right),
+            Compass::S => Ok(Direction::Down),
+            Compass::W => Ok(Direction::Left),
+            c => Err(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut p: Point2 = Point2::new(1, 2) + Point2::new(3, -4);
+        assert_eq!(Point2::new(4, -2), p);
+        p -= Point2::new(1, 1);
+        assert_eq!(Point2::new(6, -6), p * 2);
+        assert_eq!(Point2::new(-3, 3), -p);
+        assert_eq!(Point2::new(1, -1), p.signum());
+        assert_eq!(Point3::new(1, 1, 1), Point3::new(1, 2, 3) - Point3::new(0, 1, 2));
+        assert_eq!((4, -2), <(i32, i32)>::from(Point2::from((4, -2))));
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(7, Point2::new(1, 2).manhattan(Point2::new(-2, 6)));
+        assert_eq!(4, Point2::<i32>::new(1, 2).chebyshev(Point2::new(-2, 6)));
+        assert_eq!(5usize, Point2::new(3usize, 0).manhattan(Point2::new(1, 3)));
+        assert_eq!(6, Point3::new(1, 1, 1).manhattan(Point3::new(0, 3, 4)));
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Direction::Up, Direction::Left.turn_right());
+        assert_eq!(Direction::Down, Direction::Up.opposite());
+        for d in Direction::ALL {
+            assert_eq!(d, d.turn_left().turn_right());
+            assert_eq!(-d.delta(), d.opposite().delta());
+            assert_eq!(Ok(d), Direction::try_from(Compass::from(d)));
+        }
+        assert_eq!(Ok(Direction::Down), Direction::try_from('v'));
+        assert_eq!(Err('x'), Direction::try_from('x'));
+    }
+
+    #[test]
+    fn test_compass() {
+        assert_eq!(Compass::NW, Compass::N.rotate_left());
+        assert_eq!(Compass::N, Compass::NW.rotate_right());
+        assert_eq!(Compass::SW, Compass::NE.opposite());
+        assert_eq!(Compass::N.delta() + Compass::E.delta(), Compass::NE.delta());
+        assert_eq!(Err(Compass::SE), Direction::try_from(Compass::SE));
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut, Range};
+
+use crate::geometry::{Compass, Direction, Point2};
+use crate::parse::ParseError;
+
+/// Position in a grid as `(row, column)`.
+pub type Pos = (usize, usize);
+
+/// Dense row-major 2D grid.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(height: usize, width: usize, value: T) -> Self
+    where T: Clone {
+        Self { height, width, cells: vec![value; height * width] }
+    }
+
+    /// Maps every byte of every line, all lines must have the same width.
+    pub fn parse<S, F>(lines: &[S], mut cell: F) -> Result<Self, ParseError>
+    where S: AsRef<str>, F: FnMut(u8) -> Option<T> {
+        let width = lines.first().map_or(0, |line| line.as_ref().len());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            if line.len() != width {
+                let span = width.min(line.len())..width.max(line.len());
+                return Err(ParseError::new(row, line, span, format!("expected width {}, got {}", width, line.len())));
+            }
+            parse_row(row, line, &mut cell, &mut cells)?;
+        }
+        Ok(Self { height: lines.len(), width, cells })
+    }
+
+    /// Like `parse`, but shorter lines are padded with `fill` up to the longest one.
+    pub fn parse_padded<S, F>(lines: &[S], fill: T, mut cell: F) -> Result<Self, ParseError>
+    where S: AsRef<str>, T: Clone, F: FnMut(u8) -> Option<T> {
+        let width = lines.iter().map(|line| line.as_ref().len()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            parse_row(row, line, &mut cell, &mut cells)?;
+            cells.extend(std::iter::repeat_n(fill.clone(), width - line.len()));
+        }
+        Ok(Self { height: lines.len(), width, cells })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        let width = self.width;
+        self.contains(pos).then(|| &mut self.cells[pos.0 * width + pos.1])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // chunks_exact panics on 0
+        self.cells.chunks_exact(self.width.max(1)).take(self.height)
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(col < self.width, "column {} is out of the grid of width {}", col, self.width);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        self.cells.iter_mut()
+    }
+
+    pub fn position<P: FnMut(&T) -> bool>(&self, predicate: P) -> Option<Pos> {
+        self.cells.iter().position(predicate).map(|i| (i / self.width, i % self.width))
+    }
+
+    /// Moves by `delta` (`x` is the column, `y` is the row), `None` outside of the grid.
+    pub fn offset(&self, (row, col): Pos, delta: Point2) -> Option<Pos> {
+        let pos = (row.checked_add_signed(delta.y as isize)?, col.checked_add_signed(delta.x as isize)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        self.offset(pos, direction.delta())
+    }
+
+    /// Neighbours in `Direction::ALL` order that are inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL.into_iter().filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Neighbours in `Compass::ALL` order, including diagonals, that are inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Compass::ALL.into_iter().filter_map(move |c| self.offset(pos, c.delta()))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { height: self.height, width: self.width, cells: self.cells.iter().map(f).collect() }
+    }
+
+    pub fn fill(&mut self, value: T)
+    where T: Clone {
+        self.cells.fill(value);
+    }
+
+    /// Appends a row, it must have the grid width (any width for an empty grid without columns).
+    pub fn push_row<I: IntoIterator<Item = T>>(&mut self, row: I) {
+        let len = self.cells.len();
+        self.cells.extend(row);
+        let width = self.cells.len() - len;
+        if self.width == 0 && self.height == 0 {
+            self.width = width;
+        }
+        assert_eq!(self.width, width, "row width does not match the grid");
+        self.height += 1;
+    }
+
+    pub fn truncate(&mut self, height: usize) {
+        self.height = self.height.min(height);
+        self.cells.truncate(self.height * self.width);
+    }
+
+    pub fn remove_rows(&mut self, rows: Range<usize>) {
+        assert!(rows.start <= rows.end && rows.end <= self.height, "rows {:?} are out of the grid", rows);
+        self.cells.drain(rows.start * self.width..rows.end * self.width);
+        self.height -= rows.len();
+    }
+}
+
+fn parse_row<T, F>(row: usize, line: &str, cell: &mut F, cells: &mut Vec<T>) -> Result<(), ParseError>
+where F: FnMut(u8) -> Option<T> {
+    for (col, b) in line.bytes().enumerate() {
+        let value = cell(b).ok_or_else(|| {
+            let (start, c) = line.char_indices().take_while(|&(i, _)| i <= col).last().unwrap_or((col, '?'));
+            ParseError::new(row, line, start..start + c.len_utf8(), format!("unexpected {:?}", c))
+        })?;
+        cells.push(value);
+    }
+    Ok(())
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} is out of the {}x{} grid", pos, self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{:?} is out of the {}x{} grid", pos, height, width))
+    }
+}
+
+/// One line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u8> {
+        Grid::parse(&["123", "456"], |b| b.is_ascii_digit().then(|| b - b'0')).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits();
+        assert_eq!((2, 3), (grid.height(), grid.width()));
+        assert_eq!(6, grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(None, grid.get((0, 3)));
+        let error = Grid::parse(&["123", "45"], Some).unwrap_err();
+        assert_eq!((2, 3, "expected width 3, got 2"), (error.line, error.column, error.message.as_str()));
+        let error = Grid::parse(&["12", "3x"], |b| b.is_ascii_digit().then_some(b)).unwrap_err();
+        assert_eq!((2, 2, "unexpected 'x'"), (error.line, error.column, error.message.as_str()));
+        let error = Grid::parse(&["12", "3é"], Some).unwrap_err();
+        assert_eq!((2, 2, 1, "expected width 2, got 3"), (error.line, error.column, error.len, error.message.as_str()));
+        let grid = Grid::parse_padded(&["ab", "c"], b' ', Some).unwrap();
+        assert_eq!("ab\nc \n", grid.map(|&b| b as char).to_string());
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = digits();
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![2, 5], grid.column(1).copied().collect::<Vec<_>>());
+        assert_eq!(vec![5, 2], grid.column(1).rev().copied().collect::<Vec<_>>());
+        assert_eq!(3, grid.columns().count());
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(Some((1, 0)), grid.position(|&x| x == 4));
+        assert_eq!(((1, 2), &6), grid.iter().last().unwrap());
+        assert_eq!("123\n456\n", grid.to_string());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits();
+        assert_eq!(vec![(0, 1), (1, 0)], grid.neighbours4((0, 0)).collect::<Vec<_>>());
+        assert_eq!(vec![(0, 2), (1, 1), (0, 0)], grid.neighbours4((0, 1)).collect::<Vec<_>>());
+        assert_eq!(Some((0, 1)), grid.step((1, 1), Direction::Up));
+        assert_eq!(None, grid.step((1, 1), Direction::Down));
+        assert_eq!(Some((0, 2)), grid.offset((1, 1), Compass::NE.delta()));
+        assert_eq!(5, grid.neighbours8((1, 1)).count());
+        assert_eq!(3, grid.neighbours8((1, 2)).count());
+    }
+
+    #[test]
+    fn test_rows() {
+        let mut grid = Grid::new(0, 0, 0);
+        grid.push_row([1, 2]);
+        grid.push_row([3, 4]);
+        grid.push_row([5, 6]);
+        grid.remove_rows(0..1);
+        assert_eq!("34\n56\n", grid.to_string());
+        grid.truncate(1);
+        grid.row_mut(0)[1] = 7;
+        assert_eq!("37\n", grid.to_string());
+    }
+}
@@ -0,0 +1,141 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::parse::{self, Line, ParseError};
+
+/// The whole puzzle input in one buffer. The views borrow from it, so reading an input
+/// allocates once instead of once per line.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    pub fn read_file<P: AsRef<Path>>(file_name: P) -> io::Result<Self> {
+        fs::read_to_string(file_name).map(Self::from)
+    }
+
+    pub fn read_stdin() -> io::Result<Self> {
+        let mut text = String::new();
+        io::stdin().lock().read_to_string(&mut text)?;
+        Ok(Self::from(text))
+    }
+
+    /// Joins `lines`, e.g. a generated input.
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Self {
+        let mut text = String::with_capacity(lines.iter().map(|line| line.as_ref().len() + 1).sum());
+        for line in lines {
+            text.push_str(line.as_ref());
+            text.push('\n');
+        }
+        Self { text }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Lines without their `\n` or `\r\n`, every `&[S]` entry point takes them.
+    pub fn lines(&self) -> Vec<&str> {
+        self.text.lines().collect()
+    }
+
+    /// Groups of lines separated by blank lines, see `parse::blocks`.
+    pub fn blocks(&self) -> Vec<Vec<Line<'_>>> {
+        parse::group(self.text.lines().enumerate().map(|(index, text)| Line::new(index, text)))
+    }
+
+    /// Rows of a rectangular grid of bytes, blank lines at the end are left out.
+    pub fn grid(&self) -> Result<Vec<&[u8]>, ParseError> {
+        let mut lines = self.lines();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let width = lines.first().map_or(0, |line| line.len());
+        for (row, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                let span = width.min(line.len())..width.max(line.len());
+                return Err(ParseError::new(row, line, span, format!("expected width {}, got {}", width, line.len())));
+            }
+        }
+        Ok(lines.into_iter().map(str::as_bytes).collect())
+    }
+
+    /// Every integer in the text in order. A `-` right before the digits is a sign unless it
+    /// follows a letter or a digit, so `x=-3` gives -3 and `2-4` gives 2 and 4.
+    /// Numbers that do not fit in an `i64` are skipped.
+    pub fn ints(&self) -> impl Iterator<Item = i64> + '_ {
+        let bytes = self.text.as_bytes();
+        let mut i = 0;
+        std::iter::from_fn(move || {
+            loop {
+                while i < bytes.len() && !bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if i == bytes.len() {
+                    return None;
+                }
+                let signed = i > 0 && bytes[i - 1] == b'-' && (i < 2 || !bytes[i - 2].is_ascii_alphanumeric());
+                let start = if signed { i - 1 } else { i };
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if let Ok(n) = self.text[start..i].parse() {
+                    return Some(n);
+                }
+            }
+        })
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Self { text }
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Self { text: text.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(vec!["1", "2", "", "3"], Input::from("1\n2\r\n\n3").lines());
+        assert_eq!(vec!["a", "b"], Input::from("a\nb\n").lines());
+        assert_eq!(Input::from("a\nb\n"), Input::from_lines(&["a", "b"]));
+        assert!(Input::default().lines().is_empty());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = Input::from("a\nb\n\n\nc\n\n");
+        let blocks = input.blocks();
+        assert_eq!(vec![2, 1], blocks.iter().map(Vec::len).collect::<Vec<_>>());
+        assert_eq!((4, "c"), (blocks[1][0].index, blocks[1][0].text));
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = Input::from("#.\n.#\n\n");
+        assert_eq!(vec![b"#.", b".#"], input.grid().unwrap());
+        let error = Input::from("#.\n.\n").grid().unwrap_err();
+        assert_eq!((2, "expected width 2, got 1"), (error.line, error.message.as_str()));
+        let error = Input::from("12\n3é\n").grid().unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_ints() {
+        let input = Input::from("Sensor at x=-3, y=15: 2-4,6-8\nitems 79, 98 -7 a-1");
+        assert_eq!(vec![-3, 15, 2, 4, 6, 8, 79, 98, -7, 1], input.ints().collect::<Vec<_>>());
+        assert_eq!(vec![1, 2], Input::from("99999999999999999999 1 2").ints().collect::<Vec<_>>());
+        assert_eq!(vec![-5], Input::from("-5").ints().collect::<Vec<_>>());
+    }
+}
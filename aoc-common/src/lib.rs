@@ -0,0 +1,25 @@
+pub mod cli;
+pub mod cycle;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod params;
+pub mod pipeline;
+pub mod parse;
+pub mod priority_queue;
+pub mod render;
+pub mod report;
+pub mod scan;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+
+pub use geometry::{Direction, Point2, Point3};
+pub use grid::Grid;
+pub use input::Input;
+pub use params::Params;
+pub use parse::ParseError;
+pub use priority_queue::{IndexedPriorityQueue, PriorityQueue};
+pub use render::{Cell, FrameSink, Render};
+pub use simulation::{Session, Simulation, Stepper};
+pub use solution::{Part, Runner, Solution, Solver};
@@ -0,0 +1,164 @@
+use crate::parse::{self, ParseError};
+
+/// Tunable puzzle constants: every parameter has a default and can be set by name from text.
+/// Days declare them with `params!`, `()` has none.
+pub trait Params: Default {
+    /// Names and current values.
+    fn values(&self) -> Vec<(&'static str, String)>;
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    /// Defaults with the `name = value` pairs applied in order, later pairs win.
+    fn build(pairs: &[(String, String)]) -> Result<Self, String> {
+        let mut params = Self::default();
+        for (name, value) in pairs {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+impl Params for () {
+    fn values(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(format!("Unknown parameter {}", name))
+    }
+}
+
+/// Declares a parameter struct with public fields, its defaults and its `Params` impl.
+///
+/// ```
+/// aoc_common::params! {
+///     pub struct Markers {
+///         /// Characters of the start-of-packet marker.
+///         packet: usize = 4,
+///         name: String = "root".to_string(),
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident { $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: $ty,)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $($field: $default,)* }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            fn values(&self) -> Vec<(&'static str, String)> {
+                vec![$((stringify!($field), self.$field.to_string()),)*]
+            }
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+                match name {
+                    $(stringify!($field) => {
+                        self.$field = value.parse().map_err(|_| format!("Wrong value {} for {}", value, name))?;
+                    },)*
+                    _ => return Err(format!("Unknown parameter {}", name)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// `name=value` from the command line.
+pub fn parse_pair(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.trim().to_string())),
+        _ => Err(format!("Expected <name>=<value>, got {}", s)),
+    }
+}
+
+/// Parameters of `day` from a config file with a section per day:
+///
+/// ```text
+/// # comments and empty lines are skipped
+/// [day11]
+/// rounds2 = 5000
+/// ```
+pub fn parse_config<S: AsRef<str>>(lines: &[S], day: u8) -> Result<Vec<(String, String)>, ParseError> {
+    let mut section = None;
+    let mut pairs = Vec::new();
+    for line in parse::lines(lines) {
+        let text = line.text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = text.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(|| line.error(text, "expected [dayN]"))?;
+            let number = line.strip_prefix(name, "day")?;
+            section = Some(line.parse::<u8>(number)?);
+            continue;
+        }
+        let (name, value) = line.split_once(text, "=")?;
+        if name.trim().is_empty() {
+            return Err(line.error(name, "expected a parameter name"));
+        }
+        match section {
+            None => return Err(line.error(text, "expected a [dayN] section first")),
+            Some(section) if section == day => pairs.push((name.trim().to_string(), value.trim().to_string())),
+            Some(_) => {},
+        }
+    }
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        struct Test {
+            rounds: usize = 20,
+            name: String = "humn".to_string(),
+        }
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|&(n, v)| (n.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_params() {
+        let params = Test::default();
+        assert_eq!((20, "humn"), (params.rounds, params.name.as_str()));
+        assert_eq!(vec![("rounds", "20".to_string()), ("name", "humn".to_string())], params.values());
+        let params = Test::build(&pairs(&[("rounds", "5"), ("name", "me"), ("rounds", "7")])).unwrap();
+        assert_eq!((7, "me"), (params.rounds, params.name.as_str()));
+        assert_eq!(Err("Wrong value x for rounds".to_string()), Test::build(&pairs(&[("rounds", "x")])));
+        assert_eq!(Err("Unknown parameter size".to_string()), Test::build(&pairs(&[("size", "1")])));
+        assert_eq!(Ok(()), <()>::build(&[]));
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        assert_eq!(Ok(("rounds".to_string(), "5".to_string())), parse_pair("rounds=5"));
+        assert_eq!(Ok(("name".to_string(), "".to_string())), parse_pair("name="));
+        assert!(parse_pair("rounds").is_err());
+        assert!(parse_pair("=5").is_err());
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = ["# variants", "[day11]", "rounds2 = 5000", "", "[day6]", "packet=5", "[day11]", "relief = 2"];
+        assert_eq!(Ok(pairs(&[("rounds2", "5000"), ("relief", "2")])), parse_config(&config, 11));
+        assert_eq!(Ok(pairs(&[("packet", "5")])), parse_config(&config, 6));
+        assert_eq!(Ok(Vec::new()), parse_config(&config, 1));
+        let error = parse_config(&["rounds = 1"], 11).unwrap_err();
+        assert_eq!((1, "expected a [dayN] section first"), (error.line, error.message.as_str()));
+        let error = parse_config(&["[day11]", "rounds 1"], 11).unwrap_err();
+        assert_eq!(2, error.line);
+        let error = parse_config(&["[dayX]"], 11).unwrap_err();
+        assert_eq!((1, 5), (error.line, error.column));
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Malformed input with its position: 1-based line and column of the offending text.
+/// `Display` renders the message with the line and a caret under the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// Length of the offending text in characters.
+    pub len: usize,
+    /// The whole input line, empty past the end of the input.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// `span` is a byte range of the line with 0-based `index`, widened to whole characters.
+    pub fn new<M: Into<String>>(index: usize, text: &str, span: Range<usize>, message: M) -> Self {
+        let mut start = span.start.min(text.len());
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = span.end.clamp(start, text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        Self {
+            line: index + 1,
+            column: text[..start].chars().count() + 1,
+            len: text[start..end].chars().count(),
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Points at `part`, a slice of `text`, or at the whole line when `part` is not part of it.
+    pub fn at<M: Into<String>>(index: usize, text: &str, part: &str, message: M) -> Self {
+        Self::new(index, text, span_of(text, part).unwrap_or(0..text.len()), message)
+    }
+
+    /// Input ended after `count` lines while more were expected.
+    pub fn eof<M: Into<String>>(count: usize, message: M) -> Self {
+        Self::new(count, "", 0..0, message)
+    }
+}
+
+fn span_of(text: &str, part: &str) -> Option<Range<usize>> {
+    let start = (part.as_ptr() as usize).checked_sub(text.as_ptr() as usize)?;
+    (start + part.len() <= text.len()).then(|| start..start + part.len())
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.text.is_empty() && self.len == 0 && self.column == 1 {
+            return write!(f, "line {}: {}", self.line, self.message);
+        }
+        writeln!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(f, "{:gutter$} | {:pad$}{}", "", "", "^".repeat(self.len.max(1)), gutter = gutter, pad = self.column - 1)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// One input line with its 0-based index, for building positioned errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub index: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(index: usize, text: &'a str) -> Self {
+        Self { index, text }
+    }
+
+    /// Error pointing at `part`, a slice of this line.
+    pub fn error<M: Into<String>>(&self, part: &str, message: M) -> ParseError {
+        ParseError::at(self.index, self.text, part, message)
+    }
+
+    /// Parses `part` (a slice of this line) as `T`.
+    pub fn parse<T: FromStr>(&self, part: &str) -> Result<T, ParseError>
+    where T::Err: Display {
+        part.parse().map_err(|e| self.error(part, format!("cannot parse {:?}: {}", part, e)))
+    }
+
+    /// Like `str::split_once` on `part`, an error when `separator` is missing.
+    pub fn split_once(&self, part: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        part.split_once(separator).ok_or_else(|| self.error(part, format!("expected {:?}", separator)))
+    }
+
+    /// The rest of `part` after `prefix`, an error when `part` does not start with it.
+    pub fn strip_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        part.strip_prefix(prefix).ok_or_else(|| {
+            let end = part.char_indices().nth(prefix.chars().count()).map_or(part.len(), |(i, _)| i);
+            self.error(&part[..end], format!("expected {:?}", prefix))
+        })
+    }
+}
+
+/// Numbers the lines of the input.
+pub fn lines<S: AsRef<str>>(lines: &[S]) -> impl DoubleEndedIterator<Item = Line<'_>> + ExactSizeIterator {
+    lines.iter().enumerate().map(|(index, text)| Line::new(index, text.as_ref()))
+}
+
+/// Groups of lines separated by blank lines, with their line numbers. Runs of blank lines and
+/// blank lines at the start or the end give no empty groups.
+pub fn blocks<S: AsRef<str>>(lines: &[S]) -> Vec<Vec<Line<'_>>> {
+    group(self::lines(lines))
+}
+
+pub(crate) fn group<'a, I: Iterator<Item = Line<'a>>>(lines: I) -> Vec<Vec<Line<'a>>> {
+    let mut blocks = Vec::new();
+    let mut block = Vec::new();
+    for line in lines {
+        if !line.text.trim().is_empty() {
+            block.push(line);
+        } else if !block.is_empty() {
+            blocks.push(std::mem::take(&mut block));
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let text = "2-4,6-x";
+        let error = ParseError::at(11, text, &text[6..], "expected a number");
+        assert_eq!((12, 7, 1), (error.line, error.column, error.len));
+        assert_eq!("line 12, column 7: expected a number\n12 | 2-4,6-x\n   |       ^", error.to_string());
+        let error = ParseError::at(0, text, "elsewhere", "wrong line");
+        assert_eq!("line 1, column 1: wrong line\n1 | 2-4,6-x\n  | ^^^^^^^", error.to_string());
+        assert_eq!("line 4: expected a monkey", ParseError::eof(3, "expected a monkey").to_string());
+    }
+
+    #[test]
+    fn test_char_boundaries() {
+        // byte ranges starting or ending inside 'é' cover the whole character
+        let error = ParseError::new(0, "3é4", 2..3, "expected a digit");
+        assert_eq!((2, 1), (error.column, error.len));
+        let error = ParseError::new(0, "3é4", 0..2, "expected a digit");
+        assert_eq!((1, 2), (error.column, error.len));
+    }
+
+    #[test]
+    fn test_line() {
+        let line = Line::new(2, "move 3 from x");
+        let (_, rest) = line.split_once(line.text, " ").unwrap();
+        assert_eq!(Ok(3), line.parse::<u32>(&rest[..1]));
+        let error = line.parse::<u32>(&rest[7..]).unwrap_err();
+        assert_eq!((3, 13, 1), (error.line, error.column, error.len));
+        assert_eq!("cannot parse \"x\": invalid digit found in string", error.message);
+        assert_eq!(8, line.split_once(&rest[2..], ";").unwrap_err().column);
+        assert_eq!(Ok("3 from x"), line.strip_prefix(line.text, "move "));
+        assert_eq!(1, line.strip_prefix(line.text, "go ").unwrap_err().column);
+        assert_eq!(vec![0, 1], lines(&["a", "b"]).map(|l| l.index).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_blocks() {
+        fn texts<'a>(lines: &'a [&str]) -> Vec<Vec<(usize, &'a str)>> {
+            blocks(lines).iter().map(|block| block.iter().map(|l| (l.index, l.text)).collect()).collect()
+        }
+        assert_eq!(vec![vec![(0, "a"), (1, "b")], vec![(3, "c")]], texts(&["a", "b", "", "c"]));
+        assert_eq!(vec![vec![(1, "a")], vec![(4, "b")]], texts(&["", "a", "", " ", "b", "", ""]));
+        assert!(texts(&["", ""]).is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+use std::any::Any;
+use std::io::{self, BufRead};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// Why a pipeline stopped before its source ran out.
+#[derive(thiserror::Error, Debug)]
+pub enum Error<E> {
+    #[error("Cannot read input: {0}")]
+    Read(io::Error),
+    #[error("{0}")]
+    Stage(E),
+    #[error("A pipeline stage panicked: {0}")]
+    Panic(String),
+}
+
+/// Message of a caught panic, the payload of `panic!` is a `&str` or a `String`.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "panic without a message".to_string(),
+        },
+    }
+}
+
+type Item<T, E> = Result<T, Error<E>>;
+
+/// Items streaming through stages that run on their own threads, connected by channels holding
+/// at most `capacity` items each, so a pipeline runs in constant memory whatever the input size.
+/// Errors and panics travel downstream as items; the sink stops at the first one and closes its
+/// channel, which makes every stage upstream stop at its next send.
+pub struct Pipeline<T, E> {
+    receiver: Receiver<Item<T, E>>,
+    threads: Vec<JoinHandle<()>>,
+    capacity: usize,
+}
+
+impl<T: Send + 'static, E: Send + 'static> Pipeline<T, E> {
+    /// Items of `source`, produced on their own thread.
+    pub fn new<I>(capacity: usize, source: I) -> Self
+    where I: IntoIterator<Item = T>, I::IntoIter: Send + 'static {
+        Self::spawn_source(capacity, source.into_iter().map(Ok))
+    }
+
+    fn spawn_source<I>(capacity: usize, source: I) -> Self
+    where I: Iterator<Item = Item<T, E>> + Send + 'static {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let thread = thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                for item in source {
+                    if !forward(&sender, item) {
+                        break;
+                    }
+                }
+            }));
+            if let Err(payload) = result {
+                let _ = sender.send(Err(Error::Panic(panic_message(payload))));
+            }
+        });
+        Self { receiver, threads: vec![thread], capacity }
+    }
+
+    /// Items of all `pipelines` in the order they arrive, each keeps its own order.
+    /// One thread per pipeline forwards its items, so a slow one does not hold up the others.
+    pub fn merge<I: IntoIterator<Item = Self>>(capacity: usize, pipelines: I) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let mut threads = Vec::new();
+        for pipeline in pipelines {
+            let (input, sender) = (pipeline.receiver, sender.clone());
+            threads.extend(pipeline.threads);
+            threads.push(thread::spawn(move || {
+                for item in input.iter() {
+                    if !forward(&sender, item) {
+                        break;
+                    }
+                }
+            }));
+        }
+        Self { receiver, threads, capacity }
+    }
+
+    /// Runs `f` on every item on `workers` threads, items come out in any order when there are several.
+    pub fn map<U, F>(self, workers: usize, f: F) -> Pipeline<U, E>
+    where U: Send + 'static, F: Fn(T) -> Result<U, E> + Send + Sync + 'static {
+        self.filter_map(workers, move |item| f(item).map(Some))
+    }
+
+    /// Like `map`, items for which `f` returns `None` are dropped.
+    pub fn filter_map<U, F>(self, workers: usize, f: F) -> Pipeline<U, E>
+    where U: Send + 'static, F: Fn(T) -> Result<Option<U>, E> + Send + Sync + 'static {
+        let (sender, receiver) = mpsc::sync_channel(self.capacity);
+        let input = Arc::new(Mutex::new(self.receiver));
+        let f = Arc::new(f);
+        let mut threads = self.threads;
+        for _ in 0..workers.max(1) {
+            let (input, f, sender) = (input.clone(), f.clone(), sender.clone());
+            threads.push(thread::spawn(move || loop {
+                // the lock is only held while waiting, never while `f` runs
+                let Ok(item) = input.lock().unwrap().recv() else { break };
+                let item = match item.map(|item| panic::catch_unwind(AssertUnwindSafe(|| f(item)))) {
+                    Ok(Ok(Ok(None))) => continue,
+                    Ok(Ok(Ok(Some(item)))) => Ok(item),
+                    Ok(Ok(Err(error))) => Err(Error::Stage(error)),
+                    Ok(Err(payload)) => Err(Error::Panic(panic_message(payload))),
+                    Err(error) => Err(error),
+                };
+                if !forward(&sender, item) {
+                    break;
+                }
+            }));
+        }
+        Pipeline { receiver, threads, capacity: self.capacity }
+    }
+
+    /// A stage on one thread that keeps `state` across items in their order, e.g. to group lines
+    /// into records: `step` may emit an item for every input item and `finish` one at the end.
+    pub fn stage<S, U, F, G>(self, mut state: S, mut step: F, finish: G) -> Pipeline<U, E>
+    where
+        S: Send + 'static,
+        U: Send + 'static,
+        F: FnMut(&mut S, T) -> Result<Option<U>, E> + Send + 'static,
+        G: FnOnce(S) -> Result<Option<U>, E> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel(self.capacity);
+        let input = self.receiver;
+        let mut threads = self.threads;
+        threads.push(thread::spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                for item in input.iter() {
+                    let item = match item.and_then(|item| step(&mut state, item).map_err(Error::Stage)) {
+                        Ok(None) => continue,
+                        Ok(Some(item)) => Ok(item),
+                        Err(error) => Err(error),
+                    };
+                    if !forward(&sender, item) {
+                        return;
+                    }
+                }
+                if let Some(item) = finish(state).map_err(Error::Stage).transpose() {
+                    forward(&sender, item);
+                }
+            }));
+            if let Err(payload) = result {
+                let _ = sender.send(Err(Error::Panic(panic_message(payload))));
+            }
+        }));
+        Pipeline { receiver, threads, capacity: self.capacity }
+    }
+
+    /// Folds the items on the calling thread, stops the pipeline at the first error.
+    pub fn fold<A, F>(self, init: A, mut f: F) -> Result<A, Error<E>>
+    where F: FnMut(A, T) -> Result<A, E> {
+        let Pipeline { receiver, threads, .. } = self;
+        let mut result = Ok(init);
+        for item in receiver.iter() {
+            result = match (result, item) {
+                (Ok(acc), Ok(item)) => f(acc, item).map_err(Error::Stage),
+                (_, Err(error)) | (Err(error), _) => Err(error),
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+        // upstream stages notice the closed channel at their next send and stop
+        drop(receiver);
+        for thread in threads {
+            thread.join().expect("pipeline threads catch their panics");
+        }
+        result
+    }
+
+    pub fn collect(self) -> Result<Vec<T>, Error<E>> {
+        self.fold(Vec::new(), |mut items, item| {
+            items.push(item);
+            Ok(items)
+        })
+    }
+}
+
+impl<E: Send + 'static> Pipeline<(usize, String), E> {
+    /// Lines of `reader` with their 0-based index.
+    pub fn lines<R: BufRead + Send + 'static>(capacity: usize, reader: R) -> Self {
+        Self::spawn_source(capacity, reader.lines().enumerate().map(|(index, line)| line.map(|line| (index, line)).map_err(Error::Read)))
+    }
+}
+
+/// Sends `item` on, false when the pipeline should stop: the item is an error or nobody listens any more.
+fn forward<T, E>(sender: &SyncSender<Item<T, E>>, item: Item<T, E>) -> bool {
+    let failed = item.is_err();
+    sender.send(item).is_ok() && !failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{Line, ParseError};
+
+    #[test]
+    fn test_map() {
+        let lines = Pipeline::<_, ParseError>::lines(4, "1\n2\n\n30\n".as_bytes());
+        let numbers = lines.filter_map(3, |(index, text)| {
+            let line = Line::new(index, &text);
+            (!text.is_empty()).then(|| line.parse::<u32>(&text)).transpose()
+        });
+        let mut numbers = numbers.collect().unwrap();
+        numbers.sort();
+        assert_eq!(vec![1, 2, 30], numbers);
+    }
+
+    #[test]
+    fn test_stage() {
+        // sums of runs separated by zeros, in order
+        let sums = Pipeline::<_, ()>::new(2, [1, 2, 0, 3, 0, 4, 5])
+            .stage(0, |sum, x| Ok(if x == 0 { Some(std::mem::take(sum)) } else { *sum += x; None }), |sum| Ok(Some(sum)))
+            .map(2, |sum| Ok(sum * 10))
+            .fold(Vec::new(), |mut sums, sum| { sums.push(sum); Ok(sums) });
+        let mut sums = sums.unwrap();
+        sums.sort();
+        assert_eq!(vec![30, 30, 90], sums);
+    }
+
+    #[test]
+    fn test_merge() {
+        let sources = (0..4).map(|i| Pipeline::<_, String>::new(1, (0..100).map(move |x| (i, x))));
+        let items = Pipeline::merge(2, sources).collect().unwrap();
+        assert_eq!(400, items.len());
+        for i in 0..4 {
+            assert!(items.iter().filter(|(j, _)| *j == i).map(|(_, x)| *x).eq(0..100));
+        }
+        assert!(Pipeline::<u8, String>::merge(2, []).collect().unwrap().is_empty());
+        let sources = [Pipeline::new(1, 0..), Pipeline::new(1, 0..).map(1, |x: u64| if x == 50 { Err(x) } else { Ok(x) })];
+        assert!(matches!(Pipeline::merge(2, sources).collect(), Err(Error::Stage(50))));
+    }
+
+    #[test]
+    fn test_errors() {
+        // the source never ends, the pipeline has to shut it down
+        let result = Pipeline::new(8, 0..).map(4, |x: u64| if x == 1000 { Err(x) } else { Ok(x) }).fold(0, |_, x| Ok(x));
+        assert!(matches!(result, Err(Error::Stage(1000))));
+        let result = Pipeline::<_, String>::new(8, 0..).map(2, |x: u64| if x == 5 { panic!("five") } else { Ok(x) }).collect();
+        assert_eq!("A pipeline stage panicked: five", result.unwrap_err().to_string());
+        let result = Pipeline::<_, String>::new(8, 0..10).fold(0, |sum, x| if x < 3 { Ok(sum + x) } else { Err(format!("{}", x)) });
+        assert_eq!("3", result.unwrap_err().to_string());
+    }
+}
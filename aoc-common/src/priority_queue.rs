@@ -0,0 +1,433 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::slice::Iter;
+
+/// Binary heap ordered by a comparator, `peek` and `pop` give the least element.
+/// A bounded queue keeps only the `k` greatest elements pushed so far: once it is full,
+/// a push replaces the least element when the new one is greater and is dropped otherwise.
+#[derive(Clone)]
+pub struct PriorityQueue<T, C = fn(&T, &T) -> Ordering> {
+    data: Vec<T>,
+    compare: C,
+    bound: Option<usize>,
+}
+
+impl<T: Ord> PriorityQueue<T> {
+    pub fn new() -> Self {
+        Self::by(T::cmp)
+    }
+
+    /// Keeps only the `k` greatest elements.
+    pub fn with_capacity_bounded(k: usize) -> Self {
+        Self::new().bounded(k)
+    }
+}
+
+impl<T> PriorityQueue<T> {
+    /// Ordered by `key`, the element with the least key comes first.
+    pub fn by_key<K: Ord, F: Fn(&T) -> K>(key: F) -> PriorityQueue<T, impl Fn(&T, &T) -> Ordering> {
+        PriorityQueue::by(move |a: &T, b: &T| key(a).cmp(&key(b)))
+    }
+}
+
+impl<T: Ord> Default for PriorityQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C: Fn(&T, &T) -> Ordering> PriorityQueue<T, C> {
+    /// Ordered by `compare`, the least element comes first.
+    pub fn by(compare: C) -> Self {
+        Self { data: Vec::new(), compare, bound: None }
+    }
+
+    /// The same queue keeping only its `k` greatest elements.
+    pub fn bounded(mut self, k: usize) -> Self {
+        while self.data.len() > k {
+            self.pop();
+        }
+        self.bound = Some(k);
+        self
+    }
+
+    /// Number of elements a bounded queue keeps.
+    pub fn bound(&self) -> Option<usize> {
+        self.bound
+    }
+
+    pub fn push(&mut self, x: T) {
+        if let Some(bound) = self.bound.filter(|&bound| self.data.len() >= bound) {
+            if bound > 0 && (self.compare)(&x, &self.data[0]) == Ordering::Greater {
+                self.data[0] = x;
+                self.sift_down(0);
+            }
+            return;
+        }
+        self.data.push(x);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let x = self.data.swap_remove(0);
+        self.sift_down(0);
+        Some(x)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// The elements in no particular order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// The elements from the least to the greatest.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        self.data.sort_by(&self.compare);
+        self.data
+    }
+
+    fn less(&self, i: usize, j: usize) -> bool {
+        (self.compare)(&self.data[i], &self.data[j]) == Ordering::Less
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.less(i, parent) {
+                break;
+            }
+            self.data.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut least = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.data.len() && self.less(child, least) {
+                    least = child;
+                }
+            }
+            if least == i {
+                break;
+            }
+            self.data.swap(i, least);
+            i = least;
+        }
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for PriorityQueue<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriorityQueue").field("data", &self.data).field("bound", &self.bound).finish()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PriorityQueue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T, C: Fn(&T, &T) -> Ordering> Extend<T> for PriorityQueue<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// The elements in no particular order.
+impl<T, C> IntoIterator for PriorityQueue<T, C> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a PriorityQueue<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+/// Refers to an element of an `IndexedPriorityQueue` until it is popped or removed, a stale handle finds nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Slot<T, P> {
+    entry: Option<(T, P)>,
+    /// Index of the slot in the heap while it holds an entry.
+    position: usize,
+    generation: u32,
+}
+
+/// Binary heap of values with priorities that can be changed or removed through the handle `push` returns,
+/// `peek` and `pop` give the value with the least priority.
+#[derive(Debug, Clone)]
+pub struct IndexedPriorityQueue<T, P> {
+    /// Slots in heap order.
+    heap: Vec<usize>,
+    slots: Vec<Slot<T, P>>,
+    free: Vec<usize>,
+}
+
+impl<T, P: Ord> Default for IndexedPriorityQueue<T, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, P: Ord> IndexedPriorityQueue<T, P> {
+    pub fn new() -> Self {
+        Self { heap: Vec::new(), slots: Vec::new(), free: Vec::new() }
+    }
+
+    pub fn push(&mut self, value: T, priority: P) -> Handle {
+        let position = self.heap.len();
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].entry = Some((value, priority));
+                self.slots[slot].position = position;
+                slot
+            }
+            None => {
+                self.slots.push(Slot { entry: Some((value, priority)), position, generation: 0 });
+                self.slots.len() - 1
+            }
+        };
+        self.heap.push(slot);
+        self.sift_up(position);
+        Handle { slot, generation: self.slots[slot].generation }
+    }
+
+    pub fn pop(&mut self) -> Option<(T, P)> {
+        (!self.heap.is_empty()).then(|| self.remove_at(0))
+    }
+
+    pub fn peek(&self) -> Option<(&T, &P)> {
+        self.heap.first().map(|&slot| self.entry(slot))
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<(&T, &P)> {
+        self.slot(handle).map(|slot| self.entry(slot))
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.slot(handle).is_some()
+    }
+
+    /// Sets the priority of the element, returns the old one or `None` for a stale handle.
+    pub fn change_priority(&mut self, handle: Handle, priority: P) -> Option<P> {
+        let slot = self.slot(handle)?;
+        let (_, old) = self.slots[slot].entry.as_mut().unwrap();
+        let lower = priority < *old;
+        let old = std::mem::replace(old, priority);
+        let position = self.slots[slot].position;
+        if lower {
+            self.sift_up(position);
+        } else {
+            self.sift_down(position);
+        }
+        Some(old)
+    }
+
+    /// Lowers the priority of the element, false when `priority` is not lower or the handle is stale.
+    pub fn decrease_key(&mut self, handle: Handle, priority: P) -> bool {
+        match self.get(handle) {
+            Some((_, old)) if priority < *old => self.change_priority(handle, priority).is_some(),
+            _ => false,
+        }
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> Option<(T, P)> {
+        let slot = self.slot(handle)?;
+        Some(self.remove_at(self.slots[slot].position))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// The values with their priorities in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &P)> + '_ {
+        self.heap.iter().map(|&slot| self.entry(slot))
+    }
+
+    fn slot(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot)?;
+        (slot.generation == handle.generation && slot.entry.is_some()).then_some(handle.slot)
+    }
+
+    fn entry(&self, slot: usize) -> (&T, &P) {
+        let (value, priority) = self.slots[slot].entry.as_ref().unwrap();
+        (value, priority)
+    }
+
+    fn priority(&self, position: usize) -> &P {
+        self.entry(self.heap[position]).1
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.slots[self.heap[i]].position = i;
+        self.slots[self.heap[j]].position = j;
+    }
+
+    /// Takes the element at heap `position` out, its handle goes stale.
+    fn remove_at(&mut self, position: usize) -> (T, P) {
+        let last = self.heap.len() - 1;
+        self.swap(position, last);
+        let slot = self.heap.pop().unwrap();
+        let removed = &mut self.slots[slot];
+        removed.generation = removed.generation.wrapping_add(1);
+        let entry = removed.entry.take().unwrap();
+        self.free.push(slot);
+        if position < self.heap.len() {
+            self.sift_down(position);
+            self.sift_up(position);
+        }
+        entry
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.priority(i) >= self.priority(parent) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut least = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.priority(child) < self.priority(least) {
+                    least = child;
+                }
+            }
+            if least == i {
+                break;
+            }
+            self.swap(i, least);
+            i = least;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order() {
+        let mut pq = PriorityQueue::new();
+        for x in [5, 3, 8, 1, 9, 2, 7] {
+            pq.push(x);
+        }
+        assert_eq!((7, Some(&1)), (pq.len(), pq.peek()));
+        let popped = std::iter::from_fn(|| pq.pop()).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 3, 5, 7, 8, 9], popped);
+        assert!(pq.is_empty());
+        assert_eq!(None, pq.peek());
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut pq = PriorityQueue::with_capacity_bounded(3);
+        pq.extend([4, 9, 1, 7, 7, 3, 8]);
+        assert_eq!((3, Some(3)), (pq.len(), pq.bound()));
+        assert_eq!(vec![7, 8, 9], pq.into_sorted_vec());
+        let mut empty = PriorityQueue::with_capacity_bounded(0);
+        empty.push(1);
+        assert!(empty.is_empty());
+        let pq = (1..=10).collect::<PriorityQueue<_>>().bounded(2);
+        assert_eq!(vec![9, 10], pq.into_sorted_vec());
+        let pq = (1..=3).collect::<PriorityQueue<_>>().bounded(usize::MAX);
+        assert_eq!(vec![1, 2, 3], pq.into_sorted_vec());
+    }
+
+    #[test]
+    fn test_comparator() {
+        let mut pq = PriorityQueue::by(|a: &i32, b: &i32| b.cmp(a));
+        pq.extend([2, 5, 1]);
+        assert_eq!(Some(5), pq.pop());
+        let mut pq = PriorityQueue::by_key(|s: &&str| s.len()).bounded(2);
+        pq.extend(["ccc", "a", "dddd", "bb"]);
+        assert_eq!(vec!["ccc", "dddd"], pq.into_sorted_vec());
+    }
+
+    #[test]
+    fn test_iter() {
+        let pq = [7, 9, 8].into_iter().collect::<PriorityQueue<_>>();
+        let mut items = pq.iter().copied().collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(vec![7, 8, 9], items);
+        assert_eq!(3, (&pq).into_iter().count());
+        assert_eq!(vec![7, 8, 9], pq.clone().into_sorted_vec());
+        assert_eq!(3, pq.into_iter().count());
+    }
+
+    #[test]
+    fn test_indexed() {
+        let mut queue = IndexedPriorityQueue::new();
+        let a = queue.push("a", 5);
+        let b = queue.push("b", 3);
+        let c = queue.push("c", 8);
+        assert_eq!(Some((&"b", &3)), queue.peek());
+        assert!(queue.decrease_key(c, 1));
+        assert!(!queue.decrease_key(a, 6));
+        assert_eq!(Some((&"c", &1)), queue.peek());
+        assert_eq!(Some(1), queue.change_priority(c, 9));
+        assert_eq!(Some(("b", 3)), queue.remove(b));
+        assert_eq!((2, false, None), (queue.len(), queue.contains(b), queue.remove(b)));
+        assert_eq!(Some(("a", 5)), queue.pop());
+        assert_eq!(Some((&"c", &9)), queue.get(c));
+        assert_eq!(vec![(&"c", &9)], queue.iter().collect::<Vec<_>>());
+        assert_eq!(Some(("c", 9)), queue.pop());
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.pop());
+    }
+
+    #[test]
+    fn test_stale_handle() {
+        let mut queue = IndexedPriorityQueue::new();
+        let a = queue.push('a', 1);
+        queue.pop();
+        let b = queue.push('b', 2);
+        assert_eq!((None, Some((&'b', &2))), (queue.get(a), queue.get(b)));
+        assert_eq!(None, queue.change_priority(a, 0));
+        assert!(!queue.decrease_key(a, 0));
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// Colours shared by the days so that the same things look the same in every picture.
+pub mod palette {
+    use super::Rgb;
+
+    pub const BLACK: Rgb = [0x10, 0x10, 0x18];
+    pub const AIR: Rgb = [0x1e, 0x22, 0x2e];
+    pub const ROCK: Rgb = [0x70, 0x68, 0x60];
+    pub const WALL: Rgb = [0xa0, 0xa0, 0xa8];
+    pub const SAND: Rgb = [0xe8, 0xc0, 0x60];
+    pub const GROUND: Rgb = [0x5a, 0x40, 0x2a];
+    pub const ELF: Rgb = [0x40, 0xc0, 0x50];
+    pub const ACTIVE: Rgb = [0xf0, 0x50, 0x40];
+    pub const ICE: Rgb = [0x80, 0xc8, 0xf0];
+    pub const LIT: Rgb = [0xff, 0xb0, 0x30];
+}
+
+/// A cell of a picture, each cell type picks its own palette.
+pub trait Cell {
+    /// Character used for ASCII frames.
+    fn glyph(&self) -> char;
+    /// Colour of the cell in image frames.
+    fn colour(&self) -> Rgb;
+}
+
+/// State that can be drawn as a grid of cells, row 0 at the top.
+pub trait Render {
+    type Cell: Cell;
+
+    fn render(&self) -> Grid<Self::Cell>;
+}
+
+impl<C: Cell + Clone> Render for Grid<C> {
+    type Cell = C;
+
+    fn render(&self) -> Grid<C> {
+        self.clone()
+    }
+}
+
+/// One line per row, every line ends with a newline.
+pub fn ascii<C: Cell>(frame: &Grid<C>) -> String {
+    let mut out = String::with_capacity(frame.height() * (frame.width() + 1));
+    for row in frame.rows() {
+        out.extend(row.iter().map(Cell::glyph));
+        out.push('\n');
+    }
+    out
+}
+
+/// Like `ascii`, every glyph in its colour with ANSI 24-bit escapes.
+pub fn ansi<C: Cell>(frame: &Grid<C>) -> String {
+    let mut out = String::new();
+    for row in frame.rows() {
+        let mut last = None;
+        for cell in row {
+            let colour = cell.colour();
+            if last != Some(colour) {
+                out.push_str(&format!("\x1b[38;2;{};{};{}m", colour[0], colour[1], colour[2]));
+                last = Some(colour);
+            }
+            out.push(cell.glyph());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Binary PPM (P6), every cell is drawn as a `scale` x `scale` square.
+pub fn ppm<C: Cell>(frame: &Grid<C>, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (height, width) = (frame.height() * scale, frame.width() * scale);
+    let mut out = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    out.reserve(height * width * 3);
+    for row in frame.rows() {
+        let line: Vec<u8> = row.iter()
+            .flat_map(|cell| std::iter::repeat_n(cell.colour(), scale))
+            .flatten()
+            .collect();
+        for _ in 0..scale {
+            out.extend_from_slice(&line);
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameFormat {
+    Ascii,
+    #[default]
+    Ppm,
+}
+
+impl FrameFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FrameFormat::Ascii => "txt",
+            FrameFormat::Ppm => "ppm",
+        }
+    }
+
+    pub fn encode<C: Cell>(&self, frame: &Grid<C>, scale: usize) -> Vec<u8> {
+        match self {
+            FrameFormat::Ascii => ascii(frame).into_bytes(),
+            FrameFormat::Ppm => ppm(frame, scale),
+        }
+    }
+}
+
+impl FromStr for FrameFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(FrameFormat::Ascii),
+            "ppm" => Ok(FrameFormat::Ppm),
+            _ => Err(format!("Unknown frame format {}", s)),
+        }
+    }
+}
+
+/// Writes numbered frames `frame_00000.ppm`, `frame_00001.ppm`, ... to a directory.
+/// Simulations push frames without handling errors, the first write error is kept
+/// and returned by `finish`, after it nothing more is written.
+#[derive(Debug)]
+pub struct FrameSink {
+    dir: PathBuf,
+    format: FrameFormat,
+    scale: usize,
+    every: usize,
+    pushed: usize,
+    written: usize,
+    error: Option<io::Error>,
+}
+
+impl FrameSink {
+    /// Creates the directory when it does not exist yet.
+    pub fn new<P: AsRef<Path>>(dir: P, format: FrameFormat) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self { dir: dir.as_ref().to_path_buf(), format, scale: 4, every: 1, pushed: 0, written: 0, error: None })
+    }
+
+    /// Size in pixels of one cell in image frames.
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Keeps only every n-th pushed frame, starting with the first one.
+    pub fn with_every(mut self, every: usize) -> Self {
+        self.every = every.max(1);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn push<R: Render + ?Sized>(&mut self, state: &R) {
+        let keep = self.pushed.is_multiple_of(self.every);
+        self.pushed += 1;
+        if keep && self.error.is_none() {
+            let path = self.dir.join(format!("frame_{:05}.{}", self.written, self.format.extension()));
+            match fs::write(path, self.format.encode(&state.render(), self.scale)) {
+                Ok(()) => self.written += 1,
+                Err(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    /// Number of written frames, or the first write error.
+    pub fn finish(self) -> io::Result<usize> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.written),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    struct Bit(bool);
+
+    impl Cell for Bit {
+        fn glyph(&self) -> char {
+            if self.0 { '#' } else { '.' }
+        }
+        fn colour(&self) -> Rgb {
+            if self.0 { [255, 0, 0] } else { [0, 0, 255] }
+        }
+    }
+
+    fn frame() -> Grid<Bit> {
+        Grid::parse(&["#.", ".#", "##"], |c| Some(Bit(c == b'#'))).unwrap()
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert_eq!("#.\n.#\n##\n", ascii(&frame()));
+    }
+
+    #[test]
+    fn test_ansi() {
+        let frame = Grid::parse(&["##."], |c| Some(Bit(c == b'#'))).unwrap();
+        assert_eq!("\x1b[38;2;255;0;0m##\x1b[38;2;0;0;255m.\x1b[0m\n", ansi(&frame));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = ppm(&frame(), 1);
+        let header = b"P6\n2 3\n255\n";
+        assert_eq!(header, &image[..header.len()]);
+        assert_eq!(&[255, 0, 0, 0, 0, 255], &image[header.len()..header.len() + 6]);
+        assert_eq!(header.len() + 2 * 3 * 3, image.len());
+        let image = ppm(&frame(), 2);
+        assert!(image.starts_with(b"P6\n4 6\n255\n"));
+        assert_eq!(b"P6\n4 6\n255\n".len() + 4 * 6 * 3, image.len());
+    }
+
+    #[test]
+    fn test_frame_format() {
+        assert_eq!(Ok(FrameFormat::Ascii), "ascii".parse());
+        assert_eq!(Ok(FrameFormat::Ppm), "ppm".parse());
+        assert!("png".parse::<FrameFormat>().is_err());
+    }
+
+    #[test]
+    fn test_frame_sink() {
+        let dir = std::env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        let mut sink = FrameSink::new(&dir, FrameFormat::Ascii).unwrap().with_every(2);
+        for _ in 0..5 {
+            sink.push(&frame());
+        }
+        assert_eq!(3, sink.finish().unwrap());
+        assert_eq!("#.\n.#\n##\n", fs::read_to_string(dir.join("frame_00002.txt")).unwrap());
+        assert!(!dir.join("frame_00003.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::solution::Part;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format {}", s)),
+        }
+    }
+}
+
+/// Outcome of one part of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    /// The answer, or the error message when parsing or solving failed.
+    pub answer: Result<String, String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Writes records one by one, JSON is written as one object per line.
+/// Failed parts are left out of the text format, they are reported on stderr.
+pub struct Reporter<W: Write> {
+    format: Format,
+    out: W,
+    header_written: bool,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, out: W) -> Self {
+        Self { format, out, header_written: false }
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        match self.format {
+            Format::Text => self.write_text(record),
+            Format::Json => writeln!(self.out, "{}", to_json(record)),
+            Format::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "day,part,status,answer,parse_ns,solve_ns")?;
+                    self.header_written = true;
+                }
+                writeln!(self.out, "{}", to_csv(record))
+            },
+        }
+    }
+
+    fn write_text(&mut self, record: &Record) -> io::Result<()> {
+        match &record.answer {
+            Ok(answer) => {
+                let timing = format!("parse {:?}, solve {:?}", record.parse_time, record.solve_time);
+                if answer.contains('\n') {
+                    writeln!(self.out, "day {} part {} ({}):\n{}", record.day, record.part, timing, answer.trim_end())
+                } else {
+                    writeln!(self.out, "day {} part {}: {} ({})", record.day, record.part, answer, timing)
+                }
+            },
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => { let _ = write!(result, "\\u{:04x}", c as u32); },
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn to_json(record: &Record) -> String {
+    let (status, key, value) = match &record.answer {
+        Ok(answer) => ("ok", "answer", answer),
+        Err(error) => ("error", "error", error),
+    };
+    format!(
+        "{{\"day\":{},\"part\":{},\"status\":\"{}\",\"{}\":{},\"parse_ns\":{},\"solve_ns\":{}}}",
+        record.day, record.part, status, key, json_string(value),
+        record.parse_time.as_nanos(), record.solve_time.as_nanos()
+    )
+}
+
+pub fn to_csv(record: &Record) -> String {
+    let (status, value) = match &record.answer {
+        Ok(answer) => ("ok", answer),
+        Err(error) => ("error", error),
+    };
+    format!("{},{},{},{},{},{}", record.day, record.part, status, csv_field(value),
+        record.parse_time.as_nanos(), record.solve_time.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Result<&str, &str>) -> Record {
+        Record {
+            day: 10,
+            part: Part::Two,
+            answer: answer.map(String::from).map_err(String::from),
+            parse_time: Duration::from_nanos(1500),
+            solve_time: Duration::from_micros(2),
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            r##"{"day":10,"part":2,"status":"ok","answer":"#.\n\"x\"","parse_ns":1500,"solve_ns":2000}"##,
+            to_json(&record(Ok("#.\n\"x\"")))
+        );
+        assert_eq!(
+            r#"{"day":10,"part":2,"status":"error","error":"bad","parse_ns":1500,"solve_ns":2000}"#,
+            to_json(&record(Err("bad")))
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut reporter = Reporter::new(Format::Csv, Vec::new());
+        reporter.write(&record(Ok("1,2"))).unwrap();
+        reporter.write(&record(Ok("x"))).unwrap();
+        assert_eq!(
+            "day,part,status,answer,parse_ns,solve_ns\n10,2,ok,\"1,2\",1500,2000\n10,2,ok,x,1500,2000\n",
+            String::from_utf8(reporter.out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let mut reporter = Reporter::new(Format::Text, Vec::new());
+        reporter.write(&record(Ok("42"))).unwrap();
+        reporter.write(&record(Err("bad"))).unwrap();
+        reporter.write(&record(Ok("#.\n.#\n"))).unwrap();
+        assert_eq!(
+            "day 10 part 2: 42 (parse 1.5µs, solve 2µs)\nday 10 part 2 (parse 1.5µs, solve 2µs):\n#.\n.#\n",
+            String::from_utf8(reporter.out).unwrap()
+        );
+    }
+}
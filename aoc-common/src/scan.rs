@@ -0,0 +1,447 @@
+use std::fmt::Write as _;
+
+use crate::parse::{Line, ParseError};
+
+/// One piece of a compiled pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Literal(String),
+    /// Runs up to the first of `until`, or to the end of the text when `to_end` and none is found.
+    /// A repeated field is split on its separator.
+    Field { separator: Option<String>, until: Vec<String>, to_end: bool },
+    Alternatives(Vec<String>),
+    Optional(Vec<Item>),
+}
+
+/// Literals that can start a sequence and whether it can match nothing, `None` when it starts with a field.
+type First = Option<(Vec<String>, bool)>;
+
+/// A line pattern in the spirit of `scanf`:
+/// - `{}` is a field, it runs up to the first text that can follow it;
+/// - `{*, }` is a field repeated with the separator `, `;
+/// - `{+|-}` matches one of the alternatives and captures it;
+/// - `{? ...}` is an optional segment, present when its first literal or alternative matches;
+///   its fields are `None` when it is absent;
+/// - `{{` and `}}` are literal braces, everything else matches itself.
+///
+/// Matching never backtracks, so a field must be followed by a literal, an alternative or the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    items: Vec<Item>,
+    fields: usize,
+}
+
+impl Pattern {
+    /// Panics on a malformed pattern, patterns are part of the code.
+    pub fn new(pattern: &str) -> Self {
+        let mut items = compile(pattern);
+        resolve(&mut items, Some((Vec::new(), true)), pattern);
+        let fields = count(&items);
+        Self { items, fields }
+    }
+
+    /// Number of captured fields.
+    pub fn fields(&self) -> usize {
+        self.fields
+    }
+
+    /// Text of every field of `part`, a slice of `line`.
+    pub fn captures<'a>(&self, line: &Line<'a>, part: &'a str) -> Result<Vec<Capture<'a>>, ParseError> {
+        let mut scanner = Scanner { line, text: part, captures: Vec::with_capacity(self.fields) };
+        let end = scanner.items(&self.items, 0)?;
+        if end < part.len() {
+            return Err(line.error(&part[end..], "expected the end of the line"));
+        }
+        Ok(scanner.captures)
+    }
+
+    /// Typed fields of the whole line.
+    pub fn scan<'a, T: Fields<'a>>(&self, line: &Line<'a>) -> Result<T, ParseError> {
+        self.scan_part(line, line.text)
+    }
+
+    /// Typed fields of `part`, a slice of `line`.
+    pub fn scan_part<'a, T: Fields<'a>>(&self, line: &Line<'a>, part: &'a str) -> Result<T, ParseError> {
+        T::from_captures(line, self.captures(line, part)?)
+    }
+}
+
+fn compile(pattern: &str) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut literal = String::new();
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            literal.push(c);
+            rest = &rest[2..];
+            continue;
+        }
+        match c {
+            '{' => {
+                let end = closing(rest).unwrap_or_else(|| panic!("{:?}: unclosed '{{'", pattern));
+                if !literal.is_empty() {
+                    items.push(Item::Literal(std::mem::take(&mut literal)));
+                }
+                items.push(segment(&rest[1..end], pattern));
+                rest = &rest[end + 1..];
+            }
+            '}' => panic!("{:?}: unmatched '}}'", pattern),
+            _ => {
+                literal.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !literal.is_empty() {
+        items.push(Item::Literal(literal));
+    }
+    items
+}
+
+/// Index of the brace closing the one `text` starts with.
+fn closing(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn segment(inside: &str, pattern: &str) -> Item {
+    if inside.is_empty() {
+        Item::Field { separator: None, until: Vec::new(), to_end: false }
+    } else if let Some(optional) = inside.strip_prefix('?') {
+        Item::Optional(compile(optional))
+    } else if let Some(separator) = inside.strip_prefix('*') {
+        assert!(!separator.is_empty(), "{:?}: a repeated field needs a separator", pattern);
+        Item::Field { separator: Some(separator.to_string()), until: Vec::new(), to_end: false }
+    } else {
+        Item::Alternatives(inside.split('|').map(String::from).collect())
+    }
+}
+
+/// Sets what ends every field of `items`, `follow` is what can come after them.
+fn resolve(items: &mut [Item], follow: First, pattern: &str) {
+    let mut next = follow;
+    for item in items.iter_mut().rev() {
+        next = match item {
+            Item::Literal(text) => Some((vec![text.clone()], false)),
+            Item::Field { until, to_end, .. } => {
+                let (literals, nullable) = next.unwrap_or_else(|| panic!("{:?}: a field cannot follow a field", pattern));
+                (*until, *to_end) = (literals, nullable);
+                None
+            }
+            Item::Alternatives(alternatives) => {
+                let literals = alternatives.iter().filter(|a| !a.is_empty()).cloned().collect::<Vec<_>>();
+                if alternatives.iter().any(String::is_empty) {
+                    next.map(|(more, nullable)| (literals.into_iter().chain(more).collect(), nullable))
+                } else {
+                    Some((literals, false))
+                }
+            }
+            Item::Optional(inner) => {
+                resolve(inner, next.clone(), pattern);
+                match first(inner) {
+                    Some((literals, false)) => next.map(|(more, nullable)| (literals.into_iter().chain(more).collect(), nullable)),
+                    _ => panic!("{:?}: an optional segment must start with text", pattern),
+                }
+            }
+        };
+    }
+}
+
+fn first(items: &[Item]) -> First {
+    let mut literals = Vec::new();
+    for item in items {
+        match item {
+            Item::Literal(text) => {
+                literals.push(text.clone());
+                return Some((literals, false));
+            }
+            Item::Field { .. } => return None,
+            Item::Alternatives(alternatives) => {
+                literals.extend(alternatives.iter().filter(|a| !a.is_empty()).cloned());
+                if !alternatives.iter().any(String::is_empty) {
+                    return Some((literals, false));
+                }
+            }
+            Item::Optional(inner) => literals.extend(first(inner)?.0),
+        }
+    }
+    Some((literals, true))
+}
+
+fn count(items: &[Item]) -> usize {
+    items.iter().map(|item| match item {
+        Item::Literal(_) => 0,
+        Item::Field { .. } | Item::Alternatives(_) => 1,
+        Item::Optional(inner) => count(inner),
+    }).sum()
+}
+
+/// `"a"`, `"a" or "b"`, `"a", "b" or "c"`.
+fn one_of(literals: &[String]) -> String {
+    let mut out = String::new();
+    for (i, literal) in literals.iter().enumerate() {
+        let separator = match i {
+            0 => "",
+            _ if i + 1 == literals.len() => " or ",
+            _ => ", ",
+        };
+        write!(out, "{}{:?}", separator, literal).unwrap();
+    }
+    out
+}
+
+struct Scanner<'a, 'l> {
+    line: &'l Line<'a>,
+    text: &'a str,
+    captures: Vec<Capture<'a>>,
+}
+
+impl<'a> Scanner<'a, '_> {
+    /// Matches `items` at byte `pos`, returns where the match ends.
+    fn items(&mut self, items: &[Item], mut pos: usize) -> Result<usize, ParseError> {
+        for item in items {
+            let rest = &self.text[pos..];
+            match item {
+                Item::Literal(literal) => {
+                    if !rest.starts_with(literal.as_str()) {
+                        return Err(self.expected(pos, std::slice::from_ref(literal)));
+                    }
+                    pos += literal.len();
+                }
+                Item::Field { separator, until, to_end } => {
+                    let end = until.iter().filter_map(|literal| rest.find(literal.as_str())).min()
+                        .or(to_end.then_some(rest.len()))
+                        .ok_or_else(|| self.line.error(rest, format!("expected {}", one_of(until))))?;
+                    let field = &rest[..end];
+                    self.captures.push(match separator {
+                        None => Capture::One(field),
+                        Some(_) if field.is_empty() => Capture::Many(Vec::new()),
+                        Some(separator) => Capture::Many(field.split(separator.as_str()).collect()),
+                    });
+                    pos += end;
+                }
+                Item::Alternatives(alternatives) => {
+                    let matched = alternatives.iter().find(|a| rest.starts_with(a.as_str()))
+                        .ok_or_else(|| self.expected(pos, alternatives))?;
+                    self.captures.push(Capture::One(&rest[..matched.len()]));
+                    pos += matched.len();
+                }
+                Item::Optional(inner) => {
+                    let present = first(inner).is_some_and(|(literals, _)| literals.iter().any(|l| rest.starts_with(l.as_str())));
+                    if present {
+                        pos = self.items(inner, pos)?;
+                    } else {
+                        self.captures.extend((0..count(inner)).map(|_| Capture::Missing));
+                    }
+                }
+            }
+        }
+        Ok(pos)
+    }
+
+    /// Error pointing at the text at `pos` as long as the longest of `literals`.
+    fn expected(&self, pos: usize, literals: &[String]) -> ParseError {
+        let rest = &self.text[pos..];
+        let width = literals.iter().map(|l| l.chars().count()).max().unwrap_or(0).max(1);
+        let end = rest.char_indices().nth(width).map_or(rest.len(), |(i, _)| i);
+        self.line.error(&rest[..end], format!("expected {}", one_of(literals)))
+    }
+}
+
+/// What one field of a pattern matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Capture<'a> {
+    /// A field of an absent optional segment.
+    Missing,
+    One(&'a str),
+    /// The items of a repeated field.
+    Many(Vec<&'a str>),
+}
+
+/// A value built from one captured field.
+pub trait Field<'a>: Sized {
+    fn from_text(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError>;
+
+    /// Panics when the pattern does not fit the type: a missing field needs an `Option`.
+    fn from_capture(line: &Line<'a>, capture: Capture<'a>) -> Result<Self, ParseError> {
+        match capture {
+            Capture::One(text) => Self::from_text(line, text),
+            Capture::Missing => panic!("a field of an optional segment needs an Option"),
+            Capture::Many(_) => panic!("a repeated field needs a Vec"),
+        }
+    }
+}
+
+macro_rules! from_str_fields {
+    ($($t: ty),*) => {
+        $(
+            impl<'a> Field<'a> for $t {
+                fn from_text(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+                    line.parse(text)
+                }
+            }
+        )*
+    };
+}
+
+from_str_fields!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char, bool, String);
+
+impl<'a> Field<'a> for &'a str {
+    fn from_text(_: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        Ok(text)
+    }
+}
+
+impl<'a, T: Field<'a>> Field<'a> for Option<T> {
+    fn from_text(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        T::from_text(line, text).map(Some)
+    }
+
+    fn from_capture(line: &Line<'a>, capture: Capture<'a>) -> Result<Self, ParseError> {
+        match capture {
+            Capture::Missing => Ok(None),
+            capture => T::from_capture(line, capture).map(Some),
+        }
+    }
+}
+
+impl<'a, T: Field<'a>> Field<'a> for Vec<T> {
+    fn from_text(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        Ok(vec![T::from_text(line, text)?])
+    }
+
+    fn from_capture(line: &Line<'a>, capture: Capture<'a>) -> Result<Self, ParseError> {
+        match capture {
+            Capture::Many(items) => items.into_iter().map(|item| T::from_text(line, item)).collect(),
+            capture => Ok(vec![T::from_capture(line, capture)?]),
+        }
+    }
+}
+
+/// All fields of a pattern: one `Field` or a tuple with one `Field` per field.
+pub trait Fields<'a>: Sized {
+    /// Panics when the number of fields does not fit the type.
+    fn from_captures(line: &Line<'a>, captures: Vec<Capture<'a>>) -> Result<Self, ParseError>;
+}
+
+impl<'a, T: Field<'a>> Fields<'a> for T {
+    fn from_captures(line: &Line<'a>, captures: Vec<Capture<'a>>) -> Result<Self, ParseError> {
+        let [capture]: [Capture<'a>; 1] = captures.try_into()
+            .unwrap_or_else(|captures: Vec<_>| panic!("expected 1 field, the pattern has {}", captures.len()));
+        T::from_capture(line, capture)
+    }
+}
+
+macro_rules! tuple_fields {
+    ($n: literal: $($t: ident),*) => {
+        impl<'a, $($t: Field<'a>),*> Fields<'a> for ($($t,)*) {
+            fn from_captures(line: &Line<'a>, captures: Vec<Capture<'a>>) -> Result<Self, ParseError> {
+                assert_eq!($n, captures.len(), "the pattern has {} fields", captures.len());
+                let mut captures = captures.into_iter();
+                Ok(($($t::from_capture(line, captures.next().unwrap())?,)*))
+            }
+        }
+    };
+}
+
+tuple_fields!(2: A, B);
+tuple_fields!(3: A, B, C);
+tuple_fields!(4: A, B, C, D);
+tuple_fields!(5: A, B, C, D, E);
+tuple_fields!(6: A, B, C, D, E, F);
+tuple_fields!(7: A, B, C, D, E, F, G);
+tuple_fields!(8: A, B, C, D, E, F, G, H);
+
+/// Scans a `Line` (or a slice `part` of it) with a pattern compiled once per call site:
+/// `let (n, from, to): (usize, usize, usize) = scan!("move {} from {} to {}", &line)?;`
+#[macro_export]
+macro_rules! scan {
+    ($pattern: literal, $line: expr) => {{
+        static PATTERN: ::std::sync::OnceLock<$crate::scan::Pattern> = ::std::sync::OnceLock::new();
+        PATTERN.get_or_init(|| $crate::scan::Pattern::new($pattern)).scan($line)
+    }};
+    ($pattern: literal, $line: expr, $part: expr) => {{
+        static PATTERN: ::std::sync::OnceLock<$crate::scan::Pattern> = ::std::sync::OnceLock::new();
+        PATTERN.get_or_init(|| $crate::scan::Pattern::new($pattern)).scan_part($line, $part)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line<'_> {
+        Line::new(0, text)
+    }
+
+    #[test]
+    fn test_fields() {
+        let line = line("move 3 from 1 to 2");
+        assert_eq!(Ok((3, 1, 2)), scan!("move {} from {} to {}", &line));
+        assert_eq!(Ok(("3", 2u8)), scan!("move {} from 1 to {}", &line));
+        assert_eq!(Ok(2), scan!("{} to {}", &line).map(|(_, to): (&str, i32)| to));
+        assert_eq!(Ok("3".to_string()), scan!("move {} from", &line, &line.text[..11]));
+        assert_eq!(3, Pattern::new("move {} from {} to {}").fields());
+    }
+
+    #[test]
+    fn test_repeated() {
+        let line = line("items: 79, 98, 5");
+        assert_eq!(Ok(vec![79, 98, 5]), scan!("items: {*, }", &line));
+        assert_eq!(Ok(Vec::<u32>::new()), scan!("items: {*, }", &self::line("items: ")));
+        let path = self::line("498,4 -> 498,6 -> 496,6");
+        let points: Vec<&str> = scan!("{* -> }", &path).unwrap();
+        let points = points.into_iter().map(|p| scan!("{},{}", &path, p)).collect::<Result<Vec<(i32, i32)>, _>>();
+        assert_eq!(Ok(vec![(498, 4), (498, 6), (496, 6)]), points);
+    }
+
+    #[test]
+    fn test_alternatives_and_optional() {
+        let pattern = Pattern::new("{}: {}{? {+|-|*|/} {}}");
+        let line1 = line("root: pppw + sjmn");
+        assert_eq!(Ok(("root", "pppw", Some('+'), Some("sjmn"))), pattern.scan(&line1));
+        let line2 = line("dbpl: 5");
+        assert_eq!(Ok(("dbpl", 5, None::<char>, None::<&str>)), pattern.scan(&line2));
+        let line3 = line("1 robot costs 2 ores");
+        assert_eq!(Ok((1, "robot", 2, "ore")), scan!("{} {robot|robots} costs {} {ore|clay}{?s}", &line3));
+        let line4 = line("-7");
+        assert_eq!(Ok((Some('-'), 7)), scan!("{+|-|}{}", &line4));
+    }
+
+    #[test]
+    fn test_errors() {
+        let line = line("move 3 from 1 of 2");
+        let error = scan!("move {} from {} to {}", &line).map(|_: (u8, u8, u8)| ()).unwrap_err();
+        assert_eq!((13, 6, "expected \" to \""), (error.column, error.len, error.message.as_str()));
+        let error = scan!("moves {}", &line).map(|_: String| ()).unwrap_err();
+        assert_eq!((1, 6, "expected \"moves \""), (error.column, error.len, error.message.as_str()));
+        let error = scan!("move {} from {} of {}", &self::line("move 3 from x of 2")).map(|_: (u8, u8, u8)| ()).unwrap_err();
+        assert_eq!((13, "cannot parse \"x\": invalid digit found in string"), (error.column, error.message.as_str()));
+        let op = self::line("a % b");
+        let error = scan!("{} {+|-|*|/} {}", &op).map(|_: (&str, &str, &str)| ()).unwrap_err();
+        assert_eq!((3, 1, "expected \"+\", \"-\", \"*\" or \"/\""), (error.column, error.len, error.message.as_str()));
+        let error = scan!("move {} from", &line).map(|_: u8| ()).unwrap_err();
+        assert_eq!((12, "expected the end of the line"), (error.column, error.message.as_str()));
+    }
+
+    #[test]
+    fn test_compile() {
+        assert_eq!(vec![Item::Literal("{a}".to_string())], compile("{{a}}"));
+        assert_eq!(Ok("x"), Pattern::new("{{{}}}").scan(&line("{x}")));
+        for pattern in ["{}{}", "{{}", "a}", "{}{?{}}", "{*}"] {
+            assert!(std::panic::catch_unwind(|| Pattern::new(pattern)).is_err(), "{}", pattern);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::priority_queue::IndexedPriorityQueue;
+
+/// States from a start to a goal, both included, and the total cost of the moves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+/// Visited states with the index of the state they were reached from.
+struct Tree<S> {
+    states: Vec<S>,
+    parents: Vec<usize>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Tree<S> {
+    fn new() -> Self {
+        Self { states: Vec::new(), parents: Vec::new(), index: HashMap::new() }
+    }
+
+    /// Returns the index of `state`, the flag is set when it was not seen before.
+    fn insert(&mut self, state: S, parent: usize) -> (usize, bool) {
+        match self.index.entry(state) {
+            Entry::Occupied(entry) => (*entry.get(), false),
+            Entry::Vacant(entry) => {
+                let id = self.states.len();
+                self.states.push(entry.key().clone());
+                self.parents.push(parent);
+                entry.insert(id);
+                (id, true)
+            }
+        }
+    }
+
+    fn path<C>(&self, mut id: usize, cost: C) -> Path<S, C> {
+        let mut states = vec![self.states[id].clone()];
+        while self.parents[id] != id {
+            id = self.parents[id];
+            states.push(self.states[id].clone());
+        }
+        states.reverse();
+        Path { states, cost }
+    }
+}
+
+/// Breadth-first search from all `starts` at once, the cost is the number of moves.
+pub fn bfs<S, I, N, NI, G>(starts: I, mut neighbours: N, mut is_goal: G) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> NI,
+    NI: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut tree = Tree::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        let id = tree.states.len();
+        if let (id, true) = tree.insert(start, id) {
+            queue.push_back((id, 0));
+        }
+    }
+    while let Some((id, cost)) = queue.pop_front() {
+        if is_goal(&tree.states[id]) {
+            return Some(tree.path(id, cost));
+        }
+        for next in neighbours(&tree.states[id]) {
+            if let (next, true) = tree.insert(next, id) {
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest path from any of `starts`, move costs must not be negative.
+pub fn dijkstra<S, C, I, N, NI, G>(starts: I, neighbours: N, is_goal: G) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> NI,
+    NI: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Cheapest path from any of `starts`, `heuristic` must never overestimate the remaining cost.
+pub fn astar<S, C, I, N, NI, H, G>(starts: I, mut neighbours: N, mut heuristic: H, mut is_goal: G) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = S>,
+    N: FnMut(&S) -> NI,
+    NI: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut tree = Tree::new();
+    let mut costs = Vec::new();
+    // handles of the states waiting in the queue, by state index
+    let mut queued = Vec::new();
+    let mut queue = IndexedPriorityQueue::new();
+    for start in starts {
+        let id = tree.states.len();
+        if let (id, true) = tree.insert(start, id) {
+            costs.push(C::default());
+            queued.push(Some(queue.push(id, (heuristic(&tree.states[id]), C::default()))));
+        }
+    }
+    while let Some((id, (_, cost))) = queue.pop() {
+        queued[id] = None;
+        if is_goal(&tree.states[id]) {
+            return Some(tree.path(id, cost));
+        }
+        for (next, step) in neighbours(&tree.states[id]) {
+            let next_cost = cost + step;
+            let (next, new) = tree.insert(next, id);
+            if new {
+                costs.push(next_cost);
+                queued.push(None);
+            } else if next_cost < costs[next] {
+                costs[next] = next_cost;
+                tree.parents[next] = id;
+            } else {
+                continue;
+            }
+            let priority = (next_cost + heuristic(&tree.states[next]), next_cost);
+            match queued[next] {
+                Some(handle) => {
+                    queue.change_priority(handle, priority);
+                }
+                None => queued[next] = Some(queue.push(next, priority)),
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 3 costs 1 + 5, 0 -> 2 -> 3 costs 2 + 1.
+    fn graph(state: &u32) -> Vec<(u32, u32)> {
+        match state {
+            0 => vec![(1, 1), (2, 2)],
+            1 => vec![(3, 5)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let neighbours = |&x: &i32| [x + 1, x * 2];
+        assert_eq!(Some(Path { states: vec![1, 2, 4, 8, 9], cost: 4 }), bfs([1], neighbours, |&x| x == 9));
+        assert_eq!(Some(Path { states: vec![5], cost: 0 }), bfs([3, 5], neighbours, |&x| x == 5));
+        assert_eq!(Some(Path { states: vec![4, 8], cost: 1 }), bfs([1, 4], neighbours, |&x| x == 8));
+        assert_eq!(None, bfs([1], |&x: &i32| (x < 5).then_some(x + 1), |&x| x == 9));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(Some(Path { states: vec![0, 2, 3], cost: 3 }), dijkstra([0], graph, |&x| x == 3));
+        assert_eq!(Some(Path { states: vec![1, 3], cost: 5 }), dijkstra([1], graph, |&x| x == 3));
+        assert_eq!(None, dijkstra([3], graph, |&x| x == 0));
+    }
+
+    #[test]
+    fn test_astar() {
+        let neighbours = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)].into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y) && !(x == 5 && y < 9))
+                .map(|p| (p, 1))
+        };
+        let goal = (9, 0);
+        let heuristic = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+        let path = astar([(0, 0)], neighbours, heuristic, |&p| p == goal).unwrap();
+        assert_eq!(27, path.cost);
+        assert_eq!(28, path.states.len());
+        assert_eq!(Some(&(5, 9)), path.states.iter().find(|p| p.0 == 5));
+        assert_eq!(path.cost, dijkstra([(0, 0)], neighbours, |&p| p == goal).unwrap().cost);
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::VecDeque;
+
+use crate::render::{self, FrameSink, Render};
+
+/// A simulation that advances in discrete steps: a CPU cycle, a unit of sand, a round.
+/// Snapshots are clones of the whole state, so a `Stepper` can rewind it.
+pub trait Simulation: Render + Clone {
+    /// Advances one step, `false` when the simulation is over and nothing changed.
+    fn step(&mut self) -> bool;
+    /// Named counters shown with the frame, `aoc step` conditions refer to them.
+    fn status(&self) -> Vec<(&'static str, i64)>;
+}
+
+/// Pushes the state before the first step and after every step to `sink`, returns the number of steps.
+pub fn record<S: Simulation>(simulation: &mut S, sink: &mut FrameSink) -> usize {
+    sink.push(simulation);
+    let mut steps = 0;
+    while simulation.step() {
+        sink.push(simulation);
+        steps += 1;
+    }
+    steps
+}
+
+/// Steps a simulation forwards and keeps the last snapshots to step back.
+#[derive(Debug, Clone)]
+pub struct Stepper<S> {
+    current: S,
+    history: VecDeque<S>,
+    history_limit: usize,
+    steps: usize,
+    finished: bool,
+}
+
+impl<S: Simulation> Stepper<S> {
+    pub const HISTORY: usize = 1000;
+
+    pub fn new(simulation: S) -> Self {
+        Self { current: simulation, history: VecDeque::new(), history_limit: Self::HISTORY, steps: 0, finished: false }
+    }
+
+    /// Number of snapshots kept for `rewind`, the oldest are dropped first.
+    pub fn with_history(mut self, limit: usize) -> Self {
+        self.history_limit = limit;
+        self
+    }
+
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The last step did not change anything.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn step(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        let before = self.current.clone();
+        if !self.current.step() {
+            self.finished = true;
+            return false;
+        }
+        if self.history_limit > 0 {
+            if self.history.len() == self.history_limit {
+                self.history.pop_front();
+            }
+            self.history.push_back(before);
+        }
+        self.steps += 1;
+        true
+    }
+
+    /// Takes up to `n` steps, returns how many were taken.
+    pub fn step_n(&mut self, n: usize) -> usize {
+        (0..n).take_while(|_| self.step()).count()
+    }
+
+    /// Steps until `predicate` holds after a step, at most `limit` steps.
+    /// Returns the number of steps, `None` when the simulation ended or the limit was hit first.
+    pub fn run_until<P: FnMut(&S) -> bool>(&mut self, mut predicate: P, limit: usize) -> Option<usize> {
+        for n in 1..=limit {
+            if !self.step() {
+                return None;
+            }
+            if predicate(&self.current) {
+                return Some(n);
+            }
+        }
+        None
+    }
+
+    /// Goes back up to `n` steps, as far as the history reaches. Returns how many steps were undone.
+    pub fn rewind(&mut self, n: usize) -> usize {
+        let mut undone = 0;
+        while undone < n {
+            let Some(previous) = self.history.pop_back() else { break };
+            self.current = previous;
+            self.steps -= 1;
+            self.finished = false;
+            undone += 1;
+        }
+        undone
+    }
+}
+
+/// The named counters of `Simulation::status`.
+pub type Status = [(&'static str, i64)];
+
+/// Object safe view of a `Stepper`, so that the runner can step any day.
+pub trait Session {
+    fn step(&mut self, n: usize) -> usize;
+    /// Like `Stepper::run_until`, the predicate sees the status counters.
+    fn run_until(&mut self, predicate: &mut dyn FnMut(&Status) -> bool, limit: usize) -> Option<usize>;
+    fn rewind(&mut self, n: usize) -> usize;
+    fn steps(&self) -> usize;
+    fn finished(&self) -> bool;
+    fn status(&self) -> Vec<(&'static str, i64)>;
+    /// The current frame as text, with ANSI colours when `colour` is set.
+    fn frame(&self, colour: bool) -> String;
+    /// Records the rest of the simulation, see `record`.
+    fn record(&mut self, sink: &mut FrameSink) -> usize;
+}
+
+impl<S: Simulation> Session for Stepper<S> {
+    fn step(&mut self, n: usize) -> usize {
+        self.step_n(n)
+    }
+
+    fn run_until(&mut self, predicate: &mut dyn FnMut(&Status) -> bool, limit: usize) -> Option<usize> {
+        Stepper::run_until(self, |simulation| predicate(&simulation.status()), limit)
+    }
+
+    fn rewind(&mut self, n: usize) -> usize {
+        Stepper::rewind(self, n)
+    }
+
+    fn steps(&self) -> usize {
+        self.steps
+    }
+
+    fn finished(&self) -> bool {
+        self.finished
+    }
+
+    fn status(&self) -> Vec<(&'static str, i64)> {
+        self.current.status()
+    }
+
+    fn frame(&self, colour: bool) -> String {
+        let frame = self.current.render();
+        if colour { render::ansi(&frame) } else { render::ascii(&frame) }
+    }
+
+    fn record(&mut self, sink: &mut FrameSink) -> usize {
+        let steps = record(&mut self.current, sink);
+        self.steps += steps;
+        self.finished = true;
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::render::{Cell, Rgb};
+
+    #[derive(Debug, Clone, Copy)]
+    struct Digit(u8);
+
+    impl Cell for Digit {
+        fn glyph(&self) -> char {
+            (b'0' + self.0) as char
+        }
+        fn colour(&self) -> Rgb {
+            [self.0 * 20; 3]
+        }
+    }
+
+    /// Counts up to 9.
+    #[derive(Debug, Clone)]
+    struct Counter(u8);
+
+    impl Render for Counter {
+        type Cell = Digit;
+        fn render(&self) -> Grid<Digit> {
+            Grid::new(1, 1, Digit(self.0))
+        }
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.0 == 9 {
+                return false;
+            }
+            self.0 += 1;
+            true
+        }
+        fn status(&self) -> Vec<(&'static str, i64)> {
+            vec![("count", self.0 as i64)]
+        }
+    }
+
+    #[test]
+    fn test_stepper() {
+        let mut stepper = Stepper::new(Counter(0));
+        assert_eq!(3, stepper.step_n(3));
+        assert_eq!((3, 3), (stepper.steps(), stepper.current().0));
+        assert_eq!(Some(2), stepper.run_until(|c| c.0 == 5, 100));
+        assert_eq!(None, stepper.run_until(|c| c.0 == 5, 100));
+        assert!(stepper.finished());
+        assert_eq!(2, stepper.rewind(2));
+        assert!(!stepper.finished());
+        assert_eq!(stepper.steps() as u8, stepper.current().0);
+    }
+
+    #[test]
+    fn test_history_limit() {
+        let mut stepper = Stepper::new(Counter(0)).with_history(2);
+        stepper.step_n(5);
+        assert_eq!(2, stepper.rewind(10));
+        assert_eq!((3, 3), (stepper.steps(), stepper.current().0));
+    }
+
+    #[test]
+    fn test_session() {
+        let mut session: Box<dyn Session> = Box::new(Stepper::new(Counter(0)));
+        assert_eq!(Some(4), session.run_until(&mut |status| status[0].1 == 4, 10));
+        assert_eq!("4\n", session.frame(false));
+        assert!(session.frame(true).contains("\x1b[38;2;80;80;80m4"));
+        assert_eq!(vec![("count", 4)], session.status());
+    }
+}
@@ -0,0 +1,224 @@
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+
+use crate::input::Input;
+use crate::params::Params;
+use crate::render::FrameSink;
+use crate::simulation::Session;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[error("Part {0} error: {1}")]
+    Solve(Part, String),
+    #[error("Parsed input does not belong to day {0}")]
+    InputMismatch(u8),
+    #[error("{0}")]
+    Params(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A puzzle solution: parsing is done once and both parts work on the parsed input.
+pub trait Solution {
+    const DAY: u8;
+    /// Example input from the puzzle description, used by `--example`.
+    const EXAMPLE: &'static str;
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+    type Error: Display;
+    /// Puzzle constants that can be changed at run time, `()` when there are none.
+    type Params: Params;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error>;
+
+    /// `part1` with other parameters, days with parameters override it and solve `part1` with the defaults.
+    fn part1_with(input: &Self::Input, _params: &Self::Params) -> Result<Self::Answer1, Self::Error> {
+        Self::part1(input)
+    }
+
+    /// `part2` with other parameters, see `part1_with`.
+    fn part2_with(input: &Self::Input, _params: &Self::Params) -> Result<Self::Answer2, Self::Error> {
+        Self::part2(input)
+    }
+
+    /// The step-based simulation behind `part`, `None` when the day has none.
+    fn simulation(_input: &Self::Input, _part: Part, _params: &Self::Params) -> Result<Option<Box<dyn Session>>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Pushes the frames of the simulation behind `part` to `sink`,
+    /// `false` when the day has nothing to draw.
+    fn record(input: &Self::Input, part: Part, params: &Self::Params, sink: &mut FrameSink) -> Result<bool, Self::Error> {
+        Ok(Self::simulation(input, part, params)?.map(|mut session| session.record(sink)).is_some())
+    }
+
+    /// Lines of `EXAMPLE`.
+    fn example() -> Vec<&'static str> {
+        Self::EXAMPLE.lines().collect()
+    }
+
+    /// Parses the lines and solves part 1.
+    fn solve_part1<S: AsRef<str>>(lines: &[S]) -> Result<Self::Answer1, Self::Error> {
+        Self::part1(&Self::parse(lines)?)
+    }
+
+    /// Parses the lines and solves part 2.
+    fn solve_part2<S: AsRef<str>>(lines: &[S]) -> Result<Self::Answer2, Self::Error> {
+        Self::part2(&Self::parse(lines)?)
+    }
+}
+
+/// Object safe view of a `Solution` so that days can be kept in one registry.
+pub trait Runner: Send + Sync {
+    fn day(&self) -> u8;
+    fn example(&self) -> &'static str;
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>, Error>;
+    /// Default values of the parameters.
+    fn params(&self) -> Vec<(&'static str, String)>;
+    /// Solves `part` with the `name = value` parameter overrides.
+    fn solve_with(&self, input: &dyn Any, part: Part, params: &[(String, String)]) -> Result<String, Error>;
+    fn record(&self, input: &dyn Any, part: Part, params: &[(String, String)], sink: &mut FrameSink) -> Result<bool, Error>;
+    fn simulation(&self, input: &dyn Any, part: Part, params: &[(String, String)]) -> Result<Option<Box<dyn Session>>, Error>;
+
+    fn solve(&self, input: &dyn Any, part: Part) -> Result<String, Error> {
+        self.solve_with(input, part, &[])
+    }
+
+    fn run(&self, input: &Input, part: Part) -> Result<String, Error> {
+        self.solve(self.parse(input)?.as_ref(), part)
+    }
+}
+
+pub struct Solver<S>(PhantomData<fn() -> S>);
+
+impl<S> Solver<S> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S> Default for Solver<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Runner for Solver<S>
+where S: Solution, S::Input: 'static {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn example(&self) -> &'static str {
+        S::EXAMPLE
+    }
+
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>, Error> {
+        match S::parse(&input.lines()) {
+            Ok(input) => Ok(Box::new(input)),
+            Err(error) => Err(Error::Parse(error.to_string())),
+        }
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        S::Params::default().values()
+    }
+
+    fn solve_with(&self, input: &dyn Any, part: Part, params: &[(String, String)]) -> Result<String, Error> {
+        let input = input.downcast_ref::<S::Input>().ok_or(Error::InputMismatch(S::DAY))?;
+        let params = S::Params::build(params).map_err(Error::Params)?;
+        let result = match part {
+            Part::One => S::part1_with(input, &params).map(|answer| answer.to_string()),
+            Part::Two => S::part2_with(input, &params).map(|answer| answer.to_string()),
+        };
+        result.map_err(|error| Error::Solve(part, error.to_string()))
+    }
+
+    fn record(&self, input: &dyn Any, part: Part, params: &[(String, String)], sink: &mut FrameSink) -> Result<bool, Error> {
+        let input = input.downcast_ref::<S::Input>().ok_or(Error::InputMismatch(S::DAY))?;
+        let params = S::Params::build(params).map_err(Error::Params)?;
+        S::record(input, part, &params, sink).map_err(|error| Error::Solve(part, error.to_string()))
+    }
+
+    fn simulation(&self, input: &dyn Any, part: Part, params: &[(String, String)]) -> Result<Option<Box<dyn Session>>, Error> {
+        let input = input.downcast_ref::<S::Input>().ok_or(Error::InputMismatch(S::DAY))?;
+        let params = S::Params::build(params).map_err(Error::Params)?;
+        S::simulation(input, part, &params).map_err(|error| Error::Solve(part, error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        const EXAMPLE: &'static str = "1\n2";
+        type Input = Vec<i32>;
+        type Answer1 = i32;
+        type Answer2 = i32;
+        type Error = &'static str;
+        type Params = ();
+
+        fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+            lines.iter().map(|l| l.as_ref().parse().map_err(|_| "not a number")).collect()
+        }
+        fn part1(input: &Self::Input) -> Result<i32, Self::Error> {
+            Ok(input.iter().sum())
+        }
+        fn part2(input: &Self::Input) -> Result<i32, Self::Error> {
+            input.iter().max().copied().ok_or("empty input")
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let runner = Solver::<Sum>::new();
+        assert_eq!(Ok("6".to_string()), runner.run(&Input::from("1\n2\n3\n"), Part::One));
+        assert_eq!(Ok("3".to_string()), runner.run(&Input::from("1\n2\n3"), Part::Two));
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(Ok(3), Sum::solve_part1(&Sum::example()));
+        assert_eq!(Ok(2), Sum::solve_part2(&Sum::example()));
+        assert_eq!(Err("not a number"), Sum::solve_part1(&["1", "y"]));
+        assert_eq!(Ok(3), Sum::solve_part1(&Input::from("1\n2").lines()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let runner = Solver::<Sum>::new();
+        assert_eq!(Err(Error::Parse("not a number".to_string())), runner.run(&Input::from("x"), Part::One));
+        assert_eq!(Err(Error::Solve(Part::Two, "empty input".to_string())), runner.run(&Input::default(), Part::Two));
+        assert_eq!(Err(Error::InputMismatch(0)), runner.solve(&1u8, Part::One));
+        let params = [("rounds".to_string(), "3".to_string())];
+        let input = runner.parse(&Input::from("1")).unwrap();
+        assert_eq!(Err(Error::Params("Unknown parameter rounds".to_string())), runner.solve_with(input.as_ref(), Part::One, &params));
+    }
+}
@@ -0,0 +1,101 @@
+use aoc_common::priority_queue::Handle;
+use aoc_common::{IndexedPriorityQueue, PriorityQueue};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Push(u8),
+    Pop,
+    Peek,
+    /// Handles are picked by index among all pushed so far, stale ones included.
+    Change(usize, u8),
+    Decrease(usize, u8),
+    Remove(usize),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    // few priorities, so that ties are common
+    let priority = 0..8u8;
+    prop_oneof![
+        3 => priority.clone().prop_map(Op::Push),
+        2 => Just(Op::Pop),
+        1 => Just(Op::Peek),
+        1 => (any::<usize>(), priority.clone()).prop_map(|(i, p)| Op::Change(i, p)),
+        1 => (any::<usize>(), priority).prop_map(|(i, p)| Op::Decrease(i, p)),
+        1 => any::<usize>().prop_map(Op::Remove),
+    ]
+}
+
+/// Priority of every pushed value, `None` once it left the queue.
+struct Naive(Vec<Option<u8>>);
+
+impl Naive {
+    fn least(&self) -> Option<u8> {
+        self.0.iter().flatten().copied().min()
+    }
+
+    fn sorted(&self) -> Vec<(usize, u8)> {
+        let mut entries = self.0.iter().enumerate().filter_map(|(v, p)| p.map(|p| (v, p))).collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+}
+
+proptest! {
+    #[test]
+    fn indexed_queue(ops in prop::collection::vec(op(), 1..200)) {
+        let mut queue = IndexedPriorityQueue::new();
+        let mut handles: Vec<Handle> = Vec::new();
+        let mut naive = Naive(Vec::new());
+        for op in ops {
+            let pick = |i: usize| (!handles.is_empty()).then(|| (i % handles.len(), handles[i % handles.len()]));
+            match op {
+                Op::Push(p) => {
+                    handles.push(queue.push(naive.0.len(), p));
+                    naive.0.push(Some(p));
+                }
+                Op::Pop => match queue.pop() {
+                    // ties may come out in any order, the value tells which one did
+                    Some((v, p)) => {
+                        prop_assert_eq!(Some(p), naive.least());
+                        prop_assert_eq!(Some(p), naive.0[v].take());
+                    }
+                    None => prop_assert_eq!(None, naive.least()),
+                },
+                Op::Peek => prop_assert_eq!(naive.least(), queue.peek().map(|(_, &p)| p)),
+                Op::Change(i, p) => if let Some((v, handle)) = pick(i) {
+                    prop_assert_eq!(naive.0[v], queue.change_priority(handle, p));
+                    if let Some(old) = naive.0[v].as_mut() {
+                        *old = p;
+                    }
+                },
+                Op::Decrease(i, p) => if let Some((v, handle)) = pick(i) {
+                    let lower = naive.0[v].is_some_and(|old| p < old);
+                    prop_assert_eq!(lower, queue.decrease_key(handle, p));
+                    if lower {
+                        naive.0[v] = Some(p);
+                    }
+                },
+                Op::Remove(i) => if let Some((v, handle)) = pick(i) {
+                    prop_assert_eq!(naive.0[v].take().map(|p| (v, p)), queue.remove(handle));
+                },
+            }
+            let mut entries = queue.iter().map(|(&v, &p)| (v, p)).collect::<Vec<_>>();
+            entries.sort();
+            prop_assert_eq!(naive.sorted(), entries);
+            prop_assert_eq!(queue.len(), naive.sorted().len());
+        }
+    }
+
+    #[test]
+    fn bounded_queue(values in prop::collection::vec(any::<i16>(), 0..100), k in 0..10usize) {
+        let mut queue = PriorityQueue::with_capacity_bounded(k);
+        queue.extend(values.iter().copied());
+        let mut sorted = values.clone();
+        sorted.sort();
+        prop_assert_eq!(sorted[sorted.len().saturating_sub(k)..].to_vec(), queue.into_sorted_vec());
+        let mut queue = values.into_iter().collect::<PriorityQueue<_>>();
+        let popped = std::iter::from_fn(|| queue.pop()).collect::<Vec<_>>();
+        prop_assert_eq!(sorted, popped);
+    }
+}
@@ -0,0 +1,17 @@
+//! Calorie Counting: blocks of item calories, one block per elf.
+
+use crate::Rng;
+
+/// Three to a dozen elves with one to six items each.
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let mut lines = Vec::new();
+    for elf in 0..rng.size(3..=12) {
+        if elf > 0 {
+            lines.push(String::new());
+        }
+        for _ in 0..rng.size(1..=6) {
+            lines.push(rng.int(1..=20000).to_string());
+        }
+    }
+    lines
+}
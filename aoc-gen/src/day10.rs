@@ -0,0 +1,18 @@
+//! Cathode-Ray Tube: `noop` and `addx` instructions taking exactly the 240 cycles of the screen.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut cycles = 0;
+    while cycles < 240 {
+        if cycles == 239 || rng.chance(0.4) {
+            lines.push("noop".to_string());
+            cycles += 1;
+        } else {
+            lines.push(format!("addx {}", rng.int(-20..=20)));
+            cycles += 2;
+        }
+    }
+    lines
+}
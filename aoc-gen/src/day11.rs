@@ -0,0 +1,40 @@
+//! Monkey in the Middle: notes of four to eight monkeys with distinct prime tests.
+//!
+//! Only monkey 0 squares or multiplies by a large factor and nobody throws to it, so every item
+//! grows that way at most once and part 1 worry levels stay small without any modulo.
+
+use crate::Rng;
+
+const PRIMES: &[i64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+fn operation(rng: &mut Rng, monkey: usize) -> String {
+    match (monkey, rng.below(3)) {
+        (0, 0) => "old * old".to_string(),
+        (0, _) => format!("old * {}", rng.int(4..=19)),
+        (_, 0) => "old + old".to_string(),
+        (_, 1) => format!("old * {}", rng.int(2..=3)),
+        _ => format!("old + {}", rng.int(1..=8)),
+    }
+}
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let count = rng.size(4..=8);
+    let mut primes = PRIMES.to_vec();
+    rng.shuffle(&mut primes);
+    let mut lines = Vec::new();
+    for (monkey, prime) in primes.iter().enumerate().take(count) {
+        let items = (0..rng.size(1..=4)).map(|_| rng.int(1..=99).to_string()).collect::<Vec<_>>();
+        let mut targets = (1..count).filter(|&target| target != monkey).collect::<Vec<_>>();
+        rng.shuffle(&mut targets);
+        if monkey > 0 {
+            lines.push(String::new());
+        }
+        lines.push(format!("Monkey {}:", monkey));
+        lines.push(format!("  Starting items: {}", items.join(", ")));
+        lines.push(format!("  Operation: new = {}", operation(rng, monkey)));
+        lines.push(format!("  Test: divisible by {}", prime));
+        lines.push(format!("    If true: throw to monkey {}", targets[0]));
+        lines.push(format!("    If false: throw to monkey {}", targets[1]));
+    }
+    lines
+}
@@ -0,0 +1,20 @@
+//! Hill Climbing Algorithm: a hill rising by about one step per tile towards the best signal
+//! location, with noise and a few cliffs, so most maps are climbable and some are not.
+//! Like in the puzzle the map is wide, its far side is low ground where the start is.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let (height, width) = (rng.size(1..=16), rng.size(28..=36));
+    let (end_row, end_col) = (rng.size(0..=height - 1), rng.size(0..=1));
+    let mut grid = (0..height).map(|row| (0..width).map(|col| {
+        let distance = (row.abs_diff(end_row) + col.abs_diff(end_col)) as i64;
+        let noise = if rng.chance(0.03) { 3 } else { rng.int(0..=1) };
+        b'a' + (25 - distance + noise).clamp(0, 25) as u8
+    }).collect::<Vec<_>>()).collect::<Vec<_>>();
+    let lowest = (0..height * width).filter(|&k| grid[k / width][k % width] == b'a').collect::<Vec<_>>();
+    let start = if lowest.is_empty() { width - 1 } else { *rng.choose(&lowest) };
+    grid[start / width][start % width] = b'S';
+    grid[end_row][end_col] = b'E';
+    grid.into_iter().map(|row| String::from_utf8(row).unwrap()).collect()
+}
@@ -0,0 +1,58 @@
+//! Distress Signal: pairs of packets. The right packet is often a small edit of the left one,
+//! so comparisons go deep and mix integers with lists.
+
+use crate::Rng;
+
+#[derive(Debug, Clone)]
+enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    fn random(rng: &mut Rng, depth: usize) -> Self {
+        if depth > 0 && (depth >= 3 || rng.chance(0.5)) {
+            return Packet::Int(rng.below(11) as u32);
+        }
+        Packet::List((0..rng.size(0..=4)).map(|_| Packet::random(rng, depth + 1)).collect())
+    }
+
+    fn edit(&self, rng: &mut Rng) -> Self {
+        match self {
+            Packet::Int(x) if rng.chance(0.1) => Packet::List(vec![Packet::Int(*x)]),
+            Packet::Int(_) if rng.chance(0.2) => Packet::Int(rng.below(11) as u32),
+            Packet::Int(x) => Packet::Int(*x),
+            Packet::List(items) => {
+                let mut items = items.iter().map(|item| item.edit(rng)).collect::<Vec<_>>();
+                if rng.chance(0.1) {
+                    items.truncate(rng.size(0..=items.len()));
+                }
+                if rng.chance(0.1) {
+                    items.push(Packet::random(rng, 2));
+                }
+                Packet::List(items)
+            },
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Packet::Int(x) => x.to_string(),
+            Packet::List(items) => format!("[{}]", items.iter().map(Packet::text).collect::<Vec<_>>().join(",")),
+        }
+    }
+}
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let mut lines = Vec::new();
+    for pair in 0..rng.size(1..=8) {
+        if pair > 0 {
+            lines.push(String::new());
+        }
+        let left = Packet::random(rng, 0);
+        let right = if rng.chance(0.6) { left.edit(rng) } else { Packet::random(rng, 0) };
+        lines.push(left.text());
+        lines.push(right.text());
+    }
+    lines
+}
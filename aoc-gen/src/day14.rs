@@ -0,0 +1,21 @@
+//! Regolith Reservoir: rock paths of horizontal and vertical segments around the sand source.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    (0..rng.size(1..=6)).map(|_| {
+        let (mut x, mut y) = (rng.int(490..=510), rng.int(1..=15));
+        let mut points = vec![format!("{},{}", x, y)];
+        let horizontal = rng.chance(0.5);
+        for segment in 0..rng.size(1..=4) {
+            let step = rng.int(1..=6) * if rng.chance(0.5) { 1 } else { -1 };
+            if (segment % 2 == 0) == horizontal {
+                x += step;
+            } else {
+                y = if y + step < 1 { y + step.abs() } else { y + step };
+            }
+            points.push(format!("{},{}", x, y));
+        }
+        points.join(" -> ")
+    }).collect()
+}
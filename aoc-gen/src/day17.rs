@@ -0,0 +1,7 @@
+//! Pyroclastic Flow: a short jet pattern.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    vec![(0..rng.size(1..=40)).map(|_| if rng.chance(0.5) { '<' } else { '>' }).collect()]
+}
@@ -0,0 +1,11 @@
+//! Not Enough Minerals: one or two blueprints with costs in the range of the real ones.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    (1..=rng.size(1..=2)).map(|id| format!(
+        "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+         Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+        id, rng.int(2..=4), rng.int(2..=4), rng.int(2..=4), rng.int(4..=14), rng.int(2..=4), rng.int(4..=14),
+    )).collect()
+}
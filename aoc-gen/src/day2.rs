@@ -0,0 +1,9 @@
+//! Rock Paper Scissors: a strategy guide of `A|B|C X|Y|Z` rounds.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    (0..rng.size(1..=30))
+        .map(|_| format!("{} {}", rng.choose(&['A', 'B', 'C']), rng.choose(&['X', 'Y', 'Z'])))
+        .collect()
+}
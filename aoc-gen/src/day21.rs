@@ -0,0 +1,123 @@
+//! Monkey Math: an expression tree where `humn` appears once and `root` compares its two sides.
+//!
+//! The side with `humn` is a chain of operations with constant subtrees. The other side evaluates
+//! to the chain value for a chosen number of `humn`, the unique answer of part 2.
+//! Divisions are exact both for the chosen number and the one yelled in the input.
+
+use std::collections::HashSet;
+
+use crate::Rng;
+
+struct Builder<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Builder<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name = (0..4).map(|_| char::from(b'a' + self.rng.below(26) as u8)).collect::<String>();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn leaf(&mut self, value: i64) -> String {
+        let name = self.name();
+        self.lines.push(format!("{}: {}", name, value));
+        name
+    }
+
+    fn operation(&mut self, left: &str, op: char, right: &str) -> String {
+        let name = self.name();
+        self.lines.push(format!("{}: {} {} {}", name, left, op, right));
+        name
+    }
+
+    /// Subtree without `humn`, its name and value.
+    fn constant(&mut self, depth: usize) -> (String, i64) {
+        if depth == 0 || self.rng.chance(0.4) {
+            let value = self.rng.int(1..=10);
+            return (self.leaf(value), value);
+        }
+        let (left, a) = self.constant(depth - 1);
+        let (right, b) = self.constant(depth - 1);
+        let (op, value) = match self.rng.below(4) {
+            0 => ('+', a + b),
+            1 => ('-', a - b),
+            2 => ('*', a * b),
+            _ if b != 0 && a % b == 0 => ('/', a / b),
+            _ => ('+', a + b),
+        };
+        (self.operation(&left, op, &right), value)
+    }
+}
+
+fn divisors(x: i64) -> Vec<i64> {
+    let x = x.abs();
+    (2..=x.min(1000)).filter(|d| x % d == 0).collect()
+}
+
+/// Keeps products of the two sides of `root` far from overflowing.
+fn small(x: i64) -> bool {
+    x.abs() < 1_000_000_000
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let mut builder = Builder { rng: &mut *rng, names: HashSet::new(), lines: Vec::new() };
+    let yelled = builder.rng.int(1..=1000);
+    let answer = builder.rng.int(1..=1000);
+    builder.lines.push(format!("humn: {}", yelled));
+    // `humn` side for the yelled number and for the answer
+    let (mut name, mut values) = ("humn".to_string(), (yelled, answer));
+    for _ in 0..builder.rng.size(0..=6) {
+        let (other, c) = builder.constant(1);
+        let humn_left = builder.rng.chance(0.5);
+        let (op, next) = match builder.rng.below(4) {
+            0 => ('+', (values.0 + c, values.1 + c)),
+            1 if humn_left => ('-', (values.0 - c, values.1 - c)),
+            1 => ('-', (c - values.0, c - values.1)),
+            2 if c != 0 && small(values.0 * c) && small(values.1 * c) => ('*', (values.0 * c, values.1 * c)),
+            3 => {
+                let candidates = divisors(gcd(values.0, values.1));
+                if candidates.is_empty() {
+                    ('+', (values.0 + c, values.1 + c))
+                } else {
+                    let d = *builder.rng.choose(&candidates);
+                    let divisor = builder.leaf(d);
+                    name = builder.operation(&name, '/', &divisor);
+                    values = (values.0 / d, values.1 / d);
+                    continue;
+                }
+            },
+            _ => ('+', (values.0 + c, values.1 + c)),
+        };
+        name = if humn_left { builder.operation(&name, op, &other) } else { builder.operation(&other, op, &name) };
+        values = next;
+    }
+    // the other side yells the `humn` side value for the answer
+    let other = if builder.rng.chance(0.5) {
+        builder.leaf(values.1)
+    } else {
+        let k = builder.rng.int(1..=10);
+        let (a, b) = (builder.leaf(values.1 - k), builder.leaf(k));
+        builder.operation(&a, '+', &b)
+    };
+    let op = match builder.rng.below(3) {
+        0 => '+',
+        1 => '-',
+        _ if small(values.0) && small(values.1) => '*',
+        _ => '+',
+    };
+    let line = if builder.rng.chance(0.5) { format!("root: {} {} {}", name, op, other) } else { format!("root: {} {} {}", other, op, name) };
+    builder.lines.push(line);
+    let mut lines = builder.lines;
+    rng.shuffle(&mut lines);
+    lines
+}
@@ -0,0 +1,43 @@
+//! Monkey Map: a board where every row and every column is one contiguous run of tiles,
+//! like the flat map of the puzzle, and a path of steps and turns.
+
+use crate::Rng;
+
+/// Tile intervals `[start, end)` of the rows, retried until the columns are contiguous too.
+fn shape(rng: &mut Rng) -> Vec<(usize, usize)> {
+    loop {
+        let start = rng.size(0..=6);
+        let mut rows = vec![(start, start + rng.size(1..=8))];
+        for _ in 1..rng.size(2..=10) {
+            let (start, end) = *rows.last().unwrap();
+            let next = (start.saturating_add_signed(rng.int(-2..=2) as isize), end.saturating_add_signed(rng.int(-2..=2) as isize));
+            if next.0 < next.1 && next.0 < end && start < next.1 {
+                rows.push(next);
+            }
+        }
+        let width = rows.iter().map(|&(_, end)| end).max().unwrap();
+        let contiguous = (0..width).all(|col| {
+            let covered = rows.iter().map(|&(start, end)| (start..end).contains(&col)).collect::<Vec<_>>();
+            covered.windows(2).filter(|pair| pair[0] != pair[1]).count() <= 2
+                && !(covered[0] && covered[covered.len() - 1] && covered.contains(&false))
+        });
+        if contiguous {
+            return rows;
+        }
+    }
+}
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let mut lines = shape(rng).into_iter().enumerate().map(|(row, (start, end))| {
+        let tiles = (start..end).map(|col| if (row, col) != (0, start) && rng.chance(0.25) { '#' } else { '.' });
+        " ".repeat(start) + &tiles.collect::<String>()
+    }).collect::<Vec<_>>();
+    lines.push(String::new());
+    let mut path = rng.size(0..=12).to_string();
+    for _ in 0..rng.size(0..=10) {
+        path.push(*rng.choose(&['L', 'R']));
+        path += &rng.size(0..=12).to_string();
+    }
+    lines.push(path);
+    lines
+}
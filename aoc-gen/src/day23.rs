@@ -0,0 +1,15 @@
+//! Unstable Diffusion: a small scan with at least one elf.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let (height, width) = (rng.size(1..=8), rng.size(1..=8));
+    let density = rng.size(2..=6) as f64 / 10.0;
+    let mut grid = (0..height)
+        .map(|_| (0..width).map(|_| if rng.chance(density) { b'#' } else { b'.' }).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    if !grid.iter().flatten().any(|&b| b == b'#') {
+        grid[rng.size(0..=height - 1)][rng.size(0..=width - 1)] = b'#';
+    }
+    grid.into_iter().map(|row| String::from_utf8(row).unwrap()).collect()
+}
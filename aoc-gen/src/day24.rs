@@ -0,0 +1,19 @@
+//! Blizzard Basin: a walled valley with the entrance and the exit in the corners.
+//! Like in the puzzle, no vertical blizzard shares a column with the entrance or the exit.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let (height, width) = (rng.size(1..=6), rng.size(1..=8));
+    let density = rng.size(1..=5) as f64 / 10.0;
+    let mut lines = vec![format!("#.{}", "#".repeat(width))];
+    for _ in 0..height {
+        let row = (1..=width).map(|col| {
+            let blizzards: &[char] = if col == 1 || col == width { &['<', '>'] } else { &['<', '>', '^', 'v'] };
+            if rng.chance(density) { *rng.choose(blizzards) } else { '.' }
+        }).collect::<String>();
+        lines.push(format!("#{}#", row));
+    }
+    lines.push(format!("{}.#", "#".repeat(width)));
+    lines
+}
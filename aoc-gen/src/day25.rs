@@ -0,0 +1,11 @@
+//! Full of Hot Air: SNAFU numbers without leading zeros.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    (0..rng.size(1..=20)).map(|_| {
+        let mut number = rng.choose(&['1', '2']).to_string();
+        number.extend((1..rng.size(1..=12)).map(|_| *rng.choose(&['=', '-', '0', '1', '2'])));
+        number
+    }).collect()
+}
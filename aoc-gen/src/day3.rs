@@ -0,0 +1,43 @@
+//! Rucksack Reorganization: the halves of a rucksack share exactly one item type
+//! and every group of three rucksacks shares exactly one badge.
+
+use crate::Rng;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// One to five groups. Every rucksack of a group takes its items from its own third
+/// of the alphabet, so the badge is the only item common to the group.
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let mut lines = Vec::new();
+    for _ in 0..rng.size(1..=5) {
+        let mut items = ITEMS.to_vec();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+        for pool in items.chunks(17) {
+            lines.push(rucksack(rng, badge, pool));
+        }
+    }
+    lines
+}
+
+/// The shared item is the badge or the first item of the pool,
+/// the rest of the pool is split between the halves.
+fn rucksack(rng: &mut Rng, badge: u8, pool: &[u8]) -> String {
+    let shared = if rng.chance(0.25) { badge } else { pool[0] };
+    let (left, right) = pool[1..].split_at(8);
+    let badge_left = rng.chance(0.5);
+    let len = rng.size(2..=12);
+    let mut items = half(rng, len, shared, (badge_left && badge != shared).then_some(badge), left);
+    items.extend(half(rng, len, shared, (!badge_left && badge != shared).then_some(badge), right));
+    String::from_utf8(items).unwrap()
+}
+
+fn half(rng: &mut Rng, len: usize, shared: u8, badge: Option<u8>, pool: &[u8]) -> Vec<u8> {
+    let mut items = vec![shared];
+    items.extend(badge);
+    while items.len() < len {
+        items.push(*rng.choose(pool));
+    }
+    rng.shuffle(&mut items);
+    items
+}
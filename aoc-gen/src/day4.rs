@@ -0,0 +1,12 @@
+//! Camp Cleanup: pairs of section ranges `a-b,c-d`.
+
+use crate::Rng;
+
+fn range(rng: &mut Rng) -> String {
+    let start = rng.size(1..=20);
+    format!("{}-{}", start, rng.size(start..=20))
+}
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    (0..rng.size(1..=30)).map(|_| format!("{},{}", range(rng), range(rng))).collect()
+}
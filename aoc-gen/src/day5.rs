@@ -0,0 +1,32 @@
+//! Supply Stacks: the drawing of the stacks and moves that never take more crates than a stack has.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let count = rng.size(1..=9);
+    let mut stacks = (0..count)
+        .map(|_| (0..rng.size(0..=6)).map(|_| (b'A' + rng.below(26) as u8) as char).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    if stacks.iter().all(|stack| stack.is_empty()) {
+        stacks[0].push('A');
+    }
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap();
+    let mut lines = (0..height).rev().map(|row| {
+        stacks.iter()
+            .map(|stack| stack.get(row).map_or("   ".to_string(), |c| format!("[{}]", c)))
+            .collect::<Vec<_>>().join(" ")
+    }).collect::<Vec<_>>();
+    lines.push((1..=count).map(|i| format!(" {} ", i)).collect::<Vec<_>>().join(" "));
+    lines.push(String::new());
+    let mut sizes = stacks.iter().map(|stack| stack.len()).collect::<Vec<_>>();
+    for _ in 0..if count > 1 { rng.size(0..=15) } else { 0 } {
+        let sources = (0..count).filter(|&i| sizes[i] > 0).collect::<Vec<_>>();
+        let from = *rng.choose(&sources);
+        let to = (from + rng.size(1..=count - 1)) % count;
+        let moved = rng.size(1..=sizes[from]);
+        sizes[from] -= moved;
+        sizes[to] += moved;
+        lines.push(format!("move {} from {} to {}", moved, from + 1, to + 1));
+    }
+    lines
+}
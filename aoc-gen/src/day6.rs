@@ -0,0 +1,13 @@
+//! Tuning Trouble: a datastream over a small alphabet that ends with 14 distinct characters,
+//! so both markers exist.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let alphabet = rng.size(2..=26) as u64;
+    let mut stream = (0..rng.size(0..=100)).map(|_| (b'a' + rng.below(alphabet) as u8) as char).collect::<String>();
+    let mut letters = (b'a'..=b'z').map(char::from).collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    stream.extend(&letters[..14]);
+    vec![stream]
+}
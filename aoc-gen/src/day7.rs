@@ -0,0 +1,43 @@
+//! No Space Left On Device: a terminal session listing a random directory tree depth first.
+
+use crate::Rng;
+
+const NAMES: &[&str] = &["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k.dat", "log.txt", "x.y", "zz"];
+
+struct Directory {
+    files: Vec<(&'static str, u64)>,
+    directories: Vec<(&'static str, Directory)>,
+}
+
+fn tree(rng: &mut Rng, depth: usize) -> Directory {
+    let mut names = NAMES.to_vec();
+    rng.shuffle(&mut names);
+    let files = (0..rng.size(0..=4)).map(|_| {
+        let size = if rng.chance(0.5) { rng.int(1..=50_000) } else { rng.int(1..=10_000_000) };
+        (names.pop().unwrap(), size as u64)
+    }).collect();
+    let count = if depth < 3 { rng.size(0..=3) } else { 0 };
+    let directories = (0..count).map(|_| (names.pop().unwrap(), tree(rng, depth + 1))).collect();
+    Directory { files, directories }
+}
+
+fn session(rng: &mut Rng, directory: &Directory, lines: &mut Vec<String>) {
+    lines.push("$ ls".to_string());
+    let mut listing = directory.files.iter().map(|(name, size)| format!("{} {}", size, name))
+        .chain(directory.directories.iter().map(|(name, _)| format!("dir {}", name)))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut listing);
+    lines.extend(listing);
+    for (name, child) in &directory.directories {
+        lines.push(format!("$ cd {}", name));
+        session(rng, child, lines);
+        lines.push("$ cd ..".to_string());
+    }
+}
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let root = tree(rng, 0);
+    let mut lines = vec!["$ cd /".to_string()];
+    session(rng, &root, &mut lines);
+    lines
+}
@@ -0,0 +1,10 @@
+//! Treetop Tree House: a grid of tree heights.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    let width = rng.size(1..=10);
+    (0..rng.size(1..=10))
+        .map(|_| (0..width).map(|_| char::from(b'0' + rng.below(10) as u8)).collect())
+        .collect()
+}
@@ -0,0 +1,9 @@
+//! Rope Bridge: head moves `U|R|D|L <steps>`.
+
+use crate::Rng;
+
+pub fn input(rng: &mut Rng) -> Vec<String> {
+    (0..rng.size(1..=30))
+        .map(|_| format!("{} {}", rng.choose(&['U', 'R', 'D', 'L']), rng.size(1..=8)))
+        .collect()
+}
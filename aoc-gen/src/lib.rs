@@ -0,0 +1,116 @@
+//! Seeded generators of valid puzzle inputs, one module per puzzle day,
+//! and a driver comparing a solver with a slow reference solver on many generated inputs.
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day17;
+pub mod day19;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+mod rng;
+
+use std::env;
+use std::fmt::Debug;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+pub use rng::Rng;
+
+/// Builds one input from the random source.
+pub type Generator = fn(&mut Rng) -> Vec<String>;
+
+/// Generator of the puzzle day, `None` for days without one.
+pub fn generator(day: u8) -> Option<Generator> {
+    let generator: Generator = match day {
+        1 => day1::input,
+        2 => day2::input,
+        3 => day3::input,
+        4 => day4::input,
+        5 => day5::input,
+        6 => day6::input,
+        7 => day7::input,
+        8 => day8::input,
+        9 => day9::input,
+        10 => day10::input,
+        11 => day11::input,
+        12 => day12::input,
+        13 => day13::input,
+        14 => day14::input,
+        17 => day17::input,
+        19 => day19::input,
+        21 => day21::input,
+        22 => day22::input,
+        23 => day23::input,
+        24 => day24::input,
+        25 => day25::input,
+        _ => return None,
+    };
+    Some(generator)
+}
+
+/// Input generated from `seed`, what `check` uses for that seed.
+pub fn generate(generator: Generator, seed: u64) -> Vec<String> {
+    generator(&mut Rng::new(seed))
+}
+
+/// Seeds `0..cases`; `AOC_GEN_CASES` changes the number of cases, `AOC_GEN_SEED` runs a single seed.
+fn seeds(cases: usize) -> Range<u64> {
+    let var = |name| env::var(name).ok().and_then(|value| value.parse::<u64>().ok());
+    match (var("AOC_GEN_SEED"), var("AOC_GEN_CASES")) {
+        (Some(seed), _) => seed..seed + 1,
+        (None, cases_var) => 0..cases_var.unwrap_or(cases as u64),
+    }
+}
+
+/// Runs `fast` and `reference` on the inputs generated for every seed
+/// and panics with the seed and the input on the first difference or panic.
+pub fn check<I, T, G, F, R>(cases: usize, generate: G, fast: F, reference: R)
+where I: Debug, T: PartialEq + Debug, G: Fn(&mut Rng) -> I, F: Fn(&I) -> T, R: Fn(&I) -> T {
+    for seed in seeds(cases) {
+        let input = generate(&mut Rng::new(seed));
+        let run = |name: &str, solve: &dyn Fn(&I) -> T| {
+            panic::catch_unwind(AssertUnwindSafe(|| solve(&input)))
+                .unwrap_or_else(|_| panic!("{} solver panicked on seed {}, input:\n{:#?}", name, seed, input))
+        };
+        let (actual, expected) = (run("fast", &fast), run("reference", &reference));
+        assert!(actual == expected, "seed {}: got {:?}, the reference gives {:?}, input:\n{:#?}", seed, actual, expected, input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators() {
+        for day in 1..=25 {
+            if let Some(generator) = generator(day) {
+                assert_eq!(generate(generator, 3), generate(generator, 3), "day {} is not reproducible", day);
+                assert!(!generate(generator, 3).is_empty(), "day {} generated no input", day);
+            }
+        }
+        assert!(generator(15).is_none());
+    }
+
+    #[test]
+    fn test_check() {
+        check(50, |rng| rng.size(0..=100), |&x| x * 2, |&x| x + x);
+        let result = panic::catch_unwind(|| check(50, |rng| rng.size(0..=100), |&x| x.min(90), |&x| x));
+        assert!(result.is_err());
+    }
+}
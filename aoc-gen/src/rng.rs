@@ -0,0 +1,83 @@
+use std::ops::RangeInclusive;
+
+/// SplitMix64, small and reproducible: the same seed gives the same inputs on every platform.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, `n` must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn int(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        let span = end.wrapping_sub(start) as u64;
+        match span.checked_add(1) {
+            Some(n) => start.wrapping_add(self.below(n) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    pub fn size(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {}..={}", start, end);
+        start + self.below((end - start) as u64 + 1) as usize
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.int(-3..=3)));
+            assert!((2..=5).contains(&rng.size(2..=5)));
+        }
+        assert_eq!(4, rng.size(4..=4));
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!([1, 2, 3, 4, 5], items);
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        assert_eq!((0..10).map(|_| a.next_u64()).collect::<Vec<_>>(), (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use aoc_common::solution;
+use aoc_common::{Input, Part, Runner};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Solve(Part::One)),
+            "part2" => Ok(Phase::Solve(Part::Two)),
+            _ => Err(format!("Unknown phase {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Nearest rank statistics, `None` for no samples.
+    pub fn new(samples: &mut [Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let rank = |p: usize| samples[((samples.len() * p).div_ceil(100)).max(1) - 1];
+        Some(Self { min: samples[0], median: rank(50), p95: rank(95) })
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Runs parsing and every part `runs` times, the parts with the `params` overrides.
+pub fn measure(runner: &dyn Runner, text: &Input, parts: &[Part], params: &[(String, String)], runs: usize)
+    -> Result<Vec<(Phase, Stats)>, solution::Error> {
+    let mut samples = Vec::with_capacity(runs);
+    let mut input = None;
+    for _ in 0..runs {
+        let (parsed, elapsed) = time(|| runner.parse(text));
+        samples.push(elapsed);
+        input = Some(parsed?);
+    }
+    let mut result = Vec::new();
+    let Some(input) = input else { return Ok(result) };
+    result.extend(Stats::new(&mut samples).map(|stats| (Phase::Parse, stats)));
+    for &part in parts {
+        samples.clear();
+        for _ in 0..runs {
+            let (answer, elapsed) = time(|| runner.solve_with(input.as_ref(), part, params));
+            answer?;
+            samples.push(elapsed);
+        }
+        result.extend(Stats::new(&mut samples).map(|stats| (Phase::Solve(part), stats)));
+    }
+    Ok(result)
+}
+
+/// Median times saved by `aoc bench --save`, one `<day> <phase> <nanoseconds>` per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(u8, Phase), Duration>);
+
+impl Baseline {
+    /// A missing file is an empty baseline.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(data) => data.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<Duration> {
+        self.0.get(&(day, phase)).copied()
+    }
+
+    pub fn set(&mut self, day: u8, phase: Phase, median: Duration) {
+        self.0.insert((day, phase), median);
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut baseline = Self::default();
+        for (i, line) in s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let wrong = || format!("Wrong baseline line {}: {}", i + 1, line);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, phase, nanos] = fields[..] else { return Err(wrong()) };
+            let day = day.parse().map_err(|_| wrong())?;
+            let phase = phase.parse().map_err(|_| wrong())?;
+            let nanos = nanos.parse().map_err(|_| wrong())?;
+            baseline.set(day, phase, Duration::from_nanos(nanos));
+        }
+        Ok(baseline)
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ((day, phase), median) in &self.0 {
+            writeln!(f, "{} {} {}", day, phase, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// Relative change of `current` against `baseline`, 0.1 is 10% slower.
+pub fn change(current: Duration, baseline: Duration) -> f64 {
+    current.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(None, Stats::new(&mut []));
+        let stats = Stats::new(&mut ms(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(Stats { min: ms(&[1])[0], median: ms(&[3])[0], p95: ms(&[5])[0] }, stats);
+        let mut samples = ms(&(1..=100).collect::<Vec<_>>());
+        let stats = Stats::new(&mut samples).unwrap();
+        assert_eq!((50, 95), (stats.median.as_millis(), stats.p95.as_millis()));
+    }
+
+    #[test]
+    fn test_baseline() {
+        let baseline: Baseline = "12 part2 1500\n\n12 parse 20\n".parse().unwrap();
+        assert_eq!(Some(Duration::from_nanos(20)), baseline.get(12, Phase::Parse));
+        assert_eq!(None, baseline.get(12, Phase::Solve(Part::One)));
+        assert_eq!("12 parse 20\n12 part2 1500\n", baseline.to_string());
+        assert!("12 part3 10".parse::<Baseline>().is_err());
+        assert!("12 parse".parse::<Baseline>().is_err());
+    }
+
+    #[test]
+    fn test_change() {
+        let change = change(Duration::from_millis(12), Duration::from_millis(10));
+        assert!((change - 0.2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,653 @@
+mod bench;
+mod pool;
+mod registry;
+mod scaffold;
+mod step;
+mod summary;
+mod verify;
+
+use std::collections::BTreeSet;
+use std::env;
+use std::iter;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::cli::{self, Error, Options, Source, OPTIONS_USAGE};
+use aoc_common::Part;
+use aoc_common::render::{FrameFormat, FrameSink};
+use aoc_common::report::{Format, Record, Reporter};
+use bench::Baseline;
+use registry::Entry;
+use summary::Row;
+use verify::{Answers, Status};
+
+const RUN_USAGE: &str = "[--jobs <n>]";
+const BENCH_USAGE: &str = "[--runs <n>] [--baseline <path>] [--threshold <percent>] [--save]";
+const GEN_USAGE: &str = "[--seed <n>]";
+const RENDER_USAGE: &str = "--out <dir> [--frames ascii|ppm] [--scale <n>] [--every <n>]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Days {
+    All,
+    One(u8),
+}
+
+#[derive(Debug)]
+struct RunArgs {
+    days: Days,
+    options: Options,
+    /// Worker threads when several days run, the number of CPUs by default.
+    jobs: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VerifyArgs {
+    days: Days,
+    /// Checks the built-in examples against `example_answers.txt` instead of the puzzle inputs.
+    example: bool,
+}
+
+#[derive(Debug)]
+struct BenchArgs {
+    days: Days,
+    options: Options,
+    runs: usize,
+    /// Defaults to `bench_baseline.txt` in the workspace root.
+    baseline: Option<PathBuf>,
+    /// Allowed slowdown of the median against the baseline, in percent.
+    threshold: f64,
+    save: bool,
+}
+
+fn parse_days(day: Option<String>) -> Result<Days, Error> {
+    match day.as_deref() {
+        Some("all") => Ok(Days::All),
+        Some(day) => Ok(Days::One(day.parse().map_err(|_| Error::Usage(format!("Wrong day {}", day)))?)),
+        None => Err(Error::Usage("Day is not specified".to_string())),
+    }
+}
+
+fn parse_run_args<I: Iterator<Item = String>>(mut args: I) -> Result<RunArgs, Error> {
+    let days = parse_days(args.next())?;
+    let mut jobs = None;
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--jobs" | "-j" => {
+                let n = args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)))?;
+                jobs = Some(n.parse().ok().filter(|&n| n > 0).ok_or_else(|| Error::Usage(format!("Wrong number of jobs {}", n)))?);
+            },
+            _ => rest.push(arg),
+        }
+    }
+    let options = Options::parse(rest.into_iter())?;
+    if days == Days::All && !matches!(options.source, Source::Default | Source::Example) {
+        return Err(Error::Usage("Only --example can be combined with all".to_string()));
+    }
+    Ok(RunArgs { days, options, jobs })
+}
+
+fn parse_verify_args<I: Iterator<Item = String>>(args: I) -> Result<VerifyArgs, Error> {
+    let mut verify = VerifyArgs { days: Days::All, example: false };
+    let mut days = None;
+    for arg in args {
+        match arg.as_str() {
+            "--example" | "-e" => verify.example = true,
+            _ if days.is_none() => days = Some(parse_days(Some(arg))?),
+            _ => return Err(Error::Usage(format!("Unknown argument {}", arg))),
+        }
+    }
+    verify.days = days.unwrap_or(Days::All);
+    Ok(verify)
+}
+
+fn parse_bench_args<I: Iterator<Item = String>>(mut args: I) -> Result<BenchArgs, Error> {
+    let days = parse_days(args.next())?;
+    let mut bench = BenchArgs { days, options: Options::default(), runs: 10, baseline: None, threshold: 10.0, save: false };
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)));
+        match arg.as_str() {
+            "--runs" | "-n" => {
+                let runs = value()?;
+                bench.runs = runs.parse().ok().filter(|&n| n > 0)
+                    .ok_or_else(|| Error::Usage(format!("Wrong number of runs {}", runs)))?;
+            },
+            "--baseline" | "-b" => bench.baseline = Some(value()?.into()),
+            "--threshold" | "-t" => {
+                let threshold = value()?;
+                bench.threshold = threshold.parse().ok().filter(|&t: &f64| t >= 0.0)
+                    .ok_or_else(|| Error::Usage(format!("Wrong threshold {}", threshold)))?;
+            },
+            "--save" | "-s" => bench.save = true,
+            _ => rest.push(arg),
+        }
+    }
+    let run = parse_run_args(iter::once("all".to_string()).chain(rest))?;
+    if run.options.format != Format::Text {
+        return Err(Error::Usage("bench only prints text".to_string()));
+    }
+    if run.jobs.is_some() {
+        return Err(Error::Usage("bench runs one day at a time".to_string()));
+    }
+    bench.options = run.options;
+    Ok(bench)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct GenArgs {
+    day: u8,
+    seed: u64,
+}
+
+fn parse_gen_args<I: Iterator<Item = String>>(mut args: I) -> Result<GenArgs, Error> {
+    let day = match parse_days(args.next())? {
+        Days::One(day) => day,
+        Days::All => return Err(Error::Usage("gen takes a single day".to_string())),
+    };
+    let mut gen = GenArgs { day, seed: 0 };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" | "-s" => {
+                let seed = args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)))?;
+                gen.seed = seed.parse().map_err(|_| Error::Usage(format!("Wrong seed {}", seed)))?;
+            },
+            _ => return Err(Error::Usage(format!("Unknown argument {}", arg))),
+        }
+    }
+    Ok(gen)
+}
+
+fn gen(args: GenArgs) -> Result<(), Error> {
+    let generator = aoc_gen::generator(args.day)
+        .ok_or_else(|| Error::Usage(format!("Day {} has no generator", args.day)))?;
+    let mut out = io::stdout().lock();
+    for line in aoc_gen::generate(generator, args.seed) {
+        writeln!(out, "{}", line).map_err(Error::Output)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct RenderArgs {
+    day: u8,
+    options: Options,
+    /// Frames of every part go to `part1` and `part2` below it.
+    out: PathBuf,
+    format: FrameFormat,
+    scale: usize,
+    every: usize,
+}
+
+fn parse_render_args<I: Iterator<Item = String>>(mut args: I) -> Result<RenderArgs, Error> {
+    let day = match parse_days(args.next())? {
+        Days::One(day) => day,
+        Days::All => return Err(Error::Usage("render takes a single day".to_string())),
+    };
+    let (mut out, mut format, mut scale, mut every) = (None, FrameFormat::default(), 4, 1);
+    let mut rest = Vec::new();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| Error::Usage(format!("{} expects a value", arg)));
+        match arg.as_str() {
+            "--out" | "-o" => out = Some(PathBuf::from(value()?)),
+            "--frames" => format = value()?.parse().map_err(Error::Usage)?,
+            "--scale" => {
+                let n = value()?;
+                scale = n.parse().ok().filter(|&n| n > 0).ok_or_else(|| Error::Usage(format!("Wrong scale {}", n)))?;
+            },
+            "--every" => {
+                let n = value()?;
+                every = n.parse().ok().filter(|&n| n > 0).ok_or_else(|| Error::Usage(format!("Wrong frame step {}", n)))?;
+            },
+            _ => rest.push(arg),
+        }
+    }
+    let out = out.ok_or_else(|| Error::Usage("render needs --out <dir>".to_string()))?;
+    let options = Options::parse(rest.into_iter())?;
+    if options.format != Format::Text {
+        return Err(Error::Usage("render only prints text".to_string()));
+    }
+    Ok(RenderArgs { day, options, out, format, scale, every })
+}
+
+fn render(args: RenderArgs) -> Result<(), Error> {
+    let entries = registry::entries();
+    let entry = select(&entries, Days::One(args.day))?[0];
+    let default = entry.input_path(&workspace_root());
+    let text = cli::read_source(&args.options.source, entry.runner.as_ref(), Some(&default))?;
+    let params = cli::read_params(&args.options, entry.day)?;
+    let input = entry.runner.parse(&text).map_err(|e| Error::Solution(entry.day, e))?;
+    for &part in &args.options.parts {
+        let dir = args.out.join(format!("part{}", part));
+        let mut sink = FrameSink::new(&dir, args.format).map_err(Error::Output)?
+            .with_scale(args.scale)
+            .with_every(args.every);
+        let drawn = entry.runner.record(input.as_ref(), part, &params, &mut sink).map_err(|e| Error::Solution(entry.day, e))?;
+        let frames = sink.finish().map_err(Error::Output)?;
+        if drawn {
+            println!("day {} part {}: {} frame(s) in {}", entry.day, part, frames, dir.display());
+        } else {
+            println!("day {} part {}: nothing to render", entry.day, part);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct StepArgs {
+    day: u8,
+    /// The first selected part, part 1 unless `--part 2` is given.
+    part: Part,
+    options: Options,
+}
+
+fn parse_step_args<I: Iterator<Item = String>>(mut args: I) -> Result<StepArgs, Error> {
+    let day = match parse_days(args.next())? {
+        Days::One(day) => day,
+        Days::All => return Err(Error::Usage("step takes a single day".to_string())),
+    };
+    let options = Options::parse(args)?;
+    if options.format != Format::Text {
+        return Err(Error::Usage("step only prints text".to_string()));
+    }
+    if options.source == Source::Stdin {
+        return Err(Error::Usage("step reads its commands from stdin".to_string()));
+    }
+    Ok(StepArgs { day, part: options.parts[0], options })
+}
+
+fn step(args: StepArgs) -> Result<(), Error> {
+    let entries = registry::entries();
+    let entry = select(&entries, Days::One(args.day))?[0];
+    let default = entry.input_path(&workspace_root());
+    let text = cli::read_source(&args.options.source, entry.runner.as_ref(), Some(&default))?;
+    let params = cli::read_params(&args.options, entry.day)?;
+    let input = entry.runner.parse(&text).map_err(|e| Error::Solution(entry.day, e))?;
+    let mut session = entry.runner.simulation(input.as_ref(), args.part, &params)
+        .map_err(|e| Error::Solution(entry.day, e))?
+        .ok_or_else(|| Error::Usage(format!("Day {} part {} has no simulation", args.day, args.part)))?;
+    let title = format!("day {} part {}", args.day, args.part);
+    step::interact(session.as_mut(), &title).map_err(Error::Output)
+}
+
+fn parse_params_args<I: Iterator<Item = String>>(mut args: I) -> Result<Days, Error> {
+    let days = parse_days(args.next())?;
+    match args.next() {
+        Some(arg) => Err(Error::Usage(format!("Unknown argument {}", arg))),
+        None => Ok(days),
+    }
+}
+
+/// Lists the parameters of the selected days with their defaults.
+fn params(days: Days) -> Result<(), Error> {
+    let entries = registry::entries();
+    for entry in select(&entries, days)? {
+        for (name, value) in entry.runner.params() {
+            println!("{:>3}  {:<12}  {}", entry.day, name, value);
+        }
+    }
+    Ok(())
+}
+
+fn parse_new_args<I: Iterator<Item = String>>(mut args: I) -> Result<u8, Error> {
+    let day = match parse_days(args.next())? {
+        Days::One(day) => day,
+        Days::All => return Err(Error::Usage("new takes a single day".to_string())),
+    };
+    match args.next() {
+        Some(arg) => Err(Error::Usage(format!("Unknown argument {}", arg))),
+        None => Ok(day),
+    }
+}
+
+fn new(day: u8) -> Result<(), Error> {
+    let root = workspace_root();
+    let changed = scaffold::new_day(&root, day).map_err(|e| match e {
+        scaffold::Error::Io(path, e) => Error::Input(path.display().to_string(), e),
+        e => Error::Usage(e.to_string()),
+    })?;
+    for path in changed {
+        println!("{}", path.strip_prefix(&root).unwrap_or(&path).display());
+    }
+    println!("put the puzzle input in day{}/input.txt and the example in day{}/example.txt", day, day);
+    Ok(())
+}
+
+fn workspace_root() -> PathBuf {
+    env::var_os("AOC_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/..")))
+}
+
+fn run_entry<W: Write>(entry: &Entry, options: &Options, reporter: &mut Reporter<W>) -> Result<(), Error> {
+    let default = entry.input_path(&workspace_root());
+    let text = cli::read_source(&options.source, entry.runner.as_ref(), Some(&default))?;
+    let params = cli::read_params(options, entry.day)?;
+    cli::run_parts(entry.runner.as_ref(), &text, &options.parts, &params, reporter)
+}
+
+/// Records of the selected parts, when the input or the parameters cannot be read every part fails.
+fn solve_entry(entry: &Entry, options: &Options) -> Vec<Record> {
+    let default = entry.input_path(&workspace_root());
+    let prepared = cli::read_source(&options.source, entry.runner.as_ref(), Some(&default))
+        .and_then(|text| Ok((text, cli::read_params(options, entry.day)?)));
+    match prepared {
+        Ok((text, params)) => cli::solve_parts(entry.runner.as_ref(), &text, &options.parts, &params),
+        Err(error) => options.parts.iter().map(|&part| Record {
+            day: entry.day,
+            part,
+            answer: Err(error.to_string()),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        }).collect(),
+    }
+}
+
+fn select(entries: &[Entry], days: Days) -> Result<Vec<&Entry>, Error> {
+    match days {
+        Days::One(day) => {
+            let entry = entries.iter().find(|e| e.day == day)
+                .ok_or_else(|| Error::Usage(format!("Day {} is not registered", day)))?;
+            Ok(vec![entry])
+        },
+        Days::All => Ok(entries.iter().collect()),
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), Error> {
+    let entries = registry::entries();
+    let selected = select(&entries, args.days)?;
+    let mut reporter = Reporter::new(args.options.format, io::stdout().lock());
+    if let [entry] = selected[..] {
+        return run_entry(entry, &args.options, &mut reporter);
+    }
+    let workers = args.jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from));
+    let start = Instant::now();
+    let results = pool::run(selected.clone(), workers, |entry| solve_entry(entry, &args.options));
+    let wall = start.elapsed();
+    let mut rows = Vec::new();
+    for (entry, result) in selected.iter().zip(results) {
+        match result {
+            Ok(records) => rows.extend(records.into_iter().map(Row::new)),
+            Err(message) => rows.extend(args.options.parts.iter().map(|&part| Row::panic(entry.day, part, message.clone()))),
+        }
+    }
+    if reporter.format() == Format::Text {
+        let table = summary::table(&mut rows, wall, io::stdout().is_terminal());
+        write!(reporter.into_inner(), "{}", table).map_err(Error::Output)?;
+    } else {
+        rows.sort_by_key(|row| (row.record.day, row.record.part));
+        for row in &rows {
+            reporter.write(&row.record).map_err(Error::Output)?;
+        }
+    }
+    let failed = rows.iter().filter(|row| row.status != summary::Status::Ok).map(|row| row.record.day).collect::<BTreeSet<_>>();
+    if failed.is_empty() { Ok(()) } else { Err(Error::DaysFailed(failed.len())) }
+}
+
+fn bench_entry(entry: &Entry, args: &BenchArgs, baseline: &mut Baseline) -> Result<usize, Error> {
+    let default = entry.input_path(&workspace_root());
+    let text = cli::read_source(&args.options.source, entry.runner.as_ref(), Some(&default))?;
+    let params = cli::read_params(&args.options, entry.day)?;
+    let measured = bench::measure(entry.runner.as_ref(), &text, &args.options.parts, &params, args.runs)
+        .map_err(|e| Error::Solution(entry.day, e))?;
+    let mut regressions = 0;
+    for (phase, stats) in measured {
+        let previous = baseline.get(entry.day, phase);
+        let change = previous.map(|previous| bench::change(stats.median, previous) * 100.0);
+        let regressed = change.is_some_and(|change| change > args.threshold);
+        let previous = previous.map_or("-".to_string(), |previous| format!("{:.1?}", previous));
+        let change = change.map_or("-".to_string(), |change| format!("{:+.1}%", change));
+        println!("{:>3}  {:<5}  {:>10.1?}  {:>10.1?}  {:>10.1?}  {:>10}  {:>7}{}",
+            entry.day, phase, stats.min, stats.median, stats.p95, previous, change,
+            if regressed { "  REGRESSION" } else { "" });
+        regressions += regressed as usize;
+        if args.save {
+            baseline.set(entry.day, phase, stats.median);
+        }
+    }
+    Ok(regressions)
+}
+
+fn bench(args: BenchArgs) -> Result<(), Error> {
+    let entries = registry::entries();
+    let selected = select(&entries, args.days)?;
+    let path = args.baseline.clone().unwrap_or_else(|| workspace_root().join("bench_baseline.txt"));
+    let mut baseline = Baseline::load(&path).map_err(|e| Error::Input(path.display().to_string(), e))?;
+    println!("{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {:>10}  {:>7}", "day", "phase", "min", "median", "p95", "baseline", "change");
+    let (mut failed, mut regressions) = (0, 0);
+    for entry in selected {
+        match bench_entry(entry, &args, &mut baseline) {
+            Ok(count) => regressions += count,
+            Err(error) => {
+                eprintln!("{}", error);
+                failed += 1;
+            }
+        }
+    }
+    if args.save {
+        baseline.save(&path).map_err(Error::Output)?;
+        println!("baseline saved to {}", path.display());
+    }
+    if failed > 0 {
+        Err(Error::DaysFailed(failed))
+    } else if regressions > 0 {
+        Err(Error::Regressions(regressions, args.threshold))
+    } else {
+        Ok(())
+    }
+}
+
+fn verify_entry(entry: &Entry, example: bool) -> Result<Vec<(Part, Status)>, Error> {
+    let root = workspace_root();
+    let (path, source) = if example {
+        (entry.example_answers_path(&root), Source::Example)
+    } else {
+        (entry.answers_path(&root), Source::Default)
+    };
+    let answers = Answers::load(&path).map_err(|e| Error::Input(path.display().to_string(), e))?;
+    // examples of some parts are too slow or do not apply, only the recorded ones are checked
+    let parts = Part::ALL.iter().copied().filter(|&part| !example || answers.get(part).is_some()).collect::<Vec<_>>();
+    let text = cli::read_source(&source, entry.runner.as_ref(), Some(&entry.input_path(&root)))?;
+    let input = match entry.runner.parse(&text) {
+        Ok(input) => input,
+        Err(error) => return Ok(parts.iter().map(|&part| (part, Status::Error(error.to_string()))).collect()),
+    };
+    Ok(parts.iter().map(|&part| {
+        let actual = entry.runner.solve(input.as_ref(), part).map_err(|e| e.to_string());
+        (part, Status::check(answers.get(part), actual))
+    }).collect())
+}
+
+fn verify(args: VerifyArgs) -> Result<(), Error> {
+    let entries = registry::entries();
+    let (mut failed, mut missing) = (0, 0);
+    println!("{:>3}  {:>4}  {:<7}  answer", "day", "part", "status");
+    for entry in select(&entries, args.days)? {
+        let results = match verify_entry(entry, args.example) {
+            Ok(results) => results,
+            Err(error) => {
+                eprintln!("{}", error);
+                failed += 1;
+                continue;
+            }
+        };
+        for (part, status) in results {
+            let (name, details) = match &status {
+                Status::Pass => ("pass", String::new()),
+                Status::Fail { expected, actual } => ("FAIL", format!("expected {}, got {}", summary::short(expected), summary::short(actual))),
+                Status::Missing(actual) => ("missing", format!("got {}", summary::short(actual))),
+                Status::Error(error) | Status::Unanswered(error) => ("ERROR", error.clone()),
+            };
+            let row = format!("{:>3}  {:>4}  {:<7}  {}", entry.day, part.to_string(), name, details);
+            println!("{}", row.trim_end());
+            failed += status.is_failure() as usize;
+            missing += matches!(status, Status::Missing(_) | Status::Unanswered(_)) as usize;
+        }
+    }
+    if missing > 0 {
+        println!("{} part(s) have no recorded answer", missing);
+    }
+    if failed > 0 { Err(Error::VerifyFailed(failed)) } else { Ok(()) }
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("run") => parse_run_args(args).and_then(run),
+        Some("bench") => parse_bench_args(args).and_then(bench),
+        Some("verify") => parse_verify_args(args).and_then(verify),
+        Some("gen") => parse_gen_args(args).and_then(gen),
+        Some("render") => parse_render_args(args).and_then(render),
+        Some("step") => parse_step_args(args).and_then(step),
+        Some("params") => parse_params_args(args).and_then(params),
+        Some("new") => parse_new_args(args).and_then(new),
+        Some(command) => Err(Error::Usage(format!("Unknown command {}", command))),
+        None => Err(Error::Usage("Command is not specified".to_string())),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            if let Error::Usage(_) = error {
+                eprintln!("usage: aoc run <day|all> {} {}", RUN_USAGE, OPTIONS_USAGE);
+                eprintln!("       aoc verify [day|all] [--example]");
+                eprintln!("       aoc bench <day|all> {} {}", BENCH_USAGE, OPTIONS_USAGE);
+                eprintln!("       aoc gen <day> {}", GEN_USAGE);
+                eprintln!("       aoc render <day> {} {}", RENDER_USAGE, OPTIONS_USAGE);
+                eprintln!("       aoc step <day> {}", OPTIONS_USAGE);
+                eprintln!("       aoc params <day|all>");
+                eprintln!("       aoc new <day>");
+            }
+            error.exit_code()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(|s| s.to_string())
+    }
+
+    #[test]
+    fn test_parse_run_args() {
+        let run_args = parse_run_args(args("12 --part 2 --example")).unwrap();
+        assert_eq!(Days::One(12), run_args.days);
+        assert_eq!(vec![Part::Two], run_args.options.parts);
+        assert_eq!(Source::Example, run_args.options.source);
+        let run_args = parse_run_args(args("all")).unwrap();
+        assert_eq!(Days::All, run_args.days);
+        assert_eq!(vec![Part::One, Part::Two], run_args.options.parts);
+        assert_eq!(None, run_args.jobs);
+        let run_args = parse_run_args(args("all -j 3 --example")).unwrap();
+        assert_eq!((Some(3), Source::Example), (run_args.jobs, run_args.options.source));
+    }
+
+    #[test]
+    fn test_parse_run_args_errors() {
+        assert!(matches!(parse_run_args(args("")), Err(Error::Usage(_))));
+        assert!(matches!(parse_run_args(args("x")), Err(Error::Usage(_))));
+        assert!(matches!(parse_run_args(args("1 --part 3")), Err(Error::Usage(_))));
+        assert!(matches!(parse_run_args(args("all --input day1/input")), Err(Error::Usage(_))));
+        assert!(matches!(parse_run_args(args("all --jobs 0")), Err(Error::Usage(_))));
+        assert!(matches!(parse_run_args(args("all --jobs")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_verify_args() {
+        assert_eq!(VerifyArgs { days: Days::All, example: false }, parse_verify_args(args("")).unwrap());
+        assert_eq!(VerifyArgs { days: Days::All, example: true }, parse_verify_args(args("all --example")).unwrap());
+        assert_eq!(VerifyArgs { days: Days::One(7), example: true }, parse_verify_args(args("-e 7")).unwrap());
+        assert!(matches!(parse_verify_args(args("7 8")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_verify_examples() {
+        for entry in registry::entries() {
+            for (part, status) in verify_entry(&entry, true).unwrap() {
+                assert_eq!(Status::Pass, status, "day {} part {}", entry.day, part);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bench_params() {
+        let entries = registry::entries();
+        let entry = select(&entries, Days::One(17)).unwrap()[0];
+        let text = aoc_common::Input::from(entry.runner.example());
+        let params = [("rocks1".to_string(), "10".to_string())];
+        assert_eq!(2, bench::measure(entry.runner.as_ref(), &text, &[Part::One], &params, 2).unwrap().len());
+        let params = [("rocks1".to_string(), "x".to_string())];
+        assert!(bench::measure(entry.runner.as_ref(), &text, &[Part::One], &params, 2).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_args() {
+        let bench_args = parse_bench_args(args("17 -n 5 --part 2 --threshold 2.5 --save --example")).unwrap();
+        assert_eq!(Days::One(17), bench_args.days);
+        assert_eq!((5, 2.5, true, None), (bench_args.runs, bench_args.threshold, bench_args.save, bench_args.baseline));
+        assert_eq!(vec![Part::Two], bench_args.options.parts);
+        assert_eq!(Source::Example, bench_args.options.source);
+        let bench_args = parse_bench_args(args("all -b base.txt")).unwrap();
+        assert_eq!(Some(PathBuf::from("base.txt")), bench_args.baseline);
+        let bench_args = parse_bench_args(args("17 -P rocks1=10 --config variants.conf")).unwrap();
+        assert_eq!(vec![("rocks1".to_string(), "10".to_string())], bench_args.options.params);
+        assert_eq!(Some(PathBuf::from("variants.conf")), bench_args.options.config);
+        assert!(matches!(parse_bench_args(args("1 --runs 0")), Err(Error::Usage(_))));
+        assert!(matches!(parse_bench_args(args("1 --threshold")), Err(Error::Usage(_))));
+        assert!(matches!(parse_bench_args(args("1 --format json")), Err(Error::Usage(_))));
+        assert!(matches!(parse_bench_args(args("all --jobs 2")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_gen_args() {
+        assert_eq!(GenArgs { day: 11, seed: 0 }, parse_gen_args(args("11")).unwrap());
+        assert_eq!(GenArgs { day: 11, seed: 42 }, parse_gen_args(args("11 --seed 42")).unwrap());
+        assert!(matches!(parse_gen_args(args("all")), Err(Error::Usage(_))));
+        assert!(matches!(parse_gen_args(args("11 --seed x")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_render_args() {
+        let render_args = parse_render_args(args("14 --example -o frames --frames ascii --every 10 --part 2")).unwrap();
+        assert_eq!((14, PathBuf::from("frames")), (render_args.day, render_args.out));
+        assert_eq!((FrameFormat::Ascii, 4, 10), (render_args.format, render_args.scale, render_args.every));
+        assert_eq!(vec![Part::Two], render_args.options.parts);
+        assert_eq!(Source::Example, render_args.options.source);
+        assert!(matches!(parse_render_args(args("14")), Err(Error::Usage(_))));
+        assert!(matches!(parse_render_args(args("all -o frames")), Err(Error::Usage(_))));
+        assert!(matches!(parse_render_args(args("14 -o frames --frames png")), Err(Error::Usage(_))));
+        assert!(matches!(parse_render_args(args("14 -o frames --scale 0")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_new_args() {
+        assert_eq!(15, parse_new_args(args("15")).unwrap());
+        assert!(matches!(parse_new_args(args("all")), Err(Error::Usage(_))));
+        assert!(matches!(parse_new_args(args("15 16")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_params_args() {
+        assert_eq!(Days::One(11), parse_params_args(args("11")).unwrap());
+        assert_eq!(Days::All, parse_params_args(args("all")).unwrap());
+        assert!(matches!(parse_params_args(args("11 12")), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_parse_step_args() {
+        let step_args = parse_step_args(args("23 --example")).unwrap();
+        assert_eq!((23, Part::One, Source::Example), (step_args.day, step_args.part, step_args.options.source));
+        assert_eq!(Part::Two, parse_step_args(args("23 --part 2")).unwrap().part);
+        let params = parse_step_args(args("23 -P rounds=3")).unwrap().options.params;
+        assert_eq!(vec![("rounds".to_string(), "3".to_string())], params);
+        assert!(matches!(parse_step_args(args("all")), Err(Error::Usage(_))));
+        assert!(matches!(parse_step_args(args("23 -")), Err(Error::Usage(_))));
+    }
+}
@@ -0,0 +1,57 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use aoc_common::pipeline::panic_message;
+
+/// Runs `job` on every item on up to `workers` threads, the results are in the order of `items`.
+/// A job that panics gives `Err` with the panic message and does not stop the others.
+pub fn run<T, R, F>(items: Vec<T>, workers: usize, job: F) -> Vec<Result<R, String>>
+where T: Send, R: Send, F: Fn(T) -> R + Sync {
+    let count = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, count.max(1)) {
+            let (queue, job, sender) = (&queue, &job, sender.clone());
+            scope.spawn(move || loop {
+                // the lock is released before the job runs, so a panicking job cannot poison it
+                let Some((i, item)) = queue.lock().unwrap().next() else { break };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| job(item))).map_err(panic_message);
+                sender.send((i, result)).unwrap();
+            });
+        }
+    });
+    drop(sender);
+    let mut results = (0..count).map(|_| None).collect::<Vec<_>>();
+    for (i, result) in receiver {
+        results[i] = Some(result);
+    }
+    results.into_iter().map(|result| result.expect("every job sends its result")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let squares = run((0..50).collect(), 4, |n: u64| n * n);
+        assert_eq!((0..50).map(|n| Ok(n * n)).collect::<Vec<_>>(), squares);
+        assert_eq!(vec![Ok(2)], run(vec![1], 0, |n: i32| n + 1));
+        assert!(run(Vec::<i32>::new(), 4, |n| n).is_empty());
+    }
+
+    #[test]
+    fn test_panic() {
+        let results = run(vec![1, 0, 2, 0], 2, |n: i32| {
+            if n == 0 {
+                panic!("zero");
+            }
+            10 / n
+        });
+        assert_eq!(vec![Ok(10), Err("zero".to_string()), Ok(5), Err("zero".to_string())], results);
+        let results = run(vec![0], 1, |n: usize| if n == 0 { panic!("{} items", n) } else { n });
+        assert_eq!(vec![Err("0 items".to_string())], results);
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::{Runner, Solution, Solver};
+
+pub struct Entry {
+    pub day: u8,
+    /// Crate directory relative to the workspace root, it also holds the puzzle input.
+    pub dir: &'static str,
+    pub runner: Box<dyn Runner>,
+}
+
+impl Entry {
+    fn new<S>(dir: &'static str) -> Self
+    where S: Solution + 'static, S::Input: 'static {
+        Self { day: S::DAY, dir, runner: Box::new(Solver::<S>::new()) }
+    }
+
+    pub fn input_path(&self, root: &Path) -> PathBuf {
+        let dir = root.join(self.dir);
+        let txt = dir.join("input.txt");
+        if txt.exists() { txt } else { dir.join("input") }
+    }
+
+    pub fn answers_path(&self, root: &Path) -> PathBuf {
+        root.join(self.dir).join("answers.txt")
+    }
+
+    /// Answers for the built-in example, parts without one are not checked.
+    pub fn example_answers_path(&self, root: &Path) -> PathBuf {
+        root.join(self.dir).join("example_answers.txt")
+    }
+}
+
+pub fn entries() -> Vec<Entry> {
+    vec![
+        Entry::new::<day1::Day1>("day1"),
+        Entry::new::<day2_2::Day2>("day2_2"),
+        Entry::new::<day3::Day3>("day3"),
+        Entry::new::<day4::Day4>("day4"),
+        Entry::new::<day5::Day5>("day5"),
+        Entry::new::<day6::Day6>("day6"),
+        Entry::new::<day7::Day7>("day7"),
+        Entry::new::<day8::Day8>("day8"),
+        Entry::new::<day9::Day9>("day9"),
+        Entry::new::<day10::Day10>("day10"),
+        Entry::new::<day11::Day11>("day11"),
+        Entry::new::<day12::Day12>("day12"),
+        Entry::new::<day13::Day13>("day13"),
+        Entry::new::<day14::Day14>("day14"),
+        Entry::new::<day17::Day17>("day17"),
+        Entry::new::<day19::Day19>("day19"),
+        Entry::new::<day21::Day21>("day21"),
+        Entry::new::<day22::Day22>("day22"),
+        Entry::new::<day23::Day23>("day23"),
+        Entry::new::<day24::Day24>("day24"),
+        Entry::new::<day25::Day25>("day25"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_sorted() {
+        let days = entries().iter().map(|e| e.day).collect::<Vec<_>>();
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -0,0 +1,243 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = r#"[package]
+name = "day{day}"
+version.workspace = true
+edition.workspace = true
+
+[dependencies]
+aoc-common.workspace = true
+thiserror.workspace = true
+
+[lints]
+workspace = true
+"#;
+
+const MAIN_RS: &str = r#"use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    aoc_common::cli::day_main::<day{day}::Day{day}>()
+}
+"#;
+
+const LIB_RS: &str = r#"//! Day {day}.
+
+mod task;
+
+use aoc_common::Solution;
+
+pub use task::{parse_input, Error};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = task::Error;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_input(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        task::task1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        task::task2(input)
+    }
+}
+"#;
+
+const TASK_RS: &str = r#"use aoc_common::parse::{self, ParseError};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("Not solved yet")]
+    Unsolved,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+pub fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<Vec<String>, ParseError> {
+    Ok(parse::lines(lines).map(|line| line.text.to_string()).collect())
+}
+
+pub fn task1(_input: &[String]) -> Result<usize, Error> {
+    Err(Error::Unsolved)
+}
+
+pub fn task2(_input: &[String]) -> Result<usize, Error> {
+    Err(Error::Unsolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const DATA: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_parse() {
+        let lines = DATA.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), parse_input(&lines).unwrap().len());
+    }
+}
+"#;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Day {0} is not between 1 and 25")]
+    WrongDay(u8),
+    #[error("{0} already exists")]
+    Exists(PathBuf),
+    #[error("Day {0} is already listed in {1}")]
+    Listed(u8, PathBuf),
+    #[error("{0}: {1}")]
+    Io(PathBuf, io::Error),
+}
+
+/// Lines listing the days, kept sorted by day: the line prefix before the day number and the line to add.
+struct Listing {
+    file: &'static str,
+    prefix: &'static str,
+    line: &'static str,
+}
+
+const LISTINGS: [Listing; 3] = [
+    Listing { file: "Cargo.toml", prefix: "\"day", line: "    \"day{day}\"," },
+    Listing { file: "aoc/Cargo.toml", prefix: "day", line: "day{day} = { path = \"../day{day}\" }" },
+    Listing { file: "aoc/src/registry.rs", prefix: "Entry::new::<day", line: "        Entry::new::<day{day}::Day{day}>(\"day{day}\")," },
+];
+
+fn fill(template: &str, day: u8) -> String {
+    template.replace("{day}", &day.to_string())
+}
+
+/// Day number of a listing line, `day2_2` counts as day 2.
+fn listed_day(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    rest[..digits].parse().ok()
+}
+
+/// Adds `line` before the first listed day after `day`, `None` when the exact line is already there
+/// or the file lists no days at all.
+fn insert_sorted(text: &str, prefix: &str, day: u8, line: &str) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    if lines.contains(&line) {
+        return None;
+    }
+    let listed = lines.iter().enumerate()
+        .filter_map(|(i, l)| listed_day(l, prefix).map(|d| (i, d)))
+        .collect::<Vec<_>>();
+    let &(last, _) = listed.last()?;
+    let at = listed.iter().find(|&&(_, d)| d > day).map_or(last + 1, |&(i, _)| i);
+    lines.insert(at, line);
+    let mut result = lines.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    Some(result)
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))
+}
+
+fn write(path: &Path, data: &str) -> Result<(), Error> {
+    fs::write(path, data).map_err(|e| Error::Io(path.to_path_buf(), e))
+}
+
+/// Creates the `dayN` crate under `root`, adds it to the workspace and registers it in the runner.
+/// Nothing is written when the crate already exists or the day is already listed.
+/// Returns the created and changed files.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::WrongDay(day));
+    }
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(Error::Exists(dir));
+    }
+    let mut listings = Vec::new();
+    for listing in &LISTINGS {
+        let path = root.join(listing.file);
+        let text = insert_sorted(&read(&path)?, listing.prefix, day, &fill(listing.line, day))
+            .ok_or_else(|| Error::Listed(day, path.clone()))?;
+        listings.push((path, text));
+    }
+
+    let files = [
+        ("Cargo.toml", CARGO_TOML),
+        ("src/main.rs", MAIN_RS),
+        ("src/lib.rs", LIB_RS),
+        ("src/task.rs", TASK_RS),
+        ("example.txt", ""),
+        ("example_answers.txt", ""),
+        ("answers.txt", ""),
+    ];
+    let mut changed = Vec::new();
+    fs::create_dir_all(dir.join("src")).map_err(|e| Error::Io(dir.join("src"), e))?;
+    for (name, template) in files {
+        let path = dir.join(name);
+        write(&path, &fill(template, day))?;
+        changed.push(path);
+    }
+    for (path, text) in listings {
+        write(&path, &text)?;
+        changed.push(path);
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_sorted() {
+        let text = "members = [\n    \"aoc\",\n    \"day1\",\n    \"day2_2\",\n    \"day17\",\n]\n";
+        let line = "    \"day15\",";
+        assert_eq!(
+            Some("members = [\n    \"aoc\",\n    \"day1\",\n    \"day2_2\",\n    \"day15\",\n    \"day17\",\n]\n".to_string()),
+            insert_sorted(text, "\"day", 15, line)
+        );
+        assert_eq!(
+            Some("members = [\n    \"aoc\",\n    \"day1\",\n    \"day2_2\",\n    \"day17\",\n    \"day18\",\n]\n".to_string()),
+            insert_sorted(text, "\"day", 18, "    \"day18\",")
+        );
+        assert_eq!(None, insert_sorted(text, "\"day", 17, "    \"day17\","));
+        assert_eq!(None, insert_sorted("[workspace]\n", "\"day", 1, "    \"day1\","));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::create_dir_all(root.join("day20")).unwrap();
+        fs::write(root.join("Cargo.toml"), "members = [\n    \"day14\",\n    \"day17\",\n]\n").unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), "day14 = { path = \"../day14\" }\n").unwrap();
+        fs::write(root.join("aoc/src/registry.rs"), "        Entry::new::<day14::Day14>(\"day14\"),\n").unwrap();
+
+        let changed = new_day(&root, 15).unwrap();
+        assert_eq!(10, changed.len());
+        assert!(fs::read_to_string(root.join("day15/src/lib.rs")).unwrap().contains("impl Solution for Day15 {"));
+        assert_eq!("", fs::read_to_string(root.join("day15/example.txt")).unwrap());
+        assert_eq!(
+            "members = [\n    \"day14\",\n    \"day15\",\n    \"day17\",\n]\n",
+            fs::read_to_string(root.join("Cargo.toml")).unwrap()
+        );
+        assert!(fs::read_to_string(root.join("aoc/src/registry.rs")).unwrap().ends_with("Entry::new::<day15::Day15>(\"day15\"),\n"));
+
+        assert!(matches!(new_day(&root, 15), Err(Error::Exists(_))));
+        assert!(matches!(new_day(&root, 20), Err(Error::Exists(_))));
+        assert!(matches!(new_day(&root, 26), Err(Error::WrongDay(26))));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt::Write as _;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use aoc_common::Session;
+
+pub const HELP: &str = "\
+<enter>, s [n]     step once or n times
+b [n]              step back once or n times
+u <cond>           run until a status counter matches, e.g. `u units>=20` or `u x=5`
+u end              run until the simulation ends
+q                  quit";
+
+/// Steps taken by `until` before giving up.
+const UNTIL_LIMIT: usize = 10_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// `<counter><op><value>` over the status counters of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    pub name: String,
+    pub op: Op,
+    pub value: i64,
+}
+
+impl Condition {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        const OPS: [(&str, Op); 6] = [("!=", Op::Ne), ("<=", Op::Le), (">=", Op::Ge), ("=", Op::Eq), ("<", Op::Lt), (">", Op::Gt)];
+        let (at, symbol, op) = OPS.iter()
+            .filter_map(|&(symbol, op)| s.find(symbol).map(|at| (at, symbol, op)))
+            .min_by_key(|&(at, symbol, _)| (at, usize::MAX - symbol.len()))
+            .ok_or_else(|| format!("Expected <counter><op><value> in {}", s))?;
+        let name = s[..at].trim();
+        let value = s[at + symbol.len()..].trim();
+        let value = value.parse().map_err(|_| format!("Wrong value {}", value))?;
+        if name.is_empty() {
+            return Err(format!("Expected a counter name in {}", s));
+        }
+        Ok(Self { name: name.to_string(), op, value })
+    }
+
+    /// `None` when the status has no counter of that name.
+    pub fn matches(&self, status: &[(&str, i64)]) -> Option<bool> {
+        let &(_, actual) = status.iter().find(|(name, _)| *name == self.name)?;
+        Some(match self.op {
+            Op::Eq => actual == self.value,
+            Op::Ne => actual != self.value,
+            Op::Lt => actual < self.value,
+            Op::Le => actual <= self.value,
+            Op::Gt => actual > self.value,
+            Op::Ge => actual >= self.value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Step(usize),
+    Back(usize),
+    Until(Condition),
+    End,
+    Help,
+    Quit,
+}
+
+fn count(arg: Option<&str>) -> Result<usize, String> {
+    match arg {
+        None => Ok(1),
+        Some(n) => n.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("Wrong count {}", n)),
+    }
+}
+
+pub fn parse_command(line: &str) -> Result<Command, String> {
+    let mut words = line.split_whitespace();
+    let command = match words.next() {
+        None => return Ok(Command::Step(1)),
+        Some(command) => command,
+    };
+    let rest = words.collect::<Vec<_>>();
+    match (command, &rest[..]) {
+        ("s" | "step", [] | [_]) => Ok(Command::Step(count(rest.first().copied())?)),
+        ("b" | "back", [] | [_]) => Ok(Command::Back(count(rest.first().copied())?)),
+        ("u" | "until", ["end"]) => Ok(Command::End),
+        ("u" | "until", [_, ..]) => Ok(Command::Until(Condition::parse(&rest.concat())?)),
+        ("h" | "help" | "?", []) => Ok(Command::Help),
+        ("q" | "quit", []) => Ok(Command::Quit),
+        _ => Err(format!("Unknown command {}", line.trim())),
+    }
+}
+
+fn status_line(session: &dyn Session) -> String {
+    let mut line = format!("step {}", session.steps());
+    for (name, value) in session.status() {
+        write!(line, "  {}={}", name, value).unwrap();
+    }
+    if session.finished() {
+        line.push_str("  (finished)");
+    }
+    line
+}
+
+/// Applies one command, returns the message for the status area.
+fn execute(session: &mut dyn Session, command: Command) -> String {
+    match command {
+        Command::Step(n) => {
+            let taken = session.step(n);
+            if taken < n { format!("stepped {} of {}, the simulation ended", taken, n) } else { String::new() }
+        },
+        Command::Back(n) => {
+            let undone = session.rewind(n);
+            if undone < n { format!("stepped back {} of {}, no older snapshots", undone, n) } else { String::new() }
+        },
+        Command::Until(condition) => {
+            if condition.matches(&session.status()).is_none() {
+                return format!("No counter {}", condition.name);
+            }
+            match session.run_until(&mut |status| condition.matches(status) == Some(true), UNTIL_LIMIT) {
+                Some(n) => format!("matched after {} step(s)", n),
+                None if session.finished() => "the simulation ended first".to_string(),
+                None => format!("no match in {} steps", UNTIL_LIMIT),
+            }
+        },
+        Command::End => {
+            let n = session.step(UNTIL_LIMIT);
+            if session.finished() { format!("ended after {} step(s)", n) } else { format!("still running after {} steps", n) }
+        },
+        Command::Help => HELP.to_string(),
+        Command::Quit => String::new(),
+    }
+}
+
+/// Reads commands from stdin and redraws the frame after each. On a terminal the screen is
+/// cleared and the frame is coloured with ANSI escapes, otherwise frames are printed one after another.
+pub fn interact(session: &mut dyn Session, title: &str) -> io::Result<()> {
+    let colour = io::stdout().is_terminal();
+    let mut out = io::stdout().lock();
+    let mut lines = io::stdin().lock().lines();
+    let mut message = "h for help".to_string();
+    loop {
+        if colour {
+            write!(out, "\x1b[2J\x1b[H")?;
+        }
+        write!(out, "{}\n{}", title, session.frame(colour))?;
+        writeln!(out, "{}", status_line(session))?;
+        if !message.is_empty() {
+            writeln!(out, "{}", message)?;
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+        let Some(line) = lines.next().transpose()? else { break };
+        message = match parse_command(&line) {
+            Ok(Command::Quit) => break,
+            Ok(command) => execute(session, command),
+            Err(error) => error,
+        };
+    }
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(Ok(Command::Step(1)), parse_command(""));
+        assert_eq!(Ok(Command::Step(25)), parse_command("s 25"));
+        assert_eq!(Ok(Command::Back(1)), parse_command("back"));
+        assert_eq!(Ok(Command::End), parse_command("u end"));
+        assert_eq!(Ok(Command::Quit), parse_command("q"));
+        let condition = Condition { name: "units".to_string(), op: Op::Ge, value: 20 };
+        assert_eq!(Ok(Command::Until(condition)), parse_command("until units >= 20"));
+        assert!(parse_command("s 0").is_err());
+        assert!(parse_command("s 1 2").is_err());
+        assert!(parse_command("jump").is_err());
+    }
+
+    #[test]
+    fn test_condition() {
+        let status = [("x", 3), ("cycle", 20)];
+        assert_eq!(Some(true), Condition::parse("x=3").unwrap().matches(&status));
+        assert_eq!(Some(false), Condition::parse("cycle<20").unwrap().matches(&status));
+        assert_eq!(Some(true), Condition::parse("cycle!=21").unwrap().matches(&status));
+        assert_eq!(Some(true), Condition::parse("x>-1").unwrap().matches(&status));
+        assert_eq!(None, Condition::parse("y=1").unwrap().matches(&status));
+        assert!(Condition::parse("x").is_err());
+        assert!(Condition::parse("=3").is_err());
+        assert!(Condition::parse("x=three").is_err());
+    }
+}
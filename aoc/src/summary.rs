@@ -0,0 +1,146 @@
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use aoc_common::report::Record;
+use aoc_common::Part;
+
+/// Longest answer or error shown in the table.
+const ANSWER_WIDTH: usize = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Error,
+    Panic,
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Error => "ERROR",
+            Status::Panic => "PANIC",
+        }
+    }
+
+    /// ANSI colour of the status.
+    fn colour(&self) -> &'static str {
+        match self {
+            Status::Ok => "\x1b[32m",
+            Status::Error => "\x1b[31m",
+            Status::Panic => "\x1b[1;35m",
+        }
+    }
+}
+
+/// One part of one day in the summary of a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub record: Record,
+    pub status: Status,
+}
+
+impl Row {
+    pub fn new(record: Record) -> Self {
+        let status = if record.answer.is_ok() { Status::Ok } else { Status::Error };
+        Self { record, status }
+    }
+
+    /// The day panicked before `part` got an answer.
+    pub fn panic(day: u8, part: Part, message: String) -> Self {
+        let answer = Err(format!("panicked: {}", message));
+        Self { record: Record { day, part, answer, parse_time: Duration::ZERO, solve_time: Duration::ZERO }, status: Status::Panic }
+    }
+}
+
+/// Multi-line answers are shown by their line count.
+pub fn short(answer: &str) -> String {
+    match answer.trim_end().lines().count() {
+        0 | 1 => answer.trim_end().to_string(),
+        n => format!("<{} lines>", n),
+    }
+}
+
+fn shown(row: &Row) -> String {
+    let text = match &row.record.answer {
+        Ok(answer) => short(answer),
+        Err(error) => short(error),
+    };
+    match text.char_indices().nth(ANSWER_WIDTH - 1) {
+        Some((at, _)) if text.chars().count() > ANSWER_WIDTH => format!("{}…", &text[..at]),
+        _ => text,
+    }
+}
+
+/// Table of the rows sorted by day and part, the time of a part includes parsing.
+/// The last line has the total wall-clock time of the run.
+pub fn table(rows: &mut [Row], wall: Duration, colour: bool) -> String {
+    rows.sort_by_key(|row| (row.record.day, row.record.part));
+    let answers = rows.iter().map(shown).collect::<Vec<_>>();
+    let width = answers.iter().map(|answer| answer.chars().count()).max().unwrap_or(0).max("answer".len());
+    let mut out = format!("{:>3}  {:>4}  {:<width$}  {:>10}  status\n", "day", "part", "answer", "time");
+    for (row, answer) in rows.iter().zip(&answers) {
+        let time = row.record.parse_time + row.record.solve_time;
+        let status = if colour {
+            format!("{}{}\x1b[0m", row.status.colour(), row.status.name())
+        } else {
+            row.status.name().to_string()
+        };
+        // pad by characters, `…` is wider than one byte
+        let padding = width - answer.chars().count();
+        writeln!(out, "{:>3}  {:>4}  {}{}  {:>10.1?}  {}",
+            row.record.day, row.record.part.to_string(), answer, " ".repeat(padding), time, status).unwrap();
+    }
+    let failed = rows.iter().filter(|row| row.status != Status::Ok).count();
+    writeln!(out, "{} part(s), {} failed, {:.1?} wall-clock", rows.len(), failed, wall).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(day: u8, part: Part, answer: Result<&str, &str>) -> Row {
+        Row::new(Record {
+            day,
+            part,
+            answer: answer.map(String::from).map_err(String::from),
+            parse_time: Duration::from_micros(1),
+            solve_time: Duration::from_micros(2),
+        })
+    }
+
+    #[test]
+    fn test_table() {
+        let mut rows = vec![
+            row(12, Part::One, Ok("31")),
+            row(10, Part::Two, Ok("#.\n.#\n")),
+            Row::panic(11, Part::One, "index out of bounds".to_string()),
+            row(10, Part::One, Err("bad line")),
+        ];
+        let table = table(&mut rows, Duration::from_millis(12), false);
+        assert_eq!("\
+day  part  answer                               time  status
+ 10     1  bad line                            3.0µs  ERROR
+ 10     2  <2 lines>                           3.0µs  ok
+ 11     1  panicked: index out of bounds       0.0ns  PANIC
+ 12     1  31                                  3.0µs  ok
+4 part(s), 2 failed, 12.0ms wall-clock
+", table);
+    }
+
+    #[test]
+    fn test_colour() {
+        let mut rows = vec![row(1, Part::One, Ok("1")), Row::panic(2, Part::One, "x".to_string())];
+        let table = table(&mut rows, Duration::ZERO, true);
+        assert!(table.contains("\x1b[32mok\x1b[0m"));
+        assert!(table.contains("\x1b[1;35mPANIC\x1b[0m"));
+    }
+
+    #[test]
+    fn test_long_answer() {
+        let answer = "x".repeat(60);
+        let shown = shown(&row(1, Part::One, Ok(&answer)));
+        assert_eq!((ANSWER_WIDTH, true), (shown.chars().count(), shown.ends_with('…')));
+    }
+}
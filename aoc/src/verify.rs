@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc_common::Part;
+
+/// Known answers for the real input, kept in `dayN/answers.txt`:
+///
+/// ```text
+/// part1: 12520
+/// part2:
+/// ####.#..#.
+/// #....#..#.
+/// ```
+///
+/// A multi-line answer starts on the line after its empty `partN:` header.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<Part, String>);
+
+impl Answers {
+    /// A missing file has no answers.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(data) => data.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        self.0.get(&part).map(String::as_str)
+    }
+}
+
+fn header(line: &str) -> Option<(Part, &str)> {
+    let (name, value) = line.split_once(':')?;
+    let part = match name {
+        "part1" => Part::One,
+        "part2" => Part::Two,
+        _ => return None,
+    };
+    Some((part, value.trim()))
+}
+
+impl FromStr for Answers {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+        let mut current: Option<(Part, Vec<&str>)> = None;
+        for (i, line) in s.lines().enumerate() {
+            match (header(line), &mut current) {
+                (Some((part, value)), _) => {
+                    if let Some((part, lines)) = current.take() {
+                        answers.insert(part, lines.join("\n"));
+                    }
+                    if answers.contains_key(&part) {
+                        return Err(format!("Line {}: part {} is given twice", i + 1, part));
+                    }
+                    if value.is_empty() {
+                        current = Some((part, Vec::new()));
+                    } else {
+                        answers.insert(part, value.to_string());
+                    }
+                },
+                (None, Some((_, lines))) => lines.push(line.trim_end()),
+                (None, None) if line.trim().is_empty() => {},
+                (None, None) => return Err(format!("Line {}: expected part1: or part2:, got {}", i + 1, line)),
+            }
+        }
+        if let Some((part, lines)) = current {
+            answers.insert(part, lines.join("\n"));
+        }
+        for answer in answers.values_mut() {
+            answer.truncate(answer.trim_end().len());
+        }
+        Ok(Self(answers))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    /// No recorded answer, it is reported but does not fail the check.
+    Missing(String),
+    Error(String),
+    /// No recorded answer and the solver fails, e.g. a part that is not solved. Reported as
+    /// an error, but like `Missing` it does not fail the check.
+    Unanswered(String),
+}
+
+impl Status {
+    pub fn check(expected: Option<&str>, actual: Result<String, String>) -> Self {
+        let normalize = |s: &str| s.lines().map(str::trim_end).collect::<Vec<_>>().join("\n").trim_end().to_string();
+        match (expected, actual) {
+            (None, Err(error)) => Status::Unanswered(error),
+            (Some(_), Err(error)) => Status::Error(error),
+            (None, Ok(actual)) => Status::Missing(actual),
+            (Some(expected), Ok(actual)) if normalize(expected) == normalize(&actual) => Status::Pass,
+            (Some(expected), Ok(actual)) => Status::Fail { expected: expected.to_string(), actual },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers: Answers = "part1: 12520\npart2:\n##.\n#.#  \n\n".parse().unwrap();
+        assert_eq!(Some("12520"), answers.get(Part::One));
+        assert_eq!(Some("##.\n#.#"), answers.get(Part::Two));
+        let answers: Answers = "part2: ho-ho-ho\n".parse().unwrap();
+        assert_eq!(None, answers.get(Part::One));
+        assert!("12520".parse::<Answers>().is_err());
+        assert!("part1: 1\npart1: 2".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Status::Pass, Status::check(Some("##.\n#.#"), Ok("##.  \n#.#\n".to_string())));
+        assert_eq!(
+            Status::Fail { expected: "1".to_string(), actual: "2".to_string() },
+            Status::check(Some("1"), Ok("2".to_string()))
+        );
+        assert_eq!(Status::Missing("2".to_string()), Status::check(None, Ok("2".to_string())));
+        assert!(Status::check(Some("1"), Err("bad".to_string())).is_failure());
+        assert!(!Status::check(None, Ok("2".to_string())).is_failure());
+        assert_eq!(Status::Unanswered("bad".to_string()), Status::check(None, Err("bad".to_string())));
+        assert!(!Status::check(None, Err("bad".to_string())).is_failure());
+    }
+}
@@ -0,0 +1,205 @@
+use std::cmp::Reverse;
+use std::fmt::Write as _;
+
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::report::Format;
+use aoc_common::PriorityQueue;
+
+/// The food items one elf carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// 0-based position of the elf in the input.
+    pub index: usize,
+    /// 0-based index of the first line of the elf.
+    pub line: usize,
+    pub items: Vec<i64>,
+}
+
+impl Elf {
+    pub fn total(&self) -> i64 {
+        self.items.iter().sum()
+    }
+}
+
+/// Builds elves from lines fed one at a time, so the whole input never has to be in memory.
+/// Runs of blank lines separate elves, blank lines at the start or the end are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Tally {
+    elves: usize,
+    current: Option<Elf>,
+}
+
+impl Tally {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds one line, returns the elf a blank line completes.
+    /// A bad line is left out of the elf it belongs to.
+    pub fn push(&mut self, line: Line) -> Result<Option<Elf>, ParseError> {
+        if line.text.trim().is_empty() {
+            return Ok(self.current.take());
+        }
+        let elf = self.current.get_or_insert_with(|| {
+            self.elves += 1;
+            Elf { index: self.elves - 1, line: line.index, items: Vec::new() }
+        });
+        elf.items.push(line.parse(line.text)?);
+        Ok(None)
+    }
+
+    /// The last elf when the input does not end with a blank line.
+    pub fn finish(self) -> Option<Elf> {
+        self.current
+    }
+}
+
+/// All elves of the input in input order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Inventory {
+    pub elves: Vec<Elf>,
+}
+
+impl Inventory {
+    /// Fails at the first line that is not a number.
+    pub fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self, ParseError> {
+        let mut tally = Tally::new();
+        let mut elves = Vec::new();
+        for line in parse::lines(lines) {
+            elves.extend(tally.push(line)?);
+        }
+        elves.extend(tally.finish());
+        Ok(Self { elves })
+    }
+
+    /// Skips the lines that are not numbers and returns their errors.
+    pub fn parse_lenient<S: AsRef<str>>(lines: &[S]) -> (Self, Vec<ParseError>) {
+        let mut tally = Tally::new();
+        let mut elves = Vec::new();
+        let mut errors = Vec::new();
+        for line in parse::lines(lines) {
+            match tally.push(line) {
+                Ok(elf) => elves.extend(elf),
+                Err(error) => errors.push(error),
+            }
+        }
+        elves.extend(tally.finish());
+        (Self { elves }, errors)
+    }
+
+    /// Calories of every elf, in input order.
+    pub fn totals(&self) -> Vec<i64> {
+        self.elves.iter().map(Elf::total).collect()
+    }
+
+    /// Calories of all elves together.
+    pub fn total(&self) -> i64 {
+        self.elves.iter().map(Elf::total).sum()
+    }
+
+    /// The elf with the most calories, the first one on ties.
+    pub fn max(&self) -> Option<&Elf> {
+        self.top(1).pop()
+    }
+
+    /// The `k` elves with the most calories, largest first, ties in input order.
+    pub fn top(&self, k: usize) -> Vec<&Elf> {
+        let mut top = PriorityQueue::by_key(|elf: &&Elf| (elf.total(), Reverse(elf.index))).bounded(k);
+        top.extend(&self.elves);
+        let mut elves = top.into_sorted_vec();
+        elves.reverse();
+        elves
+    }
+
+    /// 1-based rank of the elf at `index`, elves with the same calories share a rank.
+    pub fn rank(&self, index: usize) -> Option<usize> {
+        let total = self.elves.get(index)?.total();
+        Some(self.elves.iter().filter(|elf| elf.total() > total).count() + 1)
+    }
+
+    /// One row per elf in input order, elves and lines are numbered from 1.
+    /// JSON has one object per line with all items, CSV separates the items with spaces.
+    pub fn report(&self, format: Format) -> String {
+        let mut sorted = self.totals();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        let ranks = self.elves.iter().map(|elf| sorted.partition_point(|&t| t > elf.total()) + 1).collect::<Vec<_>>();
+        let mut out = String::new();
+        match format {
+            Format::Text => {
+                writeln!(out, "{:>5}  {:>6}  {:>5}  {:>10}  {:>5}", "elf", "line", "items", "total", "rank").unwrap();
+                for (elf, rank) in self.elves.iter().zip(&ranks) {
+                    writeln!(out, "{:>5}  {:>6}  {:>5}  {:>10}  {:>5}", elf.index + 1, elf.line + 1, elf.items.len(), elf.total(), rank).unwrap();
+                }
+                writeln!(out, "{} elves, {} calories", self.elves.len(), self.total()).unwrap();
+            }
+            Format::Csv => {
+                writeln!(out, "elf,line,items,total,rank").unwrap();
+                for (elf, rank) in self.elves.iter().zip(&ranks) {
+                    let items = elf.items.iter().map(i64::to_string).collect::<Vec<_>>().join(" ");
+                    writeln!(out, "{},{},{},{},{}", elf.index + 1, elf.line + 1, items, elf.total(), rank).unwrap();
+                }
+            }
+            Format::Json => {
+                for (elf, rank) in self.elves.iter().zip(&ranks) {
+                    let items = elf.items.iter().map(i64::to_string).collect::<Vec<_>>().join(",");
+                    writeln!(out, "{{\"elf\":{},\"line\":{},\"items\":[{}],\"total\":{},\"rank\":{}}}",
+                        elf.index + 1, elf.line + 1, items, elf.total(), rank).unwrap();
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: [&str; 14] = ["1", "2", "", "3", "4", "", "5", "6", "", "7", "", "1", "", "9"];
+
+    #[test]
+    fn test_inventory() {
+        let inventory = Inventory::parse(&DATA).unwrap();
+        assert_eq!(vec![3, 7, 11, 7, 1, 9], inventory.totals());
+        assert_eq!(38, inventory.total());
+        assert_eq!(Some(&Elf { index: 2, line: 6, items: vec![5, 6] }), inventory.max());
+        assert_eq!(vec![2, 5, 1, 3], inventory.top(4).iter().map(|elf| elf.index).collect::<Vec<_>>());
+        assert_eq!(6, inventory.top(10).len());
+        assert_eq!(vec![Some(5), Some(3), Some(1), Some(3), Some(6), Some(2), None], (0..7).map(|i| inventory.rank(i)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let inventory = Inventory::parse(&["", "1", "", "", "2", "3", "", ""]).unwrap();
+        assert_eq!(vec![(0, 1), (1, 4)], inventory.elves.iter().map(|elf| (elf.index, elf.line)).collect::<Vec<_>>());
+        assert_eq!(Inventory::default(), Inventory::parse(&[""; 3]).unwrap());
+        assert_eq!(None, Inventory::default().max());
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        let lines = ["1", "x", "", "2", "3y"];
+        let error = Inventory::parse(&lines).unwrap_err();
+        assert_eq!((2, 1), (error.line, error.len));
+        let (inventory, errors) = Inventory::parse_lenient(&lines);
+        assert_eq!(vec![1, 2], inventory.totals());
+        assert_eq!(vec![2, 5], errors.iter().map(|error| error.line).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_report() {
+        let inventory = Inventory::parse(&["1", "2", "", "30"]).unwrap();
+        let table = concat!(
+            "  elf    line  items       total   rank\n",
+            "    1       1      2           3      2\n",
+            "    2       4      1          30      1\n",
+            "2 elves, 33 calories\n",
+        );
+        assert_eq!(table, inventory.report(Format::Text));
+        assert_eq!("elf,line,items,total,rank\n1,1,1 2,3,2\n2,4,30,30,1\n", inventory.report(Format::Csv));
+        assert_eq!(
+            "{\"elf\":1,\"line\":1,\"items\":[1,2],\"total\":3,\"rank\":2}\n{\"elf\":2,\"line\":4,\"items\":[30],\"total\":30,\"rank\":1}\n",
+            inventory.report(Format::Json)
+        );
+    }
+}
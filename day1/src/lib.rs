@@ -0,0 +1,72 @@
+//! Day 1: Calorie Counting. Elves list the calories of their food items,
+//! an empty line ends the inventory of one elf.
+
+mod inventory;
+
+use aoc_common::parse::ParseError;
+use aoc_common::{params, Solution};
+
+pub use inventory::{Elf, Inventory, Tally};
+
+/// Total calories of every elf, in input order.
+pub fn elf_totals<S: AsRef<str>>(lines: &[S]) -> Result<Vec<i64>, ParseError> {
+    Ok(Inventory::parse(lines)?.totals())
+}
+
+params! {
+    pub struct Top {
+        /// Elves with the most calories summed in part 2.
+        top: usize = 3,
+    }
+}
+
+/// The elf with the most calories and the sum of the top three.
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Inventory;
+    type Answer1 = i64;
+    type Answer2 = i64;
+    type Error = ParseError;
+    type Params = Top;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Inventory::parse(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(input.max().map_or(0, Elf::total))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Top::default())
+    }
+
+    fn part2_with(input: &Self::Input, params: &Top) -> Result<Self::Answer2, Self::Error> {
+        Ok(input.top(params.top).into_iter().map(Elf::total).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = ["1", "2", "", "3", "4", "", "5", "6", "", "7", "", "1", "", "9"]
+            .into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let totals = Day1::parse(&data).unwrap();
+        assert_eq!(Ok(11), Day1::part1(&totals));
+        assert_eq!(Ok(27), Day1::part2(&totals));
+        assert_eq!(Ok(38), Day1::part2_with(&totals, &Top { top: 10 }));
+        assert_eq!(Ok(vec![3, 7, 11, 7, 1, 9]), elf_totals(&data));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let data = ["1", "", "2x"].map(String::from);
+        let error = Day1::parse(&data).unwrap_err();
+        assert_eq!((3, 1, 2), (error.line, error.column, error.len));
+    }
+}
@@ -1,21 +1,33 @@
-use std::io::{self, BufRead};
+use std::env;
+use std::process::ExitCode;
 
-fn main() -> io::Result<()> {
-    let mut total = 0;
-    let mut current = 0;
-    let mut max_weight = 0;
-    let mut lines = io::stdin().lock().lines();
-    while let Some(line) = lines.next() {
-        let input = line?;
-        if input.is_empty() {
-            current = 0;
-        } else {
-            let x: i32 = input.parse().unwrap();
-            current += x;
-            total += x;
-            max_weight = max_weight.max(current);
+use aoc_common::cli::{self, Options};
+use aoc_common::Solver;
+use day1::{Day1, Inventory};
+
+/// Prints every elf in the requested format, bad lines are skipped and reported on stderr.
+fn elves(options: &Options) -> Result<ExitCode, cli::Error> {
+    let input = cli::read_source(&options.source, &Solver::<Day1>::new(), None)?;
+    let (inventory, errors) = Inventory::parse_lenient(&input.lines());
+    print!("{}", inventory.report(options.format));
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    Ok(if errors.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn main() -> ExitCode {
+    if env::args().nth(1).as_deref() != Some("elves") {
+        return cli::day_main::<Day1>();
+    }
+    match Options::parse(env::args().skip(2)).and_then(|options| elves(&options)) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("{}", error);
+            if let cli::Error::Usage(_) = error {
+                eprintln!("usage: day1 elves [--format text|json|csv] [--input <path> | <path> | - | --example]");
+            }
+            error.exit_code()
         }
     }
-    println!("max of one = {}\ntotal = {}", max_weight, total);
-    Ok(())
 }
@@ -0,0 +1,22 @@
+use aoc_common::Solution;
+use day1::Day1;
+
+/// Totals of all elves, largest first.
+fn reference(lines: &[String]) -> Vec<i64> {
+    let mut totals = lines.split(|line| line.is_empty())
+        .map(|elf| elf.iter().map(|line| line.parse::<i64>().unwrap()).sum())
+        .collect::<Vec<_>>();
+    totals.sort();
+    totals.reverse();
+    totals
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day1::input, |lines| Day1::solve_part1(lines).ok(), |lines| Some(reference(lines)[0]));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day1::input, |lines| Day1::solve_part2(lines).ok(), |lines| Some(reference(lines)[..3].iter().sum()));
+}
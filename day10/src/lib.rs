@@ -0,0 +1,43 @@
+//! Day 10: Cathode-Ray Tube. A CPU with one register runs `noop` and `addx` instructions.
+
+#[cfg(test)]
+#[macro_use]
+extern crate lazy_static;
+
+mod task;
+
+use aoc_common::{ParseError, Part, Session, Solution, Stepper};
+
+pub use task::{draw, parse_commands, signal_strength, Command, Cpu, Crt, Pixel};
+
+/// Signal strength (part 1) and the letters drawn on the CRT (part 2).
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<task::Command>;
+    type Answer1 = i32;
+    type Answer2 = String;
+    type Error = ParseError;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        task::parse_commands(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(task::signal_strength(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        let mut buf = Vec::new();
+        task::draw(input, &mut buf);
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// Both parts run the same CPU, one step per cycle.
+    fn simulation(input: &Self::Input, _part: Part, _params: &Self::Params) -> Result<Option<Box<dyn Session>>, Self::Error> {
+        Ok(Some(Box::new(Stepper::new(task::Cpu::new(input)))))
+    }
+}
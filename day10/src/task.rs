@@ -1,35 +1,35 @@
 
-use std::{ops::FnMut, str::FromStr, io::Write};
+use std::{ops::FnMut, io::Write};
+use aoc_common::Grid;
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::render::{self, palette, Cell, Render, Rgb};
+use aoc_common::Simulation;
 
-pub type Result<T> = std::result::Result<T, &'static str>;
-
-enum Command {
+/// One CPU instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
     Noop,
     Add(i32),
 }
 
-impl FromStr for Command {
-    type Err = &'static str;
-    fn from_str(s: &str) -> Result<Self> {
-        let cmd = s.split_ascii_whitespace().collect::<Vec<_>>();
-        match *cmd.get(0).ok_or("expected a command")? {
-            "noop" => {
-                Ok(Command::Noop)
-            },
-            "addx" => {
-                let x = cmd.get(1).ok_or("expected a number")?.parse::<i32>().map_err(|_| "cannot parse a number")?;
-                Ok(Command::Add(x))
-            },
-            _ => Err("invalid input")
-        }
+fn parse_command(line: Line) -> Result<Command, ParseError> {
+    match line.text.split_once(' ') {
+        None if line.text == "noop" => Ok(Command::Noop),
+        Some(("addx", x)) => Ok(Command::Add(line.parse(x)?)),
+        _ => Err(line.error(line.text, "expected \"noop\" or \"addx <number>\"")),
     }
 }
 
-fn process<F>(lines: &[String], mut exec: F) -> Result<()>
+/// Instructions, one per line.
+pub fn parse_commands<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Command>, ParseError> {
+    parse::lines(lines).map(parse_command).collect()
+}
+
+fn process<F>(commands: &[Command], mut exec: F)
 where F: FnMut(i32) {
     let mut x = 0;
-    for line in lines {
-        match line.parse::<Command>()? {
+    for command in commands {
+        match command {
             Command::Noop => {
                 exec(x);
             },
@@ -40,10 +40,10 @@ where F: FnMut(i32) {
             }
         }
     }
-    Ok(())
 }
 
-pub fn task1(lines: &[String]) -> Result<i32> {
+/// Sum of signal strengths during the 20th, 60th, ... 220th cycles.
+pub fn signal_strength(commands: &[Command]) -> i32 {
     let mut n = 0;
     let mut total = 0;
     let inc = |x| {
@@ -52,23 +52,134 @@ pub fn task1(lines: &[String]) -> Result<i32> {
             total += n * (x + 1);
         }
     };
-    process(lines, inc)?;
-    Ok(total)
+    process(commands, inc);
+    total
 }
 
-pub fn task2(lines: &[String], out_writer: &mut dyn Write) -> Result<()> {
-    let mut n = 0;
-    let draw = |x| {
-        let pixel = if n >= x && n <= x + 2 { '#' } else { '.' };
-        write!(out_writer, "{}", pixel).unwrap();
-        n += 1;
-        if n >= 40 {        // next line
-            writeln!(out_writer).unwrap();
-            n = 0;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pixel {
+    Dark,
+    Lit,
+}
+
+impl Cell for Pixel {
+    fn glyph(&self) -> char {
+        match self {
+            Pixel::Dark => '.',
+            Pixel::Lit => '#',
         }
-    };
-    process(lines, draw)?;
-    Ok(())
+    }
+
+    fn colour(&self) -> Rgb {
+        match self {
+            Pixel::Dark => palette::BLACK,
+            Pixel::Lit => palette::LIT,
+        }
+    }
+}
+
+/// The 40x6 screen, drawn one pixel per cycle. Cycles after the last pixel are not drawn.
+#[derive(Debug, Clone)]
+pub struct Crt {
+    screen: Grid<Pixel>,
+    cycle: usize,
+}
+
+impl Crt {
+    pub const WIDTH: usize = 40;
+    pub const HEIGHT: usize = 6;
+
+    pub fn new() -> Self {
+        Self { screen: Grid::new(Self::HEIGHT, Self::WIDTH, Pixel::Dark), cycle: 0 }
+    }
+
+    /// Draws the next pixel, `x` is the left end of the 3 pixel wide sprite.
+    pub fn tick(&mut self, x: i32) {
+        let (row, col) = (self.cycle / Self::WIDTH, self.cycle % Self::WIDTH);
+        if let Some(pixel) = self.screen.get_mut((row, col)) {
+            let col = col as i32;
+            *pixel = if col >= x && col <= x + 2 { Pixel::Lit } else { Pixel::Dark };
+        }
+        self.cycle += 1;
+    }
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for Crt {
+    type Cell = Pixel;
+
+    fn render(&self) -> Grid<Pixel> {
+        self.screen.clone()
+    }
+}
+
+/// Draws the CRT image, one line of 40 pixels per row.
+pub fn draw(commands: &[Command], out_writer: &mut dyn Write) {
+    let mut crt = Crt::new();
+    process(commands, |x| crt.tick(x));
+    write!(out_writer, "{}", render::ascii(&crt.render())).unwrap();
+}
+
+/// The CPU driving the CRT, one step per cycle.
+#[derive(Debug, Clone)]
+pub struct Cpu {
+    commands: Vec<Command>,
+    /// Next command, and whether its first cycle of `addx` is done.
+    next: usize,
+    busy: bool,
+    x: i32,
+    cycle: usize,
+    strength: i32,
+    crt: Crt,
+}
+
+impl Cpu {
+    pub fn new(commands: &[Command]) -> Self {
+        Self { commands: commands.to_vec(), next: 0, busy: false, x: 1, cycle: 0, strength: 0, crt: Crt::new() }
+    }
+
+    /// Sum of signal strengths of the cycles run so far.
+    pub fn strength(&self) -> i32 {
+        self.strength
+    }
+}
+
+impl Simulation for Cpu {
+    fn step(&mut self) -> bool {
+        let Some(&command) = self.commands.get(self.next) else { return false };
+        self.cycle += 1;
+        if (self.cycle + 20).is_multiple_of(40) {
+            self.strength += self.cycle as i32 * self.x;
+        }
+        self.crt.tick(self.x - 1);
+        match command {
+            Command::Add(y) if self.busy => {
+                self.x += y;
+                self.next += 1;
+                self.busy = false;
+            },
+            Command::Add(_) => self.busy = true,
+            Command::Noop => self.next += 1,
+        }
+        true
+    }
+
+    fn status(&self) -> Vec<(&'static str, i64)> {
+        vec![("cycle", self.cycle as i64), ("x", self.x as i64), ("strength", self.strength as i64)]
+    }
+}
+
+impl Render for Cpu {
+    type Cell = Pixel;
+
+    fn render(&self) -> Grid<Pixel> {
+        self.crt.render()
+    }
 }
 
 #[cfg(test)]
@@ -226,13 +337,20 @@ mod tests {
     }
     #[test]
     fn test1() {
-        assert_eq!(Ok(13140), task1(&DATA));
+        assert_eq!(13140, signal_strength(&parse_commands(&DATA).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_commands(&["noop".to_string(), "addx 1O".to_string()]).unwrap_err();
+        assert_eq!((2, 6, 2), (error.line, error.column, error.len));
+        assert_eq!(1, parse_commands(&["jmp 3".to_string()]).unwrap_err().line);
     }
 
     #[test]
     fn test2() {
         let mut buf = Vec::new();
-        task2(&DATA.clone(), &mut buf).unwrap();
+        draw(&parse_commands(&DATA).unwrap(), &mut buf);
         assert_eq!(
             String::from_utf8(buf),
             Ok(
@@ -245,4 +363,27 @@ mod tests {
             .to_owned())
         );
     }
+
+    #[test]
+    fn test_crt() {
+        let mut crt = Crt::new();
+        for x in [0, 0, 5, 5] {
+            crt.tick(x);
+        }
+        let screen = crt.render();
+        assert_eq!(&[Pixel::Lit, Pixel::Lit, Pixel::Dark, Pixel::Dark, Pixel::Dark], &screen.row(0)[..5]);
+        assert_eq!((6, 40), (screen.height(), screen.width()));
+    }
+
+    #[test]
+    fn test_cpu() {
+        let commands = parse_commands(&DATA).unwrap();
+        let mut cpu = Cpu::new(&commands);
+        while cpu.step() {}
+        assert_eq!(13140, cpu.strength());
+        assert_eq!(vec![("cycle", 240), ("x", 17), ("strength", 13140)], cpu.status());
+        let mut buf = Vec::new();
+        draw(&commands, &mut buf);
+        assert_eq!(String::from_utf8(buf).unwrap(), render::ascii(&cpu.render()));
+    }
 }
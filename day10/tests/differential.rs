@@ -0,0 +1,38 @@
+use aoc_common::Solution;
+use day10::Day10;
+
+/// Value of the X register during every cycle, `x[0]` is unused.
+fn register(lines: &[String]) -> Vec<i32> {
+    let mut x = vec![1, 1];
+    for line in lines {
+        let last = *x.last().unwrap();
+        match line.strip_prefix("addx ") {
+            Some(value) => x.extend([last, last + value.parse::<i32>().unwrap()]),
+            None => x.push(last),
+        }
+    }
+    x
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day10::input, |lines| Day10::solve_part1(lines).ok(), |lines| {
+        let x = register(lines);
+        Some([20, 60, 100, 140, 180, 220].iter().map(|&cycle| cycle as i32 * x[cycle]).sum())
+    });
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day10::input, |lines| Day10::solve_part2(lines).ok(), |lines| {
+        let x = register(lines);
+        let mut screen = String::new();
+        for pixel in 0..240 {
+            screen.push(if (x[pixel + 1] - (pixel % 40) as i32).abs() <= 1 { '#' } else { '.' });
+            if pixel % 40 == 39 {
+                screen.push('\n');
+            }
+        }
+        Some(screen)
+    });
+}
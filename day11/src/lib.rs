@@ -0,0 +1,64 @@
+//! Day 11: Monkey in the Middle.
+
+mod task;
+
+use aoc_common::{params, Input, ParseError, Solution};
+
+pub use task::{monkey_business, parse_monkeys, Monkey};
+
+params! {
+    pub struct Rounds {
+        /// Rounds in part 1.
+        rounds1: u32 = 20,
+        /// Rounds in part 2.
+        rounds2: u32 = 10000,
+        /// Worry levels are divided by it in part 1, values below 1 mean no relief.
+        relief: i32 = 3,
+    }
+}
+
+/// Monkey business after 20 rounds with relief (part 1) and 10000 rounds without it (part 2).
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<task::Monkey>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+    type Error = ParseError;
+    type Params = Rounds;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        task::parse_monkeys(&Input::from_lines(lines))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Rounds::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Rounds::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Rounds) -> Result<Self::Answer1, Self::Error> {
+        Ok(task::monkey_business(input, params.rounds1, params.relief.max(1)))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Rounds) -> Result<Self::Answer2, Self::Error> {
+        Ok(task::monkey_business(input, params.rounds2, 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let input = Day11::parse(&Day11::example()).unwrap();
+        let params = Rounds { rounds1: 10000, rounds2: 20, relief: 1 };
+        assert_eq!(Ok(2713310158), Day11::part1_with(&input, &params));
+        assert_eq!(Ok(99 * 103), Day11::part2_with(&input, &params));
+    }
+}
@@ -1,6 +1,7 @@
-use std::str::FromStr;
-
-pub type Result<T> = std::result::Result<T, &'static str>;
+use aoc_common::Input;
+use aoc_common::parse::{Line, ParseError};
+use aoc_common::scan;
+use aoc_common::scan::Field;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Variable {
@@ -8,14 +9,12 @@ enum Variable {
     Old
 }
 
-impl FromStr for Variable {
-    type Err = &'static str;
-    fn from_str(s: &str) -> Result<Self> {
-        if s == "old" {
+impl<'a> Field<'a> for Variable {
+    fn from_text(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        if text == "old" {
             Ok(Variable::Old)
         } else {
-            let c = s.parse().map_err(|_| "cannot parse a constant")?;
-            Ok(Variable::Const(c))
+            Ok(Variable::Const(line.parse(text)?))
         }
     }
 }
@@ -29,22 +28,18 @@ impl Variable {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operation {
     Add(Variable, Variable),
     Mult(Variable, Variable),
 }
 
-impl FromStr for Operation {
-    type Err = &'static str;
-    fn from_str(s: &str) -> Result<Self> {
-        let parts = s.split_ascii_whitespace().collect::<Vec<_>>();
-        let a = parts[0].parse()?;
-        let b = parts[2].parse()?;
-        match parts[1] {
-            "+" => Ok(Operation::Add(a, b)),
-            "*" => Ok(Operation::Mult(a, b)),
-            _ => Err("unknown operation")
+impl Operation {
+    fn parse<'a>(line: &Line<'a>, s: &'a str) -> Result<Self, ParseError> {
+        let (a, op, b) = scan!("Operation: new = {} {+|*} {}", line, s)?;
+        match op {
+            '+' => Ok(Operation::Add(a, b)),
+            _ => Ok(Operation::Mult(a, b)),
         }
     }
 }
@@ -59,7 +54,7 @@ impl Operation {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Test {
     t: i64,
 }
@@ -70,8 +65,9 @@ impl Test {
     }
 }
 
-#[derive(Debug)]
-struct Monkey {
+/// A monkey with the items it holds and its throwing rules.
+#[derive(Debug, Clone)]
+pub struct Monkey {
     items: Vec<i64>,
     op: Operation,
     test: Test,
@@ -80,20 +76,54 @@ struct Monkey {
     count: i64
 }
 
+/// The `index`-th line of a monkey without its indentation, `expected` names it when the notes end early.
+fn note<'a>(lines: &[Line<'a>], index: usize, expected: &str) -> Result<(Line<'a>, &'a str), ParseError> {
+    let line = *lines.get(index).ok_or_else(|| {
+        let last = lines.last().map_or(0, |l| l.index + 1);
+        ParseError::eof(last, format!("expected {:?}", expected))
+    })?;
+    Ok((line, line.text.trim_start()))
+}
+
 impl Monkey {
-    fn from<S: AsRef<str>>(lines: &[S]) -> Result<Self> {
-        let items = lines[1].as_ref().split(": ").last().unwrap()
-            .split(", ").map(|x| x.parse::<i64>().unwrap()).collect::<Vec<_>>();
-        let op = lines[2].as_ref().split(" = ").last().unwrap().parse()?;
-        let test = Test { t: lines[3].as_ref().rsplit(' ').next().unwrap().parse().unwrap() } ;
-        let if_true = lines[4].as_ref().rsplit(' ').next().unwrap().parse().unwrap();
-        let if_false = lines[5].as_ref().rsplit(' ').next().unwrap().parse().unwrap();
+    fn from(lines: &[Line]) -> Result<Self, ParseError> {
+        let (line, text) = note(lines, 0, "Monkey ")?;
+        let _: &str = scan!("Monkey {}:", &line, text)?;
+        let (line, text) = note(lines, 1, "Starting items: ")?;
+        let items = scan!("Starting items: {*, }", &line, text)?;
+        let (line, text) = note(lines, 2, "Operation: new = ")?;
+        let op = Operation::parse(&line, text)?;
+        let (line, text) = note(lines, 3, "Test: divisible by ")?;
+        let t = scan!("Test: divisible by {}", &line, text)?;
+        let test = Test { t: line.parse(t)? };
+        if test.t == 0 {
+            return Err(line.error(t, "cannot divide by zero"));
+        }
+        let (line, text) = note(lines, 4, "If true: throw to monkey ")?;
+        let if_true = scan!("If true: throw to monkey {}", &line, text)?;
+        let (line, text) = note(lines, 5, "If false: throw to monkey ")?;
+        let if_false = scan!("If false: throw to monkey {}", &line, text)?;
+        if let Some(extra) = lines.get(6) {
+            return Err(extra.error(extra.text, "expected an empty line after the monkey"));
+        }
         Ok(Self{items, op, test, if_true, if_false, count: 0})
     }
 }
 
-fn parse_monkeys<S: AsRef<str>>(lines: &[S]) -> Vec<Monkey> {
-    lines.chunks(7).map(|l| Monkey::from(l).unwrap()).collect()
+/// Monkey notes separated by empty lines.
+pub fn parse_monkeys(input: &Input) -> Result<Vec<Monkey>, ParseError> {
+    let blocks = input.blocks();
+    let monkeys = blocks.iter().map(|block| Monkey::from(block)).collect::<Result<Vec<_>, _>>()?;
+    for (monkey, block) in monkeys.iter().zip(&blocks) {
+        for (target, index) in [(monkey.if_true, 4), (monkey.if_false, 5)] {
+            if target >= monkeys.len() {
+                let line = block[index];
+                let (_, number): (&str, &str) = scan!("{}monkey {}", &line)?;
+                return Err(line.error(number, format!("there are only {} monkeys", monkeys.len())));
+            }
+        }
+    }
+    Ok(monkeys)
 }
 
 fn round(monkeys: &mut [Monkey], divisor: i64, common_divisor: i64) {
@@ -102,7 +132,9 @@ fn round(monkeys: &mut [Monkey], divisor: i64, common_divisor: i64) {
         let mut true_items = Vec::new();
         let mut false_items = Vec::new();
         for item in &monk.items {
-            let new_item = monk.op.apply(*item) / divisor % common_divisor;
+            // levels modulo the product of the tests keep their divisibility, but only without
+            // relief: dividing a reduced level does not give the reduced divided level
+            let new_item = if divisor == 1 { monk.op.apply(*item) % common_divisor } else { monk.op.apply(*item) / divisor };
             if monk.test.check(new_item) {
                 true_items.push(new_item);
             } else {
@@ -118,20 +150,22 @@ fn round(monkeys: &mut [Monkey], divisor: i64, common_divisor: i64) {
     }
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S], n: u32, divisor: i32) -> Result<i64> {
-    let mut monkeys = parse_monkeys(lines);
+/// Monkey business after `n` rounds: the product of the two largest inspection counts.
+/// Worry levels are divided by `divisor` after every inspection.
+pub fn monkey_business(monkeys: &[Monkey], n: u32, divisor: i32) -> i64 {
+    let mut monkeys = monkeys.to_vec();
     let commont_divisor = monkeys.iter().map(|m| m.test.t).product();
     for _ in 0..n {
         round(&mut monkeys, divisor as i64,commont_divisor);
     }
     monkeys.sort_by_key(|m| - m.count);
-    Ok(monkeys.iter().take(2).map(|m| m.count as i64).product())
+    monkeys.iter().take(2).map(|m| m.count).product()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const DATA: &'static str = "Monkey 0:
+    const DATA: &str = "Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
   Test: divisible by 23
@@ -162,12 +196,51 @@ Monkey 3:
     #[test]
     fn test1() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(10605), task1(&lines, 20, 3));
+        assert_eq!(10605, monkey_business(&parse_monkeys(&Input::from_lines(&lines)).unwrap(), 20, 3));
     }
 
     #[test]
     fn test2() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(2713310158), task1(&lines, 10000, 1));
+        assert_eq!(2713310158, monkey_business(&parse_monkeys(&Input::from_lines(&lines)).unwrap(), 10000, 1));
+    }
+
+    #[test]
+    fn test_relief_without_modulo() {
+        // after 20 rounds some levels exceed the product of the tests
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        assert_eq!(24633, monkey_business(&parse_monkeys(&Input::from_lines(&lines)).unwrap(), 30, 3));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut lines = DATA.split('\n').collect::<Vec<_>>();
+        lines[16] = "  Operation: new = old ^ old";
+        let error = parse_monkeys(&Input::from_lines(&lines)).unwrap_err();
+        assert_eq!((17, 24, "expected \"+\" or \"*\""), (error.line, error.column, error.message.as_str()));
+        lines[16] = "  Operation: new = old * old";
+        lines[12] = "    If false: throw to monkey 4";
+        let error = parse_monkeys(&Input::from_lines(&lines)).unwrap_err();
+        assert_eq!((13, 31, "there are only 4 monkeys"), (error.line, error.column, error.message.as_str()));
+        let error = parse_monkeys(&Input::from_lines(&lines[..4])).unwrap_err();
+        assert_eq!("line 5: expected \"If true: throw to monkey \"", error.to_string());
+        lines[6] = "  Test: divisible by 19";
+        let error = parse_monkeys(&Input::from_lines(&lines)).unwrap_err();
+        assert_eq!((7, "expected an empty line after the monkey"), (error.line, error.message.as_str()));
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let expected = monkey_business(&parse_monkeys(&Input::from_lines(&lines)).unwrap(), 20, 3);
+        // without the separator after the last monkey, with trailing and doubled blank lines
+        let mut variant = lines.iter().copied().filter(|l| !l.is_empty()).collect::<Vec<_>>();
+        for at in [6, 13, 20] {
+            variant.insert(at, "");
+        }
+        assert_eq!(expected, monkey_business(&parse_monkeys(&Input::from_lines(&variant)).unwrap(), 20, 3));
+        variant.insert(7, "");
+        variant.extend(["", ""]);
+        assert_eq!(expected, monkey_business(&parse_monkeys(&Input::from_lines(&variant)).unwrap(), 20, 3));
     }
 }
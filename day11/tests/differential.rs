@@ -0,0 +1,55 @@
+use aoc_common::Solution;
+use day11::Day11;
+
+struct Monkey {
+    items: Vec<u128>,
+    operation: Vec<String>,
+    test: u128,
+    targets: (usize, usize),
+}
+
+fn last_number(line: &str) -> u128 {
+    line.rsplit(' ').next().unwrap().parse().unwrap()
+}
+
+fn monkeys(lines: &[String]) -> Vec<Monkey> {
+    lines.split(|line| line.is_empty()).map(|notes| Monkey {
+        items: notes[1].split_once(": ").unwrap().1.split(", ").map(|item| item.parse().unwrap()).collect(),
+        operation: notes[2].split_once("= ").unwrap().1.split(' ').map(String::from).collect(),
+        test: last_number(&notes[3]),
+        targets: (last_number(&notes[4]) as usize, last_number(&notes[5]) as usize),
+    }).collect()
+}
+
+/// Exact worry levels with `relief` 3, levels modulo the product of the tests without relief.
+fn monkey_business(lines: &[String], rounds: usize, relief: bool) -> i64 {
+    let mut monkeys = monkeys(lines);
+    let modulus = monkeys.iter().map(|monkey| monkey.test).product::<u128>();
+    let mut inspected = vec![0; monkeys.len()];
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            for old in std::mem::take(&mut monkeys[i].items) {
+                inspected[i] += 1;
+                let value = |operand: &str| if operand == "old" { old } else { operand.parse().unwrap() };
+                let operation = &monkeys[i].operation;
+                let (a, b) = (value(&operation[0]), value(&operation[2]));
+                let new = if operation[1] == "*" { a * b } else { a + b };
+                let new = if relief { new / 3 } else { new % modulus };
+                let target = if new % monkeys[i].test == 0 { monkeys[i].targets.0 } else { monkeys[i].targets.1 };
+                monkeys[target].items.push(new);
+            }
+        }
+    }
+    inspected.sort();
+    inspected.iter().rev().take(2).product()
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day11::input, |lines| Day11::solve_part1(lines).ok(), |lines| Some(monkey_business(lines, 20, true)));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(200, aoc_gen::day11::input, |lines| Day11::solve_part2(lines).ok(), |lines| Some(monkey_business(lines, 10000, false)));
+}
@@ -0,0 +1,32 @@
+//! Day 12: Hill Climbing Algorithm. A path may climb at most one elevation level per step.
+
+mod task;
+
+use aoc_common::{Grid, Solution};
+
+pub use task::{find_path, find_target, parse_input, shortest_from_lowest, shortest_from_start, Error};
+
+/// Shortest path from the start (part 1) and from any lowest square (part 2).
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Grid<u8>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = task::Error;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        task::parse_input(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        task::shortest_from_start(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        task::shortest_from_lowest(input)
+    }
+}
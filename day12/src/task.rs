@@ -1,4 +1,6 @@
-use std::collections::VecDeque;
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::ParseError;
+use aoc_common::search::{self, Path};
 
 #[derive(Debug, Clone, thiserror::Error, PartialEq)]
 pub enum Error {
@@ -6,57 +8,46 @@ pub enum Error {
     StartNotFound,
     #[error("Target not found")]
     TargetNotFound,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
-fn to_grid<S: AsRef<str>>(lines: &[S]) -> Vec<Vec<u8>> {
-    lines.iter().map(|l| l.as_ref().bytes().collect()).collect()
-}
-
-fn start_position(grid: &Vec<Vec<u8>>) -> Option<(usize, usize)> {
-    for (i, row) in grid.iter().enumerate() {
-        if let Some(j) = row.iter().position(|x| *x == b'E') {
-            return Some((i, j));
-        }
-    }
-    None
+/// Heightmap with `S` for the start and `E` for the best signal.
+pub fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<Grid<u8>, Error> {
+    Ok(Grid::parse(lines, |b| (b.is_ascii_lowercase() || b == b'S' || b == b'E').then_some(b))?)
 }
 
+/// The search walks down from `E`, so `next_val` may be at most one below `prev_val`.
+/// `E` has the height of `z` and `S` the height of `a`.
 fn can_visit(next_val: u8, prev_val: u8) -> bool {
     (next_val.is_ascii_lowercase() && prev_val.is_ascii_lowercase() && next_val >= prev_val - 1) ||
-    (prev_val == b'E' && next_val == b'z') ||
+    (prev_val == b'E' && (next_val == b'y' || next_val == b'z')) ||
     (next_val == b'S' && (prev_val == b'a' || prev_val == b'b'))
 }
 
-pub fn find_target<F: Fn(u8)->bool>(grid: Vec<Vec<u8>>, is_finish: F) -> Result<u32, Error> {
-    const DIRECTIONS: &[(isize, isize)] = &[(-1, 0), (0, -1), (1, 0), (0, 1)];
-    let n = grid.len();
-    let m = grid[0].len();
-    let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
-    let (start_row, start_col) = start_position(&grid).ok_or(Error::StartNotFound)?;
-    let mut queue = VecDeque::new();
-    queue.push_back((start_row, start_col, 0));
-    visited[start_row][start_col] = true;
-    while let Some((row, col, length)) = queue.pop_front() {
-        if is_finish(grid[row][col]) {
-            return Ok(length);
-        }
-        for (i, j) in DIRECTIONS {
-            let (next_row, next_col) = ((row as isize + i) as usize, (col as isize + j) as usize);
-            if next_row < n && next_col < m && !visited[next_row][next_col] && can_visit(grid[next_row][next_col], grid[row][col]) {
-                visited[next_row][next_col] = true;
-                queue.push_back((next_row, next_col, length + 1));
-            }
-        }
-    }
-    Err(Error::TargetNotFound)
+/// Shortest path from `E` down to the first cell accepted by `is_finish`.
+pub fn find_path<F: Fn(u8)->bool>(grid: &Grid<u8>, is_finish: F) -> Result<Path<Pos, usize>, Error> {
+    let start = grid.position(|&x| x == b'E').ok_or(Error::StartNotFound)?;
+    search::bfs(
+        [start],
+        |&pos| grid.neighbours4(pos).filter(move |&next| can_visit(grid[next], grid[pos])),
+        |&pos| is_finish(grid[pos]),
+    ).ok_or(Error::TargetNotFound)
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    find_target(to_grid(lines), |x| x == b'S')
+/// Length of the path found by `find_path`.
+pub fn find_target<F: Fn(u8)->bool>(grid: &Grid<u8>, is_finish: F) -> Result<u32, Error> {
+    find_path(grid, is_finish).map(|path| path.cost as u32)
 }
 
-pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    find_target(to_grid(lines), |x| x == b'S' || x == b'a')
+/// Fewest steps from `S` to `E`.
+pub fn shortest_from_start(grid: &Grid<u8>) -> Result<u32, Error> {
+    find_target(grid, |x| x == b'S')
+}
+
+/// Fewest steps from any square at elevation `a` to `E`.
+pub fn shortest_from_lowest(grid: &Grid<u8>) -> Result<u32, Error> {
+    find_target(grid, |x| x == b'S' || x == b'a')
 }
 
 #[cfg(test)]
@@ -71,12 +62,33 @@ abdefghi";
     #[test]
     fn test1() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(31), task1(&lines));
+        assert_eq!(Ok(31), shortest_from_start(&parse_input(&lines).unwrap()));
+    }
+
+    #[test]
+    fn test_path() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let grid = parse_input(&lines).unwrap();
+        let path = find_path(&grid, |x| x == b'S').unwrap();
+        assert_eq!(31, path.cost);
+        assert_eq!(Some(&(2, 5)), path.states.first());
+        assert_eq!(Some(&(0, 0)), path.states.last());
+        assert!(path.states.windows(2).all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
     }
 
     #[test]
     fn test2() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(29), task2(&lines));
+        assert_eq!(Ok(29), shortest_from_lowest(&parse_input(&lines).unwrap()));
+    }
+
+    #[test]
+    fn test_end_from_y() {
+        // `E` is reachable from `y` as well as from `z`
+        let grid = parse_input(&["SbcdefghijklmnopqrstuvwxyE"]).unwrap();
+        assert_eq!(Ok(25), shortest_from_start(&grid));
+        let grid = parse_input(&["Sbcdefghijklmnopqrstuvwxy", "aaaaaaaaaaaaaaaaaaaaaaaaE"]).unwrap();
+        assert_eq!(Ok(25), shortest_from_start(&grid));
+        assert_eq!(Ok(25), shortest_from_lowest(&grid));
     }
 }
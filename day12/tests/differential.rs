@@ -0,0 +1,44 @@
+use aoc_common::Solution;
+use day12::Day12;
+
+/// Relaxes every step between neighbours until no distance changes.
+fn reference(lines: &[String], is_start: impl Fn(u8) -> bool) -> Option<u32> {
+    let grid = lines.iter().map(|line| line.as_bytes()).collect::<Vec<_>>();
+    let (n, m) = (grid.len(), grid[0].len());
+    let height = |b: u8| match b {
+        b'S' => b'a',
+        b'E' => b'z',
+        b => b,
+    };
+    let mut distance = vec![vec![u32::MAX; m]; n];
+    for (i, j) in (0..n).flat_map(|i| (0..m).map(move |j| (i, j))) {
+        if is_start(grid[i][j]) {
+            distance[i][j] = 0;
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (i, j) in (0..n).flat_map(|i| (0..m).map(move |j| (i, j))) {
+            let neighbours = [(i.wrapping_sub(1), j), (i + 1, j), (i, j.wrapping_sub(1)), (i, j + 1)];
+            for (r, c) in neighbours.into_iter().filter(|&(r, c)| r < n && c < m) {
+                if distance[i][j] != u32::MAX && height(grid[r][c]) <= height(grid[i][j]) + 1 && distance[i][j] + 1 < distance[r][c] {
+                    distance[r][c] = distance[i][j] + 1;
+                    changed = true;
+                }
+            }
+        }
+    }
+    let end = (0..n).flat_map(|i| (0..m).map(move |j| (i, j))).find(|&(i, j)| grid[i][j] == b'E').unwrap();
+    Some(distance[end.0][end.1]).filter(|&d| d != u32::MAX)
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(1000, aoc_gen::day12::input, |lines| Day12::solve_part1(lines).ok(), |lines| reference(lines, |b| b == b'S'));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(1000, aoc_gen::day12::input, |lines| Day12::solve_part2(lines).ok(), |lines| reference(lines, |b| b == b'S' || b == b'a'));
+}
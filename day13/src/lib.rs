@@ -0,0 +1,32 @@
+//! Day 13: Distress Signal. Packets are nested lists of integers.
+
+mod task;
+
+use aoc_common::{Input, Solution};
+
+pub use task::{decoder_key, ordered_pairs, parse_pairs, Error, List, ListNode};
+
+/// Pairs in the right order (part 1) and the decoder key (part 2).
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<(task::List, task::List)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = task::Error;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_pairs(&Input::from_lines(lines))?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        task::ordered_pairs(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        task::decoder_key(input)
+    }
+}
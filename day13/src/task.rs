@@ -1,33 +1,28 @@
 use std::ops::Index;
 use std::slice::SliceIndex;
-use std::str::{self, FromStr};
 use std::cmp::{PartialOrd, Ord, Ordering, PartialEq, Eq};
+use aoc_common::Input;
+use aoc_common::parse::{Line, ParseError};
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq)]
 pub enum Error {
     #[error("Internal Error")]
     #[allow(unused)]
     Internal,
-    #[error("Wrong list format")]
-    WrongListFormat,
-    #[error("Parse error")]
-    ParseError,
-}
-
-impl From<std::num::ParseIntError> for Error {
-    fn from(_: std::num::ParseIntError) -> Self {
-        Error::ParseError
-    }
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
+/// Element of a packet: an integer or a nested list.
 #[derive(Debug, Clone)]
-enum ListNode {
+pub enum ListNode {
     Val(i32),
     List(List),
 }
 
+/// A packet. `Ord` implements the packet order of the puzzle.
 #[derive(Debug, Clone, Default)]
-struct List {
+pub struct List {
     data: Vec<ListNode>,
 }
 
@@ -69,7 +64,7 @@ impl Ord for List {
                 ListNode::List(sublist1) => {
                     match &other[i] {
                         ListNode::List(sublist2) => {
-                            sublist1.cmp(&sublist2)
+                            sublist1.cmp(sublist2)
                         },
                         ListNode::Val(x2) => {
                             let sublist2 = list![ListNode::Val(*x2)];
@@ -83,7 +78,7 @@ impl Ord for List {
                         ListNode::Val(x2) if x1 > x2 => Ordering::Greater,
                         ListNode::List(sublist2) => {
                             let sublist1 = list![ListNode::Val(*x1)];
-                            sublist1.cmp(&sublist2)
+                            sublist1.cmp(sublist2)
                         },
                         _ => Ordering::Equal
                     }
@@ -117,54 +112,76 @@ where Idx: SliceIndex<[ListNode], Output = ListNode> {
     }
 }
 
-impl FromStr for List {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.as_bytes();
-        let mut i = 0;
-        let mut list = List::new();
-        let mut stack = Vec::new();
-        while i < s.len() - 1 {
-            match s[i] {
-                b'0'..=b'9' => {
-                    let l = s.iter().skip(i).position(|x| !x.is_ascii_digit()).ok_or(Error::WrongListFormat)?;
-                    let node = ListNode::Val(str::from_utf8(&s[i..i+l]).unwrap().parse()?);
-                    list.push(node);
-                    i += l;
-                },
-                b'[' => {
-                    stack.push(list);
-                    list = List::new();
-                    i += 1;
-                },
-                b']' => {
-                    if let Some(mut last) = stack.pop() {
+fn parse_list(line: Line) -> Result<List, ParseError> {
+    let s = line.text;
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&b'[') {
+        return Err(line.error(&s[..s.chars().next().map_or(0, char::len_utf8)], "expected '['"));
+    }
+    let mut i = 1;
+    let mut list = List::new();
+    let mut stack = Vec::new();
+    loop {
+        match bytes.get(i) {
+            None => return Err(ParseError::new(line.index, s, i..i, "expected ']'")),
+            Some(b'0'..=b'9') => {
+                let l = bytes[i..].iter().position(|x| !x.is_ascii_digit()).unwrap_or(bytes.len() - i);
+                list.push(ListNode::Val(line.parse(&s[i..i+l])?));
+                i += l;
+            },
+            Some(b'[') => {
+                stack.push(list);
+                list = List::new();
+                i += 1;
+            },
+            Some(b']') => {
+                i += 1;
+                match stack.pop() {
+                    Some(mut last) => {
                         last.push(ListNode::List(list));
                         list = last;
-                    }
-                    i += 1;
-                },
-                b',' => {
-                    i += 1;
+                    },
+                    None => break,
                 }
-                _ => return Err(Error::WrongListFormat)
-            };
-        }
-        return Ok(list);
+            },
+            Some(b',') => {
+                i += 1;
+            },
+            Some(_) => {
+                let c = s[i..].chars().next().unwrap_or_default();
+                return Err(line.error(&s[i..i + c.len_utf8()], format!("unexpected {:?}", c)));
+            }
+        };
+    }
+    if i < s.len() {
+        return Err(line.error(&s[i..], "unexpected text after the list"));
     }
+    Ok(list)
+}
+
+/// Pairs of packets separated by empty lines.
+pub fn parse_pairs(input: &Input) -> Result<Vec<(List, List)>, ParseError> {
+    input.blocks().into_iter().map(|block| {
+        if let Some(extra) = block.get(2) {
+            return Err(extra.error(extra.text, "expected an empty line between pairs"));
+        }
+        let second = *block.get(1).ok_or_else(|| ParseError::eof(block[0].index + 1, "expected the second list of the pair"))?;
+        Ok((parse_list(block[0])?, parse_list(second)?))
+    }).collect()
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
+/// Sum of 1-based indices of pairs in the right order.
+pub fn ordered_pairs(pairs: &[(List, List)]) -> Result<u32, Error> {
     Ok(
-        lines.chunks(3).enumerate()
-        .map(|(i, chunk)| (i, chunk[0].as_ref().parse::<List>().unwrap(), chunk[1].as_ref().parse::<List>().unwrap()))
-        .filter(|(_, list1, list2)| list1 < list2)
-        .map(|(i, _, _)| i as u32 + 1).sum()
+        pairs.iter().enumerate()
+        .filter(|(_, (list1, list2))| list1 < list2)
+        .map(|(i, _)| i as u32 + 1).sum()
     )
 }
 
-pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    let mut lists = lines.iter().map(|l| l.as_ref()).filter(|l| !l.is_empty()).map(|l| l.parse::<List>().unwrap()).collect::<Vec<_>>();
+/// Decoder key: product of positions of the divider packets `[[2]]` and `[[6]]` after sorting.
+pub fn decoder_key(pairs: &[(List, List)]) -> Result<u32, Error> {
+    let mut lists = pairs.iter().flat_map(|(list1, list2)| [list1.clone(), list2.clone()]).collect::<Vec<_>>();
     let marker1 = list![ListNode::List(list![ListNode::Val(2)])];
     let marker2 = list![ListNode::List(list![ListNode::Val(6)])];
     lists.push(marker1.clone());
@@ -178,7 +195,7 @@ pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    const DATA: &'static str = "[1,1,3,1,1]
+    const DATA: &str = "[1,1,3,1,1]
     [1,1,5,1,1]
 
     [[1],[2,3,4]]
@@ -205,12 +222,31 @@ mod tests {
     #[test]
     fn test1() {
         let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok(13), task1(&lines));
+        assert_eq!(Ok(13), ordered_pairs(&parse_pairs(&Input::from_lines(&lines)).unwrap()));
     }
 
     #[test]
     fn test2() {
         let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok(140), task2(&lines));
+        assert_eq!(Ok(140), decoder_key(&parse_pairs(&Input::from_lines(&lines)).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_pairs(&Input::from_lines(&["[1,[2]]", "[1,[x]]"])).unwrap_err();
+        assert_eq!((2, 5, "unexpected 'x'"), (error.line, error.column, error.message.as_str()));
+        let error = parse_pairs(&Input::from_lines(&["[1,[2]", "[]"])).unwrap_err();
+        assert_eq!((1, 7, "expected ']'"), (error.line, error.column, error.message.as_str()));
+        assert_eq!("unexpected text after the list", parse_pairs(&Input::from_lines(&["[]]", "[]"])).unwrap_err().message);
+        assert_eq!("line 2: expected the second list of the pair", parse_pairs(&Input::from_lines(&["[]"])).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let expected = vec![(List::new(), list![ListNode::Val(1)]), (list![ListNode::Val(2)], List::new())];
+        assert_eq!(expected, parse_pairs(&Input::from_lines(&["[]", "[1]", "", "[2]", "[]"])).unwrap());
+        assert_eq!(expected, parse_pairs(&Input::from_lines(&["", "[]", "[1]", "", "", "[2]", "[]", "", ""])).unwrap());
+        let error = parse_pairs(&Input::from_lines(&["[]", "[1]", "[2]"])).unwrap_err();
+        assert_eq!((3, "expected an empty line between pairs"), (error.line, error.message.as_str()));
     }
 }
@@ -0,0 +1,73 @@
+use std::cmp::Ordering;
+
+use aoc_common::Solution;
+use day13::Day13;
+
+#[derive(Debug, Clone)]
+enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+fn parse(text: &str) -> Packet {
+    fn list(bytes: &[u8], i: &mut usize) -> Packet {
+        if bytes[*i] != b'[' {
+            let start = *i;
+            while bytes[*i].is_ascii_digit() {
+                *i += 1;
+            }
+            return Packet::Int(std::str::from_utf8(&bytes[start..*i]).unwrap().parse().unwrap());
+        }
+        *i += 1;
+        let mut items = Vec::new();
+        while bytes[*i] != b']' {
+            items.push(list(bytes, i));
+            if bytes[*i] == b',' {
+                *i += 1;
+            }
+        }
+        *i += 1;
+        Packet::List(items)
+    }
+    list(text.as_bytes(), &mut 0)
+}
+
+/// The rules of the puzzle, one by one.
+fn compare(left: &Packet, right: &Packet) -> Ordering {
+    match (left, right) {
+        (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+        (Packet::Int(_), Packet::List(_)) => compare(&Packet::List(vec![left.clone()]), right),
+        (Packet::List(_), Packet::Int(_)) => compare(left, &Packet::List(vec![right.clone()])),
+        (Packet::List(a), Packet::List(b)) => {
+            for (x, y) in a.iter().zip(b) {
+                match compare(x, y) {
+                    Ordering::Equal => {},
+                    ordering => return ordering,
+                }
+            }
+            a.len().cmp(&b.len())
+        },
+    }
+}
+
+fn packets(lines: &[String]) -> Vec<Packet> {
+    lines.iter().filter(|line| !line.is_empty()).map(|line| parse(line)).collect()
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day13::input, |lines| Day13::solve_part1(lines).ok(), |lines| {
+        let packets = packets(lines);
+        Some(packets.chunks(2).zip(1..).filter(|(pair, _)| compare(&pair[0], &pair[1]) == Ordering::Less).map(|(_, i)| i).sum())
+    });
+}
+
+/// A divider lands after every packet that is smaller than it.
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day13::input, |lines| Day13::solve_part2(lines).ok(), |lines| {
+        let packets = packets(lines);
+        let smaller = |divider| packets.iter().filter(|packet| compare(packet, &parse(divider)) == Ordering::Less).count() as u32;
+        Some((smaller("[[2]]") + 1) * (smaller("[[6]]") + 2))
+    });
+}
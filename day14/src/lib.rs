@@ -0,0 +1,52 @@
+//! Day 14: Regolith Reservoir. Sand falls from `500,0` into a cave of rock paths.
+
+mod task;
+
+use aoc_common::{params, Part, Session, Solution, Stepper};
+
+pub use task::{parse_grid, sand_until_abyss, sand_until_blocked, Cave, Error, Point, Tile};
+
+params! {
+    pub struct Source {
+        /// Sand falls from `source_x,0`.
+        source_x: i32 = 500,
+    }
+}
+
+/// Resting sand without a floor (part 1) and with one (part 2).
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<Vec<task::Point>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = task::Error;
+    type Params = Source;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_grid(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Source::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Source::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Source) -> Result<Self::Answer1, Self::Error> {
+        task::sand_until_abyss(input, params.source_x)
+    }
+
+    fn part2_with(input: &Self::Input, params: &Source) -> Result<Self::Answer2, Self::Error> {
+        task::sand_until_blocked(input, params.source_x)
+    }
+
+    /// One step per unit of sand, part 2 has the floor.
+    fn simulation(input: &Self::Input, part: Part, params: &Source) -> Result<Option<Box<dyn Session>>, Self::Error> {
+        Ok(Some(Box::new(Stepper::new(task::Cave::new(input, part == Part::Two, params.source_x)?))))
+    }
+}
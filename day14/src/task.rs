@@ -1,38 +1,35 @@
-use std::str::{self, FromStr};
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::render::{palette, Cell, Render, Rgb};
+use aoc_common::{scan, Grid, Point2, Simulation};
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     #[error("Internal Error")]
     #[allow(unused)]
     Internal,
-    #[error("Parse error")]
-    ParseError,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("No rock paths")]
+    NoRocks,
 }
 
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl FromStr for Point {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').unwrap();
-        Ok(Self{ x: x.parse()?, y: y.parse()? })
-    }
-}
+/// `x` is the distance to the right, `y` is the depth.
+pub type Point = Point2<i32>;
 
-impl From<std::num::ParseIntError> for Error {
-    fn from(_: std::num::ParseIntError) -> Self {
-        Error::ParseError
+fn parse_point<'a>(line: &Line<'a>, s: &'a str) -> Result<Point, ParseError> {
+    let (x, y) = scan!("{},{}", line, s)?;
+    let point = Point::new(x, y);
+    if point.x < 0 || point.y < 0 {
+        return Err(line.error(s, "coordinates must not be negative"));
     }
+    Ok(point)
 }
 
-fn transform(points: Vec<Vec<Point>>, min_x: i32, max_x: i32, max_y: i32) -> Vec<Vec<u8>> {
+fn transform(points: Vec<Vec<Point>>, min_x: i32, max_x: i32, max_y: i32) -> Grid<u8> {
     use std::mem::swap;
     let n = (max_y + 1) as usize;
     let m = (max_x - min_x + 1) as usize;
-    let mut grid = vec![vec![b'.'; m]; n];
+    let mut grid = Grid::new(n, m, b'.');
     for row in points {
         for k in 1..row.len() {
             let mut start_j = (row[k - 1].x - min_x) as usize;
@@ -47,11 +44,11 @@ fn transform(points: Vec<Vec<Point>>, min_x: i32, max_x: i32, max_y: i32) -> Vec
             }
             if start_i == end_i {
                 for j in start_j..=end_j {
-                    grid[start_i][j] = b'#';
+                    grid[(start_i, j)] = b'#';
                 }
             } else {
                 for i in start_i..=end_i {
-                    grid[i][start_j] = b'#';
+                    grid[(i, start_j)] = b'#';
                 }
             }
         }
@@ -59,94 +56,220 @@ fn transform(points: Vec<Vec<Point>>, min_x: i32, max_x: i32, max_y: i32) -> Vec
     grid
 }
 
-fn parse_grid<S: AsRef<str>>(lines: &[S]) -> Vec<Vec<Point>> {
+/// Rock paths, every segment is horizontal or vertical.
+pub fn parse_grid<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Vec<Point>>, ParseError> {
     let mut points = Vec::new();
-    for line in lines {
-        let path: Vec<Point> = line.as_ref().split(" -> ").map(|p| p.parse::<Point>().unwrap()).collect();
+    for line in parse::lines(lines) {
+        let mut path: Vec<Point> = Vec::new();
+        let parts: Vec<&str> = scan!("{* -> }", &line)?;
+        for p in parts {
+            let point = parse_point(&line, p)?;
+            if let Some(&last) = path.last() {
+                if last.x != point.x && last.y != point.y {
+                    return Err(line.error(p, format!("diagonal segment from {}", last)));
+                }
+            }
+            path.push(point);
+        }
         points.push(path);
     }
-    points
+    Ok(points)
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    return simulate(lines, |points| {
-        let min_x = points.iter().map(|v| v.iter().map(|p| p.x).min().unwrap()).min().unwrap();
-        let max_x = points.iter().map(|v| v.iter().map(|p| p.x).max().unwrap()).max().unwrap();
-        let max_y = points.iter().map(|v| v.iter().map(|p| p.y).max().unwrap()).max().unwrap();
-        (min_x, max_x, max_y)
-    });
+/// Units of sand falling from `source,0` that come to rest before sand falls into the abyss.
+pub fn sand_until_abyss(points: &[Vec<Point>], source: i32) -> Result<u32, Error> {
+    Ok(Cave::new(points, false, source)?.fill())
 }
 
-pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    simulate(lines, |points| {
-        let max_y = points.iter().map(|v| v.iter().map(|p| p.y).max().unwrap()).max().unwrap();
-        let min_x = 498 - max_y;
-        let max_x = 502 + max_y;
-        points.push(vec![Point{x: min_x, y: max_y + 2}, Point{ x: max_x, y: max_y + 2}]);
-        (min_x, max_x, max_y + 2)
-    })
+/// Units of sand falling from `source,0` that come to rest on the floor until the source is blocked.
+pub fn sand_until_blocked(points: &[Vec<Point>], source: i32) -> Result<u32, Error> {
+    Ok(Cave::new(points, true, source)?.fill())
 }
 
-fn simulate<S: AsRef<str>, F: FnMut(&mut Vec<Vec<Point>>) -> (i32, i32, i32)>(lines: &[S], mut mutate: F) -> Result<u32, Error> {
-    let mut points = parse_grid(lines);
-    let (min_x, max_x, max_y) = mutate(&mut points);
-    let mut grid = transform(points, min_x, max_x, max_y);
-    let mut total = 0;
-    loop {
-        let n = drop(&mut grid, min_x as usize);
-        if n == 0 {
-            break;
+/// Columns and the lowest row of the rocks and the source.
+fn abyss_bounds(points: &[Vec<Point>], source: i32) -> (i32, i32, i32) {
+    let min_x = points.iter().flatten().map(|p| p.x).min().unwrap().min(source);
+    let max_x = points.iter().flatten().map(|p| p.x).max().unwrap().max(source);
+    let max_y = points.iter().flatten().map(|p| p.y).max().unwrap();
+    (min_x, max_x, max_y)
+}
+
+/// Adds the floor, wide enough for the pile of sand and for every rock.
+fn floor_bounds(points: &mut Vec<Vec<Point>>, source: i32) -> (i32, i32, i32) {
+    let max_y = points.iter().flatten().map(|p| p.y).max().unwrap();
+    // the pile on the floor is never wider than this, rocks may be further out
+    let min_x = points.iter().flatten().map(|p| p.x).min().unwrap().min(source - 2 - max_y);
+    let max_x = points.iter().flatten().map(|p| p.x).max().unwrap().max(source + 2 + max_y);
+    points.push(vec![Point::new(min_x, max_y + 2), Point::new(max_x, max_y + 2)]);
+    (min_x, max_x, max_y + 2)
+}
+
+/// The cave filling with sand, one step per unit of sand.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    grid: Grid<u8>,
+    /// Column of the source in the grid.
+    source: usize,
+    units: u32,
+}
+
+impl Cave {
+    /// Sand falls from `source,0`, with `floor` the cave has a floor two units below the lowest rock.
+    pub fn new(points: &[Vec<Point>], floor: bool, source: i32) -> Result<Self, Error> {
+        if points.iter().flatten().next().is_none() {
+            return Err(Error::NoRocks);
         }
-        total += n;
+        let mut points = points.to_vec();
+        let (min_x, max_x, max_y) = if floor { floor_bounds(&mut points, source) } else { abyss_bounds(&points, source) };
+        Ok(Self { grid: transform(points, min_x, max_x, max_y), source: (source - min_x) as usize, units: 0 })
+    }
+
+    /// Units of sand at rest.
+    pub fn units(&self) -> u32 {
+        self.units
+    }
+
+    /// Drops sand until it falls into the abyss or blocks the source.
+    fn fill(mut self) -> u32 {
+        while self.step() {}
+        self.units
     }
-    Ok(total)
 }
 
-fn drop(grid: &mut Vec<Vec<u8>>, center: usize) -> u32 {
-    const WIDTH: usize = 500;
-    let mut j = WIDTH - center;
-    let n = grid.len();
-    let m = grid[0].len();
+impl Simulation for Cave {
+    fn step(&mut self) -> bool {
+        let n = drop(&mut self.grid, self.source);
+        self.units += n;
+        n > 0
+    }
+
+    fn status(&self) -> Vec<(&'static str, i64)> {
+        vec![("units", self.units as i64)]
+    }
+}
+
+/// Cell of the cave grid: `.` air, `#` rock, `o` sand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile(u8);
+
+impl Cell for Tile {
+    fn glyph(&self) -> char {
+        self.0 as char
+    }
+
+    fn colour(&self) -> Rgb {
+        match self.0 {
+            b'#' => palette::ROCK,
+            b'o' => palette::SAND,
+            _ => palette::AIR,
+        }
+    }
+}
+
+impl Render for Cave {
+    type Cell = Tile;
+
+    fn render(&self) -> Grid<Tile> {
+        self.grid.map(|&b| Tile(b))
+    }
+}
+
+fn drop(grid: &mut Grid<u8>, source: usize) -> u32 {
+    let mut j = source;
+    let n = grid.height();
+    let m = grid.width();
     for i in 1..n {
-        if grid[0][WIDTH - center] == b'o' {
+        if grid[(0, source)] == b'o' {
             break;
-        } else if grid[i][j] == b'.' {
+        } else if grid[(i, j)] == b'.' {
             continue;
         } else {
+            // nothing is outside the grid, sand sliding off a side falls into the abyss
             if j == 0 {
-                grid[i - 1][j] = b'o';
-                return (n - i - 1) as u32;
-            } else if grid[i][j - 1] == b'.' {
+                return 0;
+            } else if grid[(i, j - 1)] == b'.' {
                 j -= 1;
             } else if j + 1 == m {
-                grid[i - 1][j] = b'o';
-                return (n - i - 1) as u32;
-            } else if grid[i][j + 1] == b'.' {
+                return 0;
+            } else if grid[(i, j + 1)] == b'.' {
                 j += 1;
             } else {
-                grid[i - 1][j] = b'o';
+                grid[(i - 1, j)] = b'o';
                 return 1;
             }
         }
     }
-    return 0;
+    0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const DATA: &'static str = "498,4 -> 498,6 -> 496,6
+    const DATA: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
 
     #[test]
     fn test1() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(24), task1(&lines));
+        assert_eq!(Ok(24), sand_until_abyss(&parse_grid(&lines).unwrap(), 500));
     }
 
     #[test]
     fn test2() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(93), task2(&lines));
+        assert_eq!(Ok(93), sand_until_blocked(&parse_grid(&lines).unwrap(), 500));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_grid(&["498,4 -> 498,6", "503,4 -> 502;4"]).unwrap_err();
+        assert_eq!((2, 10, "expected \",\""), (error.line, error.column, error.message.as_str()));
+        let error = parse_grid(&["498,4 -> 496,6"]).unwrap_err();
+        assert_eq!((1, 10, 5), (error.line, error.column, error.len));
+        assert_eq!(Err(Error::NoRocks), sand_until_abyss(&[], 500));
+    }
+
+    #[test]
+    fn test_side_abyss() {
+        // sand sliding off the left end of the grid falls, it does not rest on the edge
+        let points = parse_grid(&["505,2 -> 505,8 -> 500,8 -> 500,10 -> 502,10"]).unwrap();
+        assert_eq!(Ok(0), sand_until_abyss(&points, 500));
+        let points = parse_grid(&["500,5 -> 501,5", "495,3 -> 495,3"]).unwrap();
+        assert_eq!(Ok(0), sand_until_abyss(&points, 500));
+    }
+
+    #[test]
+    fn test_rocks_beyond_floor() {
+        // rocks further out than the pile on the floor still fit in the grid
+        let points = parse_grid(&["480,1 -> 480,2"]).unwrap();
+        assert_eq!(Ok(16), sand_until_blocked(&points, 500));
+        let points = parse_grid(&["520,1 -> 520,2"]).unwrap();
+        assert_eq!(Ok(16), sand_until_blocked(&points, 500));
+    }
+
+    #[test]
+    fn test_source() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let points = parse_grid(&lines).unwrap();
+        // right of every rock the sand falls straight into the abyss
+        assert_eq!(Ok(0), sand_until_abyss(&points, 510));
+        // one unit to the left the pile is still held by the rocks
+        assert!(sand_until_abyss(&points, 499).unwrap() > 0);
+        assert_eq!(Ok(4), sand_until_blocked(&parse_grid(&["10,0 -> 10,0"]).unwrap(), 0));
+    }
+
+    #[test]
+    fn test_record() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let dir = std::env::temp_dir().join(format!("day14-frames-{}", std::process::id()));
+        let mut sink = aoc_common::FrameSink::new(&dir, aoc_common::render::FrameFormat::Ascii).unwrap();
+        let mut cave = Cave::new(&parse_grid(&lines).unwrap(), false, 500).unwrap();
+        assert_eq!(24, aoc_common::simulation::record(&mut cave, &mut sink));
+        assert_eq!(25, sink.finish().unwrap());
+        let last = std::fs::read_to_string(dir.join("frame_00024.txt")).unwrap();
+        assert_eq!(24, last.matches('o').count());
+        let first = std::fs::read_to_string(dir.join("frame_00000.txt")).unwrap();
+        assert_eq!((0, 20), (first.matches('o').count(), first.matches('#').count()));
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
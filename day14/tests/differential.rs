@@ -0,0 +1,55 @@
+use std::collections::HashSet;
+
+use aoc_common::Solution;
+use day14::Day14;
+
+fn rocks(lines: &[String]) -> HashSet<(i32, i32)> {
+    let mut rocks = HashSet::new();
+    for line in lines {
+        let points = line.split(" -> ").map(|point| {
+            let (x, y) = point.split_once(',').unwrap();
+            (x.parse::<i32>().unwrap(), y.parse::<i32>().unwrap())
+        }).collect::<Vec<_>>();
+        for pair in points.windows(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            for x in x1.min(x2)..=x1.max(x2) {
+                for y in y1.min(y2)..=y1.max(y2) {
+                    rocks.insert((x, y));
+                }
+            }
+        }
+    }
+    rocks
+}
+
+/// Drops sand grain by grain, `floor` adds the infinite floor two below the lowest rock.
+fn reference(lines: &[String], floor: bool) -> u32 {
+    let mut blocked = rocks(lines);
+    let bottom = blocked.iter().map(|&(_, y)| y).max().unwrap();
+    let mut grains = 0;
+    while !blocked.contains(&(500, 0)) {
+        let (mut x, mut y) = (500, 0);
+        loop {
+            if !floor && y > bottom {
+                return grains;
+            }
+            match [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)].into_iter().find(|p| !(blocked.contains(p) || floor && p.1 == bottom + 2)) {
+                Some(next) => (x, y) = next,
+                None => break,
+            }
+        }
+        blocked.insert((x, y));
+        grains += 1;
+    }
+    grains
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day14::input, |lines| Day14::solve_part1(lines).ok(), |lines| Some(reference(lines, false)));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(1000, aoc_gen::day14::input, |lines| Day14::solve_part2(lines).ok(), |lines| Some(reference(lines, true)));
+}
@@ -0,0 +1,63 @@
+//! Day 17: Pyroclastic Flow. Rocks of five shapes fall into a chamber 7 units wide and are pushed by jets.
+
+mod task;
+
+use aoc_common::{params, FrameSink, Part, Session, Solution, Stepper};
+
+pub use task::{parse_pattern, record, tower_height, Block, Dir, Error, Tower};
+
+params! {
+    /// Rocks dropped in each part.
+    pub struct Rocks {
+        rocks1: usize = 2022,
+        rocks2: usize = 1000000000000,
+    }
+}
+
+/// Tower height after 2022 (part 1) and 1000000000000 rocks (part 2).
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<task::Dir>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = task::Error;
+    type Params = Rocks;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_pattern(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Rocks::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Rocks::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Rocks) -> Result<Self::Answer1, Self::Error> {
+        task::task1(input, params.rocks1)
+    }
+
+    fn part2_with(input: &Self::Input, params: &Rocks) -> Result<Self::Answer2, Self::Error> {
+        task::task2(input, params.rocks2)
+    }
+
+    /// Both parts drop the same rocks, one step per rock.
+    fn simulation(input: &Self::Input, _part: Part, _params: &Rocks) -> Result<Option<Box<dyn Session>>, Self::Error> {
+        Ok(Some(Box::new(Stepper::new(task::Tower::new(input)))))
+    }
+
+    /// One frame per rock at rest, both parts stop once the tower repeats.
+    fn record(input: &Self::Input, part: Part, params: &Rocks, sink: &mut FrameSink) -> Result<bool, Self::Error> {
+        let rocks = match part {
+            Part::One => params.rocks1,
+            Part::Two => params.rocks2,
+        };
+        task::record(input, rocks, sink)?;
+        Ok(true)
+    }
+}
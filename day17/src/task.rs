@@ -1,15 +1,21 @@
-use std::{mem::swap, fmt::Display};
+use std::mem::swap;
+use aoc_common::cycle::CycleDetector;
+use aoc_common::render::{palette, Cell, FrameSink, Render, Rgb};
+use aoc_common::Simulation;
+use aoc_common::{Grid, ParseError};
 use lazy_static::lazy_static;
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    #[error("Internal Error")]
-    #[allow(unused)]
-    Internal,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("Tower height overflows")]
+    Overflow,
 }
 
+/// Direction of a jet of hot gas.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Dir {
+pub enum Dir {
     Left,
     Right,
 }
@@ -40,20 +46,28 @@ lazy_static! {
     ];
 }
 
-#[derive(Debug)]
+/// Rows below the top compared to tell whether the tower repeats.
+const SURFACE_DEPTH: usize = 32;
+
+#[derive(Debug, Clone)]
 struct Game {
-    board: Vec<Vec<u8>>,
-    new_board: Vec<Vec<u8>>,
+    board: Grid<u8>,
+    new_board: Grid<u8>,
+    /// Next jet of the pattern and next shape to fall.
+    jet: usize,
+    shape: usize,
+    /// Rows cut off the bottom of the board, they count towards the height.
+    trimmed: usize,
 }
 
 impl Game {
     fn new() -> Self {
-        let board = vec![vec![2; 9]];
-        let new_board = vec![vec![2; 9]];
-        Self { board, new_board }
+        let board = Grid::new(1, 9, 2);
+        let new_board = Grid::new(1, 9, 2);
+        Self { board, new_board, jet: 0, shape: 0, trimmed: 0 }
     }
-    fn clear_board(board: &mut Vec<Vec<u8>>) {
-        board.iter_mut().for_each(|row| row.iter_mut().for_each(|x| if *x == 1 { *x = 0; }));
+    fn clear_board(board: &mut Grid<u8>) {
+        board.values_mut().for_each(|x| if *x == 1 { *x = 0; });
     }
     fn shift(&mut self, direction: Dir) {
         #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -72,14 +86,14 @@ impl Game {
         };
         Self::clear_board(&mut self.new_board);
         let mut state = ShiftState::Search;
-        for i in (0..self.board.len()).rev() {
+        for i in (0..self.board.height()).rev() {
             let mut found = false;
             for j in range.clone() {
-                if self.board[i][prev(j)] == 1 {
-                    if self.board[i][j] == 2 {
+                if self.board[(i, prev(j))] == 1 {
+                    if self.board[(i, j)] == 2 {
                         return;
                     }
-                    self.new_board[i][j] = 1;
+                    self.new_board[(i, j)] = 1;
                     found = true;
                 }
             }
@@ -103,13 +117,13 @@ impl Game {
     }
     fn drop(&mut self) -> bool {
         Self::clear_board(&mut self.new_board);
-        for i in (0..self.board.len()).rev() {
+        for i in (0..self.board.height()).rev() {
             for j in 1..8 {
-                if self.board[i][j] == 1 {
-                    if i == 0 || self.board[i - 1][j] == 2 {
+                if self.board[(i, j)] == 1 {
+                    if i == 0 || self.board[(i - 1, j)] == 2 {
                         return false;
                     }
-                    self.new_board[i - 1][j] = self.board[i][j];
+                    self.new_board[(i - 1, j)] = self.board[(i, j)];
                 }
             }
         }
@@ -117,88 +131,266 @@ impl Game {
         true
     }
     fn freeze(&mut self) {
-        for i in 0..self.board.len() {
+        for i in 0..self.board.height() {
             for j in 1..8 {
-                if self.board[i][j] == 1 {
-                    self.board[i][j] = 2;
-                    self.new_board[i][j] = 2;
+                if self.board[(i, j)] == 1 {
+                    self.board[(i, j)] = 2;
+                    self.new_board[(i, j)] = 2;
                 }
             }
         }
     }
     fn add_shape(&mut self, n: usize) {
         let shape = &SHAPES[n];
-        let board_height = self.len();
+        let board_height = self.top();
         let shape_height = shape.len();
         let new_board_height = board_height + 3 + shape_height;
-        let mut h = self.board.len();
+        let mut h = self.board.height();
         if h > new_board_height {
-            self.board.resize(new_board_height, vec![]);
-            self.new_board.resize(new_board_height, vec![])
+            self.board.truncate(new_board_height);
+            self.new_board.truncate(new_board_height);
         } else {
             for _ in 0..(new_board_height - h) {
-                self.board.push(vec![2, 0, 0, 0, 0, 0, 0, 0, 2]);
-                self.new_board.push(vec![2, 0, 0, 0, 0, 0, 0, 0, 2]);
+                self.board.push_row([2, 0, 0, 0, 0, 0, 0, 0, 2]);
+                self.new_board.push_row([2, 0, 0, 0, 0, 0, 0, 0, 2]);
             }
         }
-        h = self.board.len();
-        let shape_len = shape.len();
-        for i in 0..shape_len {
-            self.board[h - i - 1] = shape[i].clone();
+        h = self.board.height();
+        for (i, row) in shape.iter().enumerate() {
+            self.board.row_mut(h - i - 1).copy_from_slice(row);
         }
         if h > 10000 {
-            self.board.drain(..9000);
-            self.new_board.drain(..9000);
+            self.board.remove_rows(0..9000);
+            self.new_board.remove_rows(0..9000);
+            self.trimmed += 9000;
         }
     }
-    fn play(&mut self, pattern: &[Dir], mut n: usize) {
-        let mut p = 0;
-        let mut s = 1;
-        self.add_shape(0);
+    fn drop_rock(&mut self, pattern: &[Dir]) {
+        self.add_shape(self.shape);
+        self.shape = (self.shape + 1) % SHAPES.len();
         loop {
-            self.shift(pattern[p]);
+            self.shift(pattern[self.jet]);
+            self.jet = (self.jet + 1) % pattern.len();
             if !self.drop() {
                 self.freeze();
-                n -= 1;
-                if n == 0 {
-                    break;
-                }
-                self.add_shape(s);
-                s = (s + 1) % SHAPES.len();
+                return;
             }
-            p = (p + 1) % pattern.len();
         }
     }
+    /// The next shape, the next jet and the top rows of the tower as bit masks.
+    fn state(&self) -> (usize, usize, Vec<u8>) {
+        let top = self.top();
+        let surface = (top.saturating_sub(SURFACE_DEPTH)..top).map(|i| {
+            self.board.row(i)[1..8].iter().fold(0, |mask, &x| mask << 1 | (x == 2) as u8)
+        }).collect();
+        (self.shape, self.jet, surface)
+    }
+    /// Rows of the board up to the highest rock at rest, the floor included.
+    fn top(&self) -> usize {
+        self.board.rows().rposition(|row| row.iter().skip(1).take(7).any(|x| x == &2)).unwrap_or_default() + 1
+    }
+    /// Like `top`, with the trimmed rows.
     fn len(&self) -> usize {
-        self.board.iter().rposition(|row| row.iter().skip(1).take(7).any(|x| x == &2)).unwrap_or_default() + 1
+        self.trimmed + self.top()
+    }
+}
+
+/// Cell of the chamber: 0 is air, 1 the falling rock, 2 rock at rest or a wall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block(u8);
+
+impl Cell for Block {
+    fn glyph(&self) -> char {
+        match self.0 {
+            1 => '@',
+            2 => '#',
+            _ => '.',
+        }
+    }
+
+    fn colour(&self) -> Rgb {
+        match self.0 {
+            1 => palette::ACTIVE,
+            2 => palette::ROCK,
+            _ => palette::AIR,
+        }
     }
 }
 
-impl Display for Game {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in (0..self.board.len()).rev() {
-            let s1 = self.board[i].iter().map(|x| match *x {1 => '@', 2 => '#', _ => '.' }).collect::<String>();
-            let s2 = self.new_board[i].iter().map(|x| match *x {1 => '@', 2 => '#', _ => '.' }).collect::<String>();
-            writeln!(f, "{} {}", s1, s2)?;
+/// Rows of the chamber shown in a frame, counted from the top.
+const VIEW: usize = 40;
+
+impl Render for Game {
+    type Cell = Block;
+
+    /// The top of the chamber, the highest row first.
+    fn render(&self) -> Grid<Block> {
+        let mut frame = Grid::new(0, self.board.width(), Block(0));
+        for row in self.board.rows().rev().take(VIEW) {
+            frame.push_row(row.iter().map(|&x| Block(x)));
         }
-        Ok(())
+        frame
+    }
+}
+
+/// The chamber filling with rocks, one step per rock. It never ends.
+#[derive(Debug, Clone)]
+pub struct Tower {
+    game: Game,
+    pattern: Vec<Dir>,
+    rocks: usize,
+}
+
+impl Tower {
+    pub fn new(pattern: &[Dir]) -> Self {
+        Self { game: Game::new(), pattern: pattern.to_vec(), rocks: 0 }
+    }
+
+    pub fn height(&self) -> usize {
+        self.game.len() - 1
+    }
+}
+
+impl Simulation for Tower {
+    fn step(&mut self) -> bool {
+        self.game.drop_rock(&self.pattern);
+        self.rocks += 1;
+        true
+    }
+
+    fn status(&self) -> Vec<(&'static str, i64)> {
+        vec![("rocks", self.rocks as i64), ("height", self.height() as i64)]
+    }
+}
+
+impl Render for Tower {
+    type Cell = Block;
+
+    fn render(&self) -> Grid<Block> {
+        self.game.render()
+    }
+}
+
+/// Jet pattern of the single input line, `<` and `>`.
+pub fn parse_pattern<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Dir>, ParseError> {
+    let line = lines.first().map_or("", |line| line.as_ref());
+    if line.is_empty() {
+        return Err(ParseError::eof(0, "jet pattern is empty"));
     }
+    line.char_indices().map(|(i, c)| match c {
+        '<' => Ok(Dir::Left),
+        '>' => Ok(Dir::Right),
+        c => Err(ParseError::new(0, line, i..i + c.len_utf8(), format!("unexpected jet {:?}", c))),
+    }).collect()
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S], n: usize) -> Result<usize, Error> {
+/// Height of the tower after `n` rocks. Rocks are dropped until the tower starts repeating,
+/// the rest is extrapolated from the cycle.
+pub fn tower_height(pattern: &[Dir], n: usize) -> Result<usize, Error> {
+    simulate_until_cycle(pattern, n, |_| {})
+}
+
+/// Pushes the chamber to `sink` after every rock of `tower_height`. Both parts stop once the tower
+/// starts repeating, or after `n` rocks if that comes first.
+pub fn record(pattern: &[Dir], n: usize, sink: &mut FrameSink) -> Result<usize, Error> {
+    simulate_until_cycle(pattern, n, |game| sink.push(game))
+}
+
+fn simulate_until_cycle<F: FnMut(&Game)>(pattern: &[Dir], n: usize, mut observe: F) -> Result<usize, Error> {
     let mut game = Game::new();
-    let pattern = lines[0].as_ref().bytes().map(|b| if b == b'<' { Dir::Left } else { Dir::Right }).collect::<Vec<_>>();
-    game.play(&pattern, n);
+    let mut detector = CycleDetector::new();
+    for rocks in 0.. {
+        let height = game.len() - 1;
+        if rocks == n {
+            return Ok(height);
+        }
+        if let Some(cycle) = detector.push(game.state(), height) {
+            return cycle.extrapolate(n).ok_or(Error::Overflow);
+        }
+        game.drop_rock(pattern);
+        observe(&game);
+    }
+    unreachable!()
+}
+
+/// Height of the tower after `n` rocks.
+pub fn task1(pattern: &[Dir], n: usize) -> Result<usize, Error> {
+    tower_height(pattern, n)
+}
+
+/// Height of the tower after `n` rocks, too many to drop one by one.
+pub fn task2(pattern: &[Dir], n: usize) -> Result<usize, Error> {
+    tower_height(pattern, n)
+}
+
+/// Drops every rock without looking for a cycle.
+#[cfg(test)]
+fn simulate(pattern: &[Dir], n: usize) -> Result<usize, Error> {
+    let mut game = Game::new();
+    (0..n).for_each(|_| game.drop_rock(pattern));
     Ok(game.len() - 1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    const DATA: &'static [&'static str] = &[">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"];
+    const DATA: &[&str] = &[">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"];
 
     #[test]
     fn test1() {
-        assert_eq!(Ok(3069), task1(&DATA, 2022));
+        // the example answer of the puzzle, the floor is not counted
+        assert_eq!(Ok(3068), task1(&parse_pattern(DATA).unwrap(), 2022));
+        let pattern = parse_pattern(DATA).unwrap();
+        assert_eq!(vec![Ok(1), Ok(4), Ok(6)], (1..=3).map(|n| task1(&pattern, n)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test2() {
+        assert_eq!(Ok(1514285714288), task2(&parse_pattern(DATA).unwrap(), 1000000000000));
+    }
+
+    #[test]
+    fn test_extrapolation() {
+        let pattern = parse_pattern(DATA).unwrap();
+        for n in [0, 1, 10, 100, 1000, 3000, 5000] {
+            assert_eq!(simulate(&pattern, n), tower_height(&pattern, n), "{} rocks", n);
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let pattern = parse_pattern(DATA).unwrap();
+        let mut game = Game::new();
+        game.drop_rock(&pattern);
+        game.drop_rock(&pattern);
+        assert_eq!(
+            "#.......#\n#.......#\n#.......#\n#...#...#\n#..###..#\n#...#...#\n#..####.#\n#########\n",
+            aoc_common::render::ascii(&game.render())
+        );
+    }
+
+    #[test]
+    fn test_tower() {
+        let mut tower = Tower::new(&parse_pattern(DATA).unwrap());
+        (0..10).for_each(|_| { tower.step(); });
+        assert_eq!(vec![("rocks", 10), ("height", 17)], tower.status());
+    }
+
+    #[test]
+    fn test_tall_tower() {
+        // the board is trimmed once it is 10000 rows high
+        let pattern = parse_pattern(DATA).unwrap();
+        let mut tower = Tower::new(&pattern);
+        (0..7000).for_each(|_| { tower.step(); });
+        assert_eq!(10607, tower.height());
+        assert_eq!(Ok(10607), tower_height(&pattern, 7000));
+        assert_eq!(Ok(10607), simulate(&pattern, 7000));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_pattern(&["<<>x>"]).unwrap_err();
+        assert_eq!((1, 4, "unexpected jet 'x'"), (error.line, error.column, error.message.as_str()));
+        assert_eq!("line 1: jet pattern is empty", parse_pattern::<&str>(&[]).unwrap_err().to_string());
     }
 }
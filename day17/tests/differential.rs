@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use aoc_common::Solution;
+use day17::Day17;
+
+const ROCKS: &[&[(i64, i64)]] = &[
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+];
+
+/// Drops every rock into a set of occupied cells, `y` grows up from the floor at 0.
+fn reference(pattern: &str, rocks: usize) -> usize {
+    let jets = pattern.as_bytes();
+    let mut occupied = HashSet::new();
+    let (mut height, mut jet) = (0, 0);
+    let free = |occupied: &HashSet<(i64, i64)>, rock: &[(i64, i64)], x: i64, y: i64| {
+        rock.iter().all(|&(dx, dy)| (0..7).contains(&(x + dx)) && y + dy >= 0 && !occupied.contains(&(x + dx, y + dy)))
+    };
+    for rock in ROCKS.iter().cycle().take(rocks) {
+        let (mut x, mut y) = (2, height + 3);
+        loop {
+            let dx = if jets[jet % jets.len()] == b'<' { -1 } else { 1 };
+            jet += 1;
+            if free(&occupied, rock, x + dx, y) {
+                x += dx;
+            }
+            if !free(&occupied, rock, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for &(dx, dy) in rock.iter() {
+            occupied.insert((x + dx, y + dy));
+            height = height.max(y + dy + 1);
+        }
+    }
+    height as usize
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(300, aoc_gen::day17::input, |lines| Day17::solve_part1(lines).ok(), |lines| Some(reference(&lines[0], 2022)));
+}
+
+/// Part 2 is out of reach of the reference, the cycle extrapolation is checked on smaller counts.
+#[test]
+fn tower_height() {
+    aoc_gen::check(300, |rng| (aoc_gen::day17::input(rng), rng.size(0..=3000)),
+        |(lines, rocks)| day17::tower_height(&day17::parse_pattern(lines).unwrap(), *rocks).ok(),
+        |(lines, rocks)| Some(reference(&lines[0], *rocks)));
+}
@@ -0,0 +1,51 @@
+//! Day 19: Not Enough Minerals.
+
+mod task;
+
+use aoc_common::{params, Solution};
+
+pub use task::{first_blueprints, parse_blueprints, quality_levels, Error, Resource};
+
+params! {
+    pub struct Minutes {
+        /// Minutes in part 1.
+        minutes1: u32 = 24,
+        /// Minutes in part 2.
+        minutes2: u32 = 32,
+        /// Blueprints that are not eaten by the elephants in part 2.
+        blueprints: usize = 3,
+    }
+}
+
+/// Quality levels (part 1) and the product for the first three blueprints (part 2).
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<Vec<task::Resource>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = task::Error;
+    type Params = Minutes;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_blueprints(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Minutes::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Minutes::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Minutes) -> Result<Self::Answer1, Self::Error> {
+        task::quality_levels(input, params.minutes1)
+    }
+
+    fn part2_with(input: &Self::Input, params: &Minutes) -> Result<Self::Answer2, Self::Error> {
+        task::first_blueprints(input, params.minutes2, params.blueprints)
+    }
+}
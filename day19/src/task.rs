@@ -1,18 +1,21 @@
 use std::ops::{Add, Sub};
 use std::collections::HashMap;
 
-use lazy_static::lazy_static;
-use regex::Regex;
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::scan;
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     #[error("Internal Error")]
     #[allow(unused)]
     Internal,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
+/// Amount of every resource, also used for robot costs and robot counts.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
-struct Resource {
+pub struct Resource {
     ore: u32,
     clay: u32,
     obsidian: u32,
@@ -37,6 +40,19 @@ impl Resource {
     fn new() -> Self {
         Default::default()
     }
+    /// Storage clamped to what can still be spent in the `step` minutes left: at most one robot is
+    /// built per minute, so no more than `max_needed * step` is spent, and the robots add
+    /// `robots * (step - 1)` of it by the last minute a robot is built. Clamping merges states in
+    /// the cache without changing the geodes, which are never spent.
+    fn cap(&self, robots: &Self, max_needed: &Self, step: u32) -> Self {
+        let limit = |max: u32, produced: u32| (max * step).saturating_sub(produced * (step - 1));
+        Self {
+            ore: self.ore.min(limit(max_needed.ore, robots.ore)),
+            clay: self.clay.min(limit(max_needed.clay, robots.clay)),
+            obsidian: self.obsidian.min(limit(max_needed.obsidian, robots.obsidian)),
+            geode: self.geode,
+        }
+    }
     fn ge(&self, rhs: &Self) -> bool {
         self.ore >= rhs.ore && self.clay >= rhs.clay && self.obsidian >= rhs.obsidian
     }
@@ -48,25 +64,33 @@ impl From<(u32, u32, u32, u32)> for Resource {
     }
 }
 
-fn parse_schema(line: &str) -> Result<Vec<Resource>, Error> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"(\d+)").unwrap();
-    }
-    let grep = RE.captures_iter(line).map(|cap| cap[1].parse::<u32>().unwrap()).collect::<Vec<_>>();
+/// Robot costs of a blueprint: ore, clay, obsidian and geode robots.
+fn parse_schema(line: Line) -> Result<Vec<Resource>, ParseError> {
+    let (_, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian): (&str, _, _, _, _, _, _) = scan!(
+        "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+        Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+        &line
+    )?;
     Ok(vec![
-        Resource::from((grep[1], 0, 0, 0)),
-        Resource::from((grep[2], 0, 0, 0,)),
-        Resource::from((grep[3], grep[4], 0, 0)),
-        Resource::from((grep[5], 0, grep[6], 0)),
+        Resource::from((ore, 0, 0, 0)),
+        Resource::from((clay, 0, 0, 0)),
+        Resource::from((obsidian_ore, obsidian_clay, 0, 0)),
+        Resource::from((geode_ore, 0, geode_obsidian, 0)),
     ])
 }
 
+/// Robot costs of every blueprint, one blueprint per line.
+pub fn parse_blueprints<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Vec<Resource>>, ParseError> {
+    parse::lines(lines).map(parse_schema).collect()
+}
+
 fn dfs(step: u32, costs: &[Resource], storage: Resource, robots: Resource, max_needed: &Resource
     , cache: &mut HashMap<(u32, Resource, Resource), u32>) -> u32
 {
     if step == 0 {
         return storage.geode;
     }
+    let storage = storage.cap(&robots, max_needed, step);
     let key = (step, storage, robots);
     if let Some(cache_val) = cache.get(&key) {
         return *cache_val;
@@ -74,11 +98,14 @@ fn dfs(step: u32, costs: &[Resource], storage: Resource, robots: Resource, max_n
     let mut max_geode = 0;
     for i in (0..4).rev() {
         if storage.ge(&costs[i]) {
-            match i {
-                0 => if max_needed.ore <= robots.ore { continue; },
-                1 => if max_needed.clay <= robots.clay { continue; },
-                2 => if max_needed.obsidian <= robots.obsidian { continue; },
-                _ => {},
+            let enough = match i {
+                0 => max_needed.ore <= robots.ore,
+                1 => max_needed.clay <= robots.clay,
+                2 => max_needed.obsidian <= robots.obsidian,
+                _ => false,
+            };
+            if enough {
+                continue;
             }
             let mut new_robots = robots;
             match i {
@@ -102,18 +129,17 @@ fn calculate(costs: &[Resource], n: u32) -> u32 {
     let max_needed = costs.iter().fold(Resource::new(), |r, cost|
         Resource{ ore: r.ore.max(cost.ore), clay: r.clay.max(cost.clay), obsidian: r.obsidian.max(cost.obsidian), geode: u32::MAX}
     );
-    let res = dfs(n, &costs, storage, robots, &max_needed, &mut HashMap::new());
-    res
+    dfs(n, costs, storage, robots, &max_needed, &mut HashMap::new())
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    let costs = lines.iter().map(|l| parse_schema(l.as_ref()).unwrap()).collect::<Vec<_>>();
-    Ok(costs.iter().enumerate().fold(0, |total, (i, cost)| total + calculate(cost, 24) * (i as u32 + 1)))
+/// Sum of quality levels: the blueprint id times the geodes opened in `minutes`.
+pub fn quality_levels(costs: &[Vec<Resource>], minutes: u32) -> Result<u32, Error> {
+    Ok(costs.iter().enumerate().fold(0, |total, (i, cost)| total + calculate(cost, minutes) * (i as u32 + 1)))
 }
 
-pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    let costs = lines.iter().map(|l| parse_schema(l.as_ref()).unwrap()).collect::<Vec<_>>();
-    Ok(costs.iter().take(3).map(|cost| calculate(cost,32)).product())
+/// Product of the geodes opened in `minutes` by the first `count` blueprints.
+pub fn first_blueprints(costs: &[Vec<Resource>], minutes: u32, count: usize) -> Result<u32, Error> {
+    Ok(costs.iter().take(count).map(|cost| calculate(cost, minutes)).product())
 }
 
 #[cfg(test)]
@@ -126,11 +152,38 @@ mod tests {
 
     #[test]
     fn test1() {
-        assert_eq!(Ok(33), task1(&DATA));
+        assert_eq!(Ok(33), quality_levels(&parse_blueprints(DATA).unwrap(), 24));
     }
 
     #[test]
     fn test2() {
-        assert_eq!(Ok(62), task2(&DATA));
+        // the example has two blueprints, they open 56 and 62 geodes in 32 minutes
+        let blueprints = parse_blueprints(DATA).unwrap();
+        assert_eq!(Ok(56), first_blueprints(&blueprints, 32, 1));
+        assert_eq!(Ok(62), first_blueprints(&blueprints[1..], 32, 1));
+        assert_eq!(Ok(56 * 62), first_blueprints(&blueprints, 32, 3));
+        // blueprint 1 opens 9 geodes in 24 minutes, blueprint 2 opens 12
+        assert_eq!(Ok(9), first_blueprints(&parse_blueprints(DATA).unwrap(), 24, 1));
+    }
+
+    #[test]
+    fn test_cap() {
+        let storage = Resource::from((100, 5, 30, 50));
+        let robots = Resource::from((2, 0, 10, 1));
+        let max_needed = Resource::from((4, 14, 7, u32::MAX));
+        assert_eq!(Resource::from((8, 5, 1, 50)), storage.cap(&robots, &max_needed, 3));
+        assert_eq!(Resource::from((4, 5, 7, 50)), storage.cap(&robots, &max_needed, 1));
+        // the cap only merges states, the differential tests compare with a search without it
+        let blueprints = parse_blueprints(DATA).unwrap();
+        assert_eq!((9, 12), (calculate(&blueprints[0], 24), calculate(&blueprints[1], 24)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let line = DATA[0].replace("14 clay", "1x clay");
+        let error = parse_blueprints(&[DATA[1], &line]).unwrap_err();
+        assert_eq!((2, 107, 2), (error.line, error.column, error.len));
+        let error = parse_blueprints(&["Blueprint 1: Each ore robot costs 4 ore."]).unwrap_err();
+        assert_eq!("expected \" ore. Each clay robot costs \"", error.message);
     }
 }
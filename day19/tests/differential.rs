@@ -0,0 +1,69 @@
+use aoc_common::Solution;
+use day19::{Day19, Minutes};
+
+/// Costs in ore, clay and obsidian of the ore, clay, obsidian and geode robots.
+fn blueprint(line: &str) -> [[u32; 3]; 4] {
+    let numbers = line.split(|c: char| !c.is_ascii_digit())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.parse().unwrap())
+        .collect::<Vec<u32>>();
+    [[numbers[1], 0, 0], [numbers[2], 0, 0], [numbers[3], numbers[4], 0], [numbers[5], 0, numbers[6]]]
+}
+
+/// Tries every order of building robots, each one as soon as it is affordable.
+/// The only pruning: no more robots of a kind than the most of it a robot costs.
+fn geodes(costs: &[[u32; 3]; 4], minutes: u32, robots: [u32; 4], stock: [u32; 4]) -> u32 {
+    let mut best = stock[3] + robots[3] * minutes;
+    for (kind, cost) in costs.iter().enumerate() {
+        if kind < 3 && robots[kind] >= costs.iter().map(|cost| cost[kind]).max().unwrap() {
+            continue;
+        }
+        if (0..3).any(|r| cost[r] > 0 && robots[r] == 0) {
+            continue;
+        }
+        let wait = (0..3).map(|r| if stock[r] >= cost[r] { 0 } else { (cost[r] - stock[r]).div_ceil(robots[r]) }).max().unwrap();
+        if wait + 1 >= minutes {
+            continue;
+        }
+        let mut next_stock = stock;
+        for r in 0..4 {
+            next_stock[r] += robots[r] * (wait + 1);
+        }
+        for r in 0..3 {
+            next_stock[r] -= cost[r];
+        }
+        let mut next_robots = robots;
+        next_robots[kind] += 1;
+        best = best.max(geodes(costs, minutes - wait - 1, next_robots, next_stock));
+    }
+    best
+}
+
+fn quality_levels(lines: &[String], minutes: u32) -> u32 {
+    lines.iter().enumerate().map(|(i, line)| (i as u32 + 1) * geodes(&blueprint(line), minutes, [1, 0, 0, 0], [0; 4])).sum()
+}
+
+fn first_blueprints(lines: &[String], minutes: u32, count: usize) -> u32 {
+    lines.iter().take(count).map(|line| geodes(&blueprint(line), minutes, [1, 0, 0, 0], [0; 4])).product()
+}
+
+/// The full 24 and 32 minutes are far too long for the reference, the cases run fewer.
+fn input(rng: &mut aoc_gen::Rng) -> (Vec<String>, u32) {
+    (aoc_gen::day19::input(rng), rng.int(8..=20) as u32)
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(1000, input, |(lines, minutes)| {
+        let blueprints = Day19::parse(lines).ok()?;
+        Day19::part1_with(&blueprints, &Minutes { minutes1: *minutes, ..Minutes::default() }).ok()
+    }, |(lines, minutes)| Some(quality_levels(lines, *minutes)));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(1000, input, |(lines, minutes)| {
+        let blueprints = Day19::parse(lines).ok()?;
+        Day19::part2_with(&blueprints, &Minutes { minutes2: *minutes, ..Minutes::default() }).ok()
+    }, |(lines, minutes)| Some(first_blueprints(lines, *minutes, 3)));
+}
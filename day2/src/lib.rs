@@ -0,0 +1,163 @@
+//! Streaming variant of day 1: lines flow through a `Pipeline`, `day1::Tally` groups them into elves
+//! and the three largest calorie totals are kept in a bounded priority queue.
+//! `aggregate` does the same for several files read concurrently and keeps track of where every elf comes from.
+//! It solves day 1's puzzle, so it is not in the runner's registry: `aoc run 1` and `aoc verify 1` cover the answers.
+
+use std::cmp::Reverse;
+use std::fmt::Write as _;
+
+use aoc_common::parse::{Line, ParseError};
+use aoc_common::pipeline::{self, Pipeline};
+use aoc_common::PriorityQueue;
+use day1::{Elf, Tally};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum Error {
+    #[error("{0}: {1}")]
+    Parse(String, ParseError),
+}
+
+/// Capacity of the channels between the pipeline stages.
+pub const CAPACITY: usize = 1024;
+
+/// A file name with the numbered lines of the file.
+pub type File = (String, Pipeline<(usize, String), Error>);
+
+/// The three largest calorie totals of the numbered lines, largest first.
+pub fn process(lines: Pipeline<(usize, String), ParseError>) -> Result<Vec<i64>, pipeline::Error<ParseError>> {
+    let top = lines
+        .stage(Tally::new(), |tally, (index, text)| tally.push(Line::new(index, &text)), |tally| Ok(tally.finish()))
+        .fold(PriorityQueue::with_capacity_bounded(3), |mut top, elf| {
+            top.push(elf.total());
+            Ok(top)
+        })?;
+    Ok(top.into_sorted_vec().into_iter().rev().collect())
+}
+
+/// Calories of one input file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSummary {
+    pub name: String,
+    pub elves: usize,
+    pub calories: i64,
+    /// The elf with the most calories, the first one on ties.
+    pub max: Option<Elf>,
+}
+
+/// Elves of several files: the ones with the most calories overall and a summary per file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    /// Index of the file and elf, largest first, ties in file then input order.
+    pub top: Vec<(usize, Elf)>,
+    pub files: Vec<FileSummary>,
+}
+
+impl Aggregate {
+    /// Where the elf starts as `file:line`, lines are numbered from 1.
+    pub fn location(&self, file: usize, elf: &Elf) -> String {
+        format!("{}:{}", self.files[file].name, elf.line + 1)
+    }
+
+    /// The top elves with their location, then one row per file.
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{:>4}  {:>10}  elf", "rank", "total").unwrap();
+        for (rank, (file, elf)) in self.top.iter().enumerate() {
+            writeln!(out, "{:>4}  {:>10}  {}", rank + 1, elf.total(), self.location(*file, elf)).unwrap();
+        }
+        writeln!(out, "total {}", self.top.iter().map(|(_, elf)| elf.total()).sum::<i64>()).unwrap();
+        writeln!(out, "{:>5}  {:>10}  {:>10}  file", "elves", "calories", "max").unwrap();
+        for summary in &self.files {
+            let max = summary.max.as_ref().map_or(0, Elf::total);
+            writeln!(out, "{:>5}  {:>10}  {:>10}  {}", summary.elves, summary.calories, max, summary.name).unwrap();
+        }
+        out
+    }
+}
+
+/// The `k` elves with the most calories among all `files`, given as names with their numbered lines.
+/// Every file is grouped into elves on its own thread, the elves of all files meet in one fold.
+pub fn aggregate(files: Vec<File>, k: usize) -> Result<Aggregate, pipeline::Error<Error>> {
+    let mut summaries = Vec::new();
+    let mut sources = Vec::new();
+    for (file, (name, lines)) in files.into_iter().enumerate() {
+        let parse_error = name.clone();
+        sources.push(lines.stage(
+            Tally::new(),
+            move |tally, (index, text)| match tally.push(Line::new(index, &text)) {
+                Ok(elf) => Ok(elf.map(|elf| (file, elf))),
+                Err(error) => Err(Error::Parse(parse_error.clone(), error)),
+            },
+            move |tally| Ok(tally.finish().map(|elf| (file, elf))),
+        ));
+        summaries.push(FileSummary { name, elves: 0, calories: 0, max: None });
+    }
+    let top = PriorityQueue::by_key(|(file, elf): &(usize, Elf)| (elf.total(), Reverse(*file), Reverse(elf.index))).bounded(k);
+    let (top, files) = Pipeline::merge(CAPACITY, sources).fold((top, summaries), |(mut top, mut files), (file, elf)| {
+        // the elves of one file arrive in input order
+        let summary = &mut files[file];
+        summary.elves += 1;
+        summary.calories += elf.total();
+        if summary.max.as_ref().is_none_or(|max| elf.total() > max.total()) {
+            summary.max = Some(elf.clone());
+        }
+        top.push((file, elf));
+        Ok((top, files))
+    })?;
+    let mut top = top.into_sorted_vec();
+    top.reverse();
+    Ok(Aggregate { top, files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(lines: &[&str]) -> Pipeline<(usize, String), ParseError> {
+        Pipeline::new(2, lines.iter().map(|line| line.to_string()).enumerate().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn top3() {
+        let test_data = ["1", "2", "", "3", "4", "", "5", "6", "", "7", "", "1", "", "9"];
+        assert_eq!(vec![11, 9, 7], process(numbered(&test_data)).unwrap());
+    }
+
+    #[test]
+    fn parse_error() {
+        let Err(pipeline::Error::Stage(error)) = process(numbered(&["1", "", "2", "x3"])) else { panic!("expected a parse error") };
+        assert_eq!((4, 1, 2), (error.line, error.column, error.len));
+    }
+
+    fn file(name: &str, lines: &[&str]) -> File {
+        (name.to_string(), Pipeline::new(2, lines.iter().map(|line| line.to_string()).enumerate().collect::<Vec<_>>()))
+    }
+
+    #[test]
+    fn aggregate_files() {
+        let files = vec![file("a", &["1", "2", "", "9", "", "5"]), file("b", &["", "9", "", "20"]), file("c", &[])];
+        let aggregate = aggregate(files, 3).unwrap();
+        let top = aggregate.top.iter().map(|(file, elf)| (aggregate.location(*file, elf), elf.total())).collect::<Vec<_>>();
+        assert_eq!(vec![("b:4".to_string(), 20), ("a:4".to_string(), 9), ("b:2".to_string(), 9)], top);
+        let summaries = aggregate.files.iter().map(|s| (s.elves, s.calories, s.max.as_ref().map(|elf| elf.line))).collect::<Vec<_>>();
+        assert_eq!(vec![(3, 17, Some(3)), (2, 29, Some(3)), (0, 0, None)], summaries);
+        let report = concat!(
+            "rank       total  elf\n",
+            "   1          20  b:4\n",
+            "   2           9  a:4\n",
+            "   3           9  b:2\n",
+            "total 38\n",
+            "elves    calories         max  file\n",
+            "    3          17           9  a\n",
+            "    2          29          20  b\n",
+            "    0           0           0  c\n",
+        );
+        assert_eq!(report, aggregate.report());
+    }
+
+    #[test]
+    fn aggregate_error() {
+        let error = aggregate(vec![file("a", &["1"]), file("b", &["2", "x"])], 3).unwrap_err();
+        assert_eq!("b: line 2, column 1: cannot parse \"x\": invalid digit found in string", error.to_string().lines().next().unwrap());
+    }
+}
@@ -1,78 +1,79 @@
-mod input;
-mod priority_queue;
-
 use std::env;
-use std::io;
-use std::sync::mpsc::{channel, Receiver, Sender};
-use std::thread;
-use priority_queue::PriorityQueue;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use std::process::ExitCode;
 
-enum Mode {
-    Stdin,
-    File(String),
-}
+use aoc_common::cli::{self, Options, Source};
+use aoc_common::pipeline::Pipeline;
+use aoc_common::report::Format;
+use aoc_common::{Params, Solution};
+use day1::{Day1, Top};
+use day2::{aggregate, CAPACITY};
 
-fn process(input_stream: Receiver<String>, result_sender: Sender<Vec<i32>>) {
-    let mut current = 0;
-    let mut top = PriorityQueue::new();
-    let mut update_top = |x| {
-        top.push(x);
-        if top.len() > 3 {
-            top.pop();
-        }
-    };
-    while let Ok(input) = input_stream.recv() {
-        if input.is_empty() {
-            update_top(current);
-            current = 0;
+const USAGE: &str = "usage: day2 [<file or directory>... | - | --example] [--param top=<k>] [--config <path>]";
+
+/// The files to read: directories stand for the files directly in them, in name order.
+fn input_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, cli::Error> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let error = |e| cli::Error::Input(path.display().to_string(), e);
+            let mut entries = fs::read_dir(path).map_err(error)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>().map_err(error)?;
+            entries.retain(|entry| entry.is_file());
+            entries.sort();
+            files.extend(entries);
         } else {
-            let x: i32 = input.parse().unwrap();
-            current += x;
+            files.push(path.clone());
         }
     }
-    update_top(current);
-    result_sender.send(top.into_iter().collect()).unwrap();
+    Ok(files)
 }
 
-fn main() -> Result<(), io::Error> {
-    let mode = if let Some(file_name) = env::args().nth(1) {
-        Mode::File(file_name)
-    } else {
-        Mode::Stdin
-    };
-    let (sender, receiver) = channel();
-    let (result_sender, result_receiver) = channel();
-    let handle = thread::spawn(move || process(receiver, result_sender));
-
-    match mode {
-        Mode::Stdin => input::read_stdin(sender)?,
-        Mode::File(file_name) => input::read_file(sender, file_name)?
+/// Prints the top elves of all inputs, `top` comes from day 1's parameters.
+fn elves(options: &Options, mut paths: Vec<PathBuf>) -> Result<ExitCode, cli::Error> {
+    if options.format != Format::Text {
+        return Err(cli::Error::Usage("day2 only writes text".to_string()));
+    }
+    let top = Top::build(&cli::read_params(options, Day1::DAY)?).map_err(cli::Error::Usage)?;
+    let mut sources = Vec::new();
+    match &options.source {
+        Source::Default if !paths.is_empty() => {},
+        Source::Default | Source::Stdin => sources.push(("-".to_string(), Pipeline::lines(CAPACITY, BufReader::new(io::stdin())))),
+        Source::File(path) => paths.insert(0, path.clone()),
+        Source::Example => {
+            let lines = Day1::EXAMPLE.lines().map(String::from).enumerate();
+            sources.push(("example".to_string(), Pipeline::new(CAPACITY, lines)));
+        },
+    }
+    for path in input_files(&paths)? {
+        let name = path.display().to_string();
+        let file = File::open(&path).map_err(|e| cli::Error::Input(name.clone(), e))?;
+        sources.push((name, Pipeline::lines(CAPACITY, BufReader::new(file))));
+    }
+    match aggregate(sources, top.top) {
+        Ok(aggregate) => {
+            print!("{}", aggregate.report());
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            Ok(ExitCode::FAILURE)
+        }
     }
-
-    let top = result_receiver.recv().unwrap();
-    handle.join().unwrap();
-    println!("{} {} {}", top[0], top[1], top[2]);
-    println!("total {}", top.into_iter().sum::<i32>());
-    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use std::sync::mpsc::channel;
-    use std::thread;
-    use super::*;
-    #[test]
-    fn top3() {
-        let test_data = vec!["1", "2", "", "3", "4", "", "5", "6", "", "7", "", "1", "", "9"];
-        let (sender, receiver) = channel();
-        let (result_sender, result_receiver) = channel();
-        let handle = thread::spawn(move || process(receiver, result_sender));
-        for s in test_data {
-            sender.send(s.to_owned()).unwrap();
+fn main() -> ExitCode {
+    match Options::parse_files(env::args().skip(1)).and_then(|(options, paths)| elves(&options, paths)) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("{}", error);
+            if let cli::Error::Usage(_) = error {
+                eprintln!("{}", USAGE);
+            }
+            error.exit_code()
         }
-        drop(sender);
-        let top = result_receiver.recv().unwrap();
-        handle.join().unwrap();
-        assert_eq!(vec![11, 9, 7], top);
     }
 }
@@ -0,0 +1,34 @@
+use aoc_common::pipeline::Pipeline;
+
+fn top3(lines: &[String]) -> Vec<i64> {
+    day2::process(Pipeline::new(16, lines.iter().cloned().enumerate().collect::<Vec<_>>())).unwrap()
+}
+
+/// Splits the input into three files at elf boundaries.
+fn top3_of_files(lines: &[String]) -> Vec<i64> {
+    let elves = lines.split(|line| line.is_empty()).collect::<Vec<_>>();
+    let files = elves.chunks(elves.len().div_ceil(3).max(1)).enumerate().map(|(i, elves)| {
+        let lines = elves.join(&String::new()).into_iter().enumerate().collect::<Vec<_>>();
+        (format!("file{}", i), Pipeline::new(16, lines))
+    });
+    let aggregate = day2::aggregate(files.collect(), 3).unwrap();
+    aggregate.top.iter().map(|(_, elf)| elf.total()).collect()
+}
+
+fn reference(lines: &[String]) -> Vec<i64> {
+    let mut totals = lines.split(|line| line.is_empty())
+        .map(|elf| elf.iter().map(|line| line.parse::<i64>().unwrap()).sum())
+        .collect::<Vec<_>>();
+    totals.sort();
+    totals.into_iter().rev().take(3).collect()
+}
+
+#[test]
+fn top_three() {
+    aoc_gen::check(1000, aoc_gen::day1::input, |lines| top3(lines), |lines| reference(lines));
+}
+
+#[test]
+fn top_three_of_files() {
+    aoc_gen::check(500, aoc_gen::day1::input, |lines| top3_of_files(lines), |lines| reference(lines));
+}
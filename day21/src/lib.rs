@@ -0,0 +1,48 @@
+//! Day 21: Monkey Math.
+
+mod task;
+
+use aoc_common::{params, Solution};
+
+pub use task::{human_number, parse_input, root_number, Error, Monkeys, Op, Yell};
+
+params! {
+    /// Names of the monkeys the puzzle asks about.
+    pub struct Names {
+        root: String = "root".to_string(),
+        human: String = "humn".to_string(),
+    }
+}
+
+/// Number yelled by `root` (part 1) and the number `humn` has to yell (part 2).
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = task::Monkeys;
+    type Answer1 = i64;
+    type Answer2 = i64;
+    type Error = task::Error;
+    type Params = Names;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_input(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Names::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Names::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Names) -> Result<Self::Answer1, Self::Error> {
+        task::root_number(input, &params.root)
+    }
+
+    fn part2_with(input: &Self::Input, params: &Names) -> Result<Self::Answer2, Self::Error> {
+        task::human_number(input, &params.root, &params.human)
+    }
+}
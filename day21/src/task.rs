@@ -1,168 +1,173 @@
-use std::{collections::HashMap, str::FromStr};
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::scan;
+use aoc_common::scan::Field;
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    #[error("Internal error")]
-    #[allow(unused)]
-    Internal,
-
-    #[error("Parse operation error")]
-    ParseOperatonError,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 
     #[error("Wrong operation")]
     WrongOperation,
 
-    #[error("Monkey not found")]
-    NoMonkey,
+    #[error("Unknown monkey {0}")]
+    UnknownMonkey(String),
+
+    #[error("Monkey {0} depends on its own number")]
+    Cycle(String),
+
+    #[error("Division by zero at monkey {0}")]
+    DivisionByZero(String),
+
+    #[error("Overflow at monkey {0}")]
+    Overflow(String),
+
+    #[error("The number of {0} does not matter to the root")]
+    NoHuman(String),
 }
 
 #[derive(Debug, Clone, Copy)]
 enum OpType { Add, Sub, Mul, Div }
 
+/// Operation on the numbers of two other monkeys.
 #[derive(Debug, Clone)]
-struct Op {
+pub struct Op {
     lhs: String,
     rhs: String,
     op_type: OpType,
 }
 
+/// What a monkey yells: a number or the result of an operation.
 #[derive(Debug, Clone)]
-enum Yell {
+pub enum Yell {
     Number(i64),
     Operation(Op)
 }
 
-impl FromStr for Op {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split_ascii_whitespace().collect::<Vec<_>>();
-        let lhs = parts.get(0).ok_or(Error::ParseOperatonError)?.to_string();
-        let rhs = parts.get(2).ok_or(Error::ParseOperatonError)?.to_string();
-        let op_type = match parts[1] {
-            "+" => OpType::Add,
-            "-" => OpType::Sub,
-            "*" => OpType::Mul,
-            "/" => OpType::Div,
-            _ => return Err(Error::WrongOperation)
-        };
-        Ok(Op{ lhs, rhs, op_type })
+impl<'a> Field<'a> for OpType {
+    fn from_text(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        match text {
+            "+" => Ok(OpType::Add),
+            "-" => Ok(OpType::Sub),
+            "*" => Ok(OpType::Mul),
+            "/" => Ok(OpType::Div),
+            _ => Err(line.error(text, "expected \"+\", \"-\", \"*\" or \"/\"")),
+        }
     }
 }
 
-impl FromStr for Yell {
-    type Err = Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.parse::<i64>().map(|x| Yell::Number(x)).or_else(|_| s.parse::<Op>().map(|op| Yell::Operation(op)))
+impl Op {
+    /// Number of the monkey `name` doing this operation.
+    fn apply(&self, name: &str, lhs: i64, rhs: i64) -> Result<i64, Error> {
+        let result = match self.op_type {
+            OpType::Add => lhs.checked_add(rhs),
+            OpType::Sub => lhs.checked_sub(rhs),
+            OpType::Mul => lhs.checked_mul(rhs),
+            OpType::Div if rhs == 0 => return Err(Error::DivisionByZero(name.to_string())),
+            OpType::Div => lhs.checked_div(rhs),
+        };
+        result.ok_or_else(|| Error::Overflow(name.to_string()))
     }
 }
 
-impl Op {
-    fn apply(&self, lhs: i64, rhs: i64) -> i64 {
-        match self.op_type {
-            OpType::Add => lhs + rhs,
-            OpType::Sub => lhs - rhs,
-            OpType::Mul => lhs * rhs,
-            OpType::Div => lhs / rhs,
+/// Jobs of all monkeys by name.
+pub type Monkeys = HashMap<String, Yell>;
+
+/// Monkey jobs, `root: pppw + sjmn` or `dbpl: 5`.
+pub fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<Monkeys, ParseError> {
+    let mut monkeys = Monkeys::new();
+    for line in parse::lines(lines) {
+        let (name, lhs, op, rhs): (&str, &str, Option<OpType>, Option<&str>) = scan!("{}: {}{? {+|-|*|/} {}}", &line)?;
+        let yell = match (op, rhs) {
+            (Some(op_type), Some(rhs)) => Yell::Operation(Op { lhs: lhs.to_string(), rhs: rhs.to_string(), op_type }),
+            _ => Yell::Number(line.parse(lhs)?),
+        };
+        if monkeys.insert(name.to_string(), yell).is_some() {
+            return Err(line.error(name, format!("monkey {} is defined twice", name)));
         }
     }
+    Ok(monkeys)
 }
 
-fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<HashMap<String, Yell>, Error> {
-    Ok(lines.iter().map(|l| {
-        let (name, yell) = l.as_ref().split_once(':').unwrap();
-        (name.to_string(), yell.trim().parse::<Yell>().unwrap())
-    }).collect())
+/// Numbers of `key` and of every monkey it depends on, with whether they depend on `human`.
+struct Values<'a> {
+    monkeys: &'a Monkeys,
+    human: Option<&'a str>,
+    values: HashMap<&'a str, (i64, bool)>,
+    /// Monkeys whose operands are being evaluated, meeting one of them again is a cycle.
+    visiting: HashSet<&'a str>,
 }
 
-fn compute(monkeys: &HashMap<String, Yell>, key: &str) -> Result<i64, Error> {
-    if let Some(yell) = monkeys.get(key) {
-        let res = match yell {
-            Yell::Number(x) => *x,
-            Yell::Operation(op) => op.apply(compute(monkeys, &op.lhs)?, compute(monkeys, &op.rhs)?)
-        };
-        Ok(res)
-    } else {
-        Err(Error::NoMonkey)
+impl<'a> Values<'a> {
+    fn new(monkeys: &'a Monkeys, human: Option<&'a str>) -> Self {
+        Self { monkeys, human, values: HashMap::new(), visiting: HashSet::new() }
     }
-}
 
-fn compute_with_cache(monkeys: &HashMap<String, Yell>, key: &str, cache: &mut HashMap<String, i64>, human_cache: &mut HashMap<String, bool>) -> Result<(i64, bool), Error> {
-    if let Some(yell) = monkeys.get(key) {
-        let res = match yell {
-            Yell::Number(x) => (*x, key == "humn"),
-            Yell::Operation(op) => {
-                let (lhs, lhumn) = compute_with_cache(monkeys, &op.lhs, cache, human_cache)?;
-                let (rhs, rhumn) = compute_with_cache(monkeys, &op.rhs, cache, human_cache)?;
-                (op.apply(lhs, rhs), lhumn || rhumn)
-            }
-        };
-        cache.insert(key.to_string(), res.0);
-        human_cache.insert(key.to_string(), res.1);
-        Ok(res)
-    } else {
-        Err(Error::NoMonkey)
+    fn yell(&self, key: &str) -> Result<&'a Yell, Error> {
+        self.monkeys.get(key).ok_or_else(|| Error::UnknownMonkey(key.to_string()))
     }
-}
 
-fn expect(monkeys: &HashMap<String, Yell>, key: &str, expected_num: i64, cache: &HashMap<String, i64>, human_cache: &HashMap<String, bool>) -> Option<i64>{
-    let yell = monkeys.get(key).unwrap();
-    match yell {
-        Yell::Operation(op) => {
-            let left_human = *human_cache.get(&op.lhs).unwrap();
-            let left_num = *cache.get(&op.lhs).unwrap();
-            let right_num = *cache.get(&op.rhs).unwrap();
-            match op.op_type {
-                OpType::Add => {
-                    if left_human {
-                        expect(monkeys, &op.lhs, expected_num - right_num, cache, human_cache)
-                    } else {
-                        expect(monkeys, &op.rhs, expected_num - left_num, cache, human_cache)
-                    }
-                },
-                OpType::Sub => {
-                    if left_human {
-                        expect(monkeys, &op.lhs, expected_num + right_num, cache, human_cache)
-                    } else {
-                        expect(monkeys, &op.rhs, left_num - expected_num, cache, human_cache)
-                    }
-                },
-                OpType::Mul => {
-                    if left_human {
-                        expect(monkeys, &op.lhs, expected_num / right_num, cache, human_cache)
-                    } else {
-                        expect(monkeys, &op.rhs, expected_num / left_num, cache, human_cache)
-                    }
-                },
-                OpType::Div => {
-                    if left_human {
-                        expect(monkeys, &op.lhs, expected_num * right_num, cache, human_cache)
-                    } else {
-                        expect(monkeys, &op.rhs, left_num / expected_num, cache, human_cache)
-                    }
+    fn evaluate(&mut self, key: &'a str) -> Result<(i64, bool), Error> {
+        if let Some(&value) = self.values.get(key) {
+            return Ok(value);
+        }
+        let value = match self.yell(key)? {
+            Yell::Number(x) => (*x, self.human == Some(key)),
+            Yell::Operation(op) => {
+                if !self.visiting.insert(key) {
+                    return Err(Error::Cycle(key.to_string()));
                 }
+                let (lhs, left_human) = self.evaluate(&op.lhs)?;
+                let (rhs, right_human) = self.evaluate(&op.rhs)?;
+                self.visiting.remove(key);
+                (op.apply(key, lhs, rhs)?, left_human || right_human)
             }
-        },
-        Yell::Number(_) => {
-            if *human_cache.get(key).unwrap() {
-                Some(expected_num)
-            } else {
-                None
-            }
-        }
+        };
+        self.values.insert(key, value);
+        Ok(value)
     }
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<i64, Error> {
-    let monkeys = parse_input(lines)?;
-    compute(&monkeys, "root")
+/// Number yelled by the monkey `root`.
+pub fn root_number(monkeys: &Monkeys, root: &str) -> Result<i64, Error> {
+    Ok(Values::new(monkeys, None).evaluate(root)?.0)
 }
 
-pub fn task2<S: AsRef<str>>(lines: &[S], expected_num: i64) -> Result<i64, Error> {
-    let monkeys = parse_input(lines)?;
-    let mut cache = HashMap::new();
-    let mut human_cache = HashMap::new();
-    compute_with_cache(&monkeys, "root", &mut cache, &mut human_cache)?;
-    Ok(expect(&monkeys, "root", expected_num, &cache, &human_cache).unwrap())
+/// Finds the number `human` has to yell so that both operands of `root` are equal.
+pub fn human_number(monkeys: &Monkeys, root: &str, human: &str) -> Result<i64, Error> {
+    let mut values = Values::new(monkeys, Some(human));
+    values.evaluate(root)?;
+    let Yell::Operation(op) = values.yell(root)? else { return Err(Error::WrongOperation) };
+    let (lhs, left_human) = values.evaluate(&op.lhs)?;
+    let (rhs, right_human) = values.evaluate(&op.rhs)?;
+    let (mut key, mut expected) = match (left_human, right_human) {
+        (true, _) => (op.lhs.as_str(), rhs),
+        (false, true) => (op.rhs.as_str(), lhs),
+        (false, false) => return Err(Error::NoHuman(human.to_string())),
+    };
+    // walk down the operands that depend on the human, inverting each operation
+    while let Yell::Operation(op) = values.yell(key)? {
+        let (lhs, left_human) = values.evaluate(&op.lhs)?;
+        let (rhs, _) = values.evaluate(&op.rhs)?;
+        let (next, divisor, number) = match (op.op_type, left_human) {
+            (OpType::Add, true) => (&op.lhs, 1, expected.checked_sub(rhs)),
+            (OpType::Add, false) => (&op.rhs, 1, expected.checked_sub(lhs)),
+            (OpType::Sub, true) => (&op.lhs, 1, expected.checked_add(rhs)),
+            (OpType::Sub, false) => (&op.rhs, 1, lhs.checked_sub(expected)),
+            (OpType::Mul, true) => (&op.lhs, rhs, expected.checked_div(rhs)),
+            (OpType::Mul, false) => (&op.rhs, lhs, expected.checked_div(lhs)),
+            (OpType::Div, true) => (&op.lhs, 1, expected.checked_mul(rhs)),
+            (OpType::Div, false) => (&op.rhs, expected, lhs.checked_div(expected)),
+        };
+        if divisor == 0 {
+            return Err(Error::DivisionByZero(key.to_string()));
+        }
+        expected = number.ok_or_else(|| Error::Overflow(key.to_string()))?;
+        key = next;
+    }
+    Ok(expected)
 }
 
 #[cfg(test)]
@@ -187,12 +192,40 @@ mod tests {
     #[test]
     fn test1() {
         let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok(152), task1(&lines));
+        assert_eq!(Ok(152), root_number(&parse_input(&lines).unwrap(), "root"));
+        assert_eq!(Ok(150), root_number(&parse_input(&lines).unwrap(), "sjmn"));
+        assert_eq!(Err(Error::UnknownMonkey("none".to_string())), root_number(&parse_input(&lines).unwrap(), "none"));
     }
 
     #[test]
     fn test2() {
         let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok(301), task2(&lines, 300));
+        assert_eq!(Ok(301), human_number(&parse_input(&lines).unwrap(), "root", "humn"));
+        // with sllz unknown pppw = (sllz + 4) / 4 has to equal sjmn = 150
+        assert_eq!(Ok(596), human_number(&parse_input(&lines).unwrap(), "root", "sllz"));
+        assert_eq!(Err(Error::NoHuman("none".to_string())), human_number(&parse_input(&lines).unwrap(), "root", "none"));
+    }
+
+    #[test]
+    fn test_bad_monkeys() {
+        let monkeys = parse_input(&["root: a / b", "a: 4", "b: c - c", "c: 2"]).unwrap();
+        assert_eq!(Err(Error::DivisionByZero("root".to_string())), root_number(&monkeys, "root"));
+        assert_eq!(Err(Error::DivisionByZero("a".to_string())), human_number(&parse_input(&["root: a + d", "a: b * c", "b: 5", "c: 0", "d: 1"]).unwrap(), "root", "b"));
+        let monkeys = parse_input(&["root: a + b", "a: b * b", "b: root - a"]).unwrap();
+        assert_eq!(Err(Error::Cycle("root".to_string())), root_number(&monkeys, "root"));
+        assert_eq!(Err(Error::Cycle("root".to_string())), human_number(&parse_input(&["root: root + root"]).unwrap(), "root", "humn"));
+        assert_eq!(Err(Error::UnknownMonkey("b".to_string())), root_number(&parse_input(&["root: a + b", "a: 1"]).unwrap(), "root"));
+        let monkeys = parse_input(&["root: a * a", "a: 9223372036854775807"]).unwrap();
+        assert_eq!(Err(Error::Overflow("root".to_string())), root_number(&monkeys, "root"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input(&["root: pppw + sjmn", "pppw: cczh % lfqf"]).unwrap_err();
+        assert_eq!((2, 12, 1), (error.line, error.column, error.len));
+        assert_eq!("expected \": \"", parse_input(&["root pppw"]).unwrap_err().message);
+        assert_eq!(7, parse_input(&["dbpl: 5x"]).unwrap_err().column);
+        let error = parse_input(&["dbpl: 5", "root: dbpl + dbpl", "dbpl: 7"]).unwrap_err();
+        assert_eq!((3, 1, 4, "monkey dbpl is defined twice"), (error.line, error.column, error.len, error.message.as_str()));
     }
 }
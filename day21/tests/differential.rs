@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use aoc_common::Solution;
+use day21::Day21;
+
+fn jobs(lines: &[String]) -> HashMap<&str, Vec<&str>> {
+    lines.iter().map(|line| {
+        let (name, job) = line.split_once(": ").unwrap();
+        (name, job.split(' ').collect())
+    }).collect()
+}
+
+/// Evaluates the tree with `humn` yelling `human`, `None` when a division is not exact.
+fn yell(jobs: &HashMap<&str, Vec<&str>>, name: &str, human: i128) -> Option<i128> {
+    if name == "humn" {
+        return Some(human);
+    }
+    match jobs[name][..] {
+        [number] => Some(number.parse().unwrap()),
+        [left, op, right] => {
+            let (a, b) = (yell(jobs, left, human)?, yell(jobs, right, human)?);
+            match op {
+                "+" => Some(a + b),
+                "-" => Some(a - b),
+                "*" => Some(a * b),
+                _ => (b != 0 && a % b == 0).then(|| a / b),
+            }
+        },
+        _ => panic!("unexpected job of {}", name),
+    }
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day21::input, |lines| Day21::solve_part1(lines).ok(), |lines| {
+        let jobs = jobs(lines);
+        yell(&jobs, "root", jobs["humn"][0].parse().unwrap()).map(|x| x as i64)
+    });
+}
+
+/// Tries every number until both sides of `root` are equal.
+#[test]
+fn part2() {
+    aoc_gen::check(1000, aoc_gen::day21::input, |lines| Day21::solve_part2(lines).ok(), |lines| {
+        let jobs = jobs(lines);
+        let (left, right) = (jobs["root"][0], jobs["root"][2]);
+        (0..=5000).find(|&human| {
+            let (a, b) = (yell(&jobs, left, human), yell(&jobs, right, human));
+            a.is_some() && a == b
+        }).map(|x| x as i64)
+    });
+}
@@ -0,0 +1,33 @@
+//! Day 22: Monkey Map.
+
+mod task;
+mod wrapper;
+
+use aoc_common::Solution;
+
+pub use task::{parse_input, password, Error, Field, Notes, Path};
+
+/// Final password on the flat map (part 1); the cube (part 2) is not solved.
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = task::Notes;
+    type Answer1 = i32;
+    type Answer2 = i32;
+    type Error = task::Error;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_input(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        task::password(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        task::task2(input)
+    }
+}
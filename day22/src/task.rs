@@ -1,109 +1,98 @@
-use std::str::FromStr;
-use lazy_static::lazy_static;
-use regex::Regex;
+use aoc_common::grid::Grid;
+use aoc_common::parse::{Line, ParseError};
+use aoc_common::Direction;
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    #[error("Internal error")]
-    #[allow(unused)]
-    Internal,
-    #[error("Parse field error")]
-    ParseField(u8),
-    #[error("Parse grid error")]
-    ParseGrid,
-    #[error("Parse path error")]
-    ParsePath,
-    #[error("Wrong grid format")]
-    WrongGridFormat
+    #[error("Not solved yet")]
+    Unsolved,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Tile of the board, `Empty` is outside of the map.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Field {
+pub enum Field {
     Empty,
     Space,
     Wall,
 }
 
-impl TryFrom<u8> for Field {
-    type Error = Error;
-    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+impl Field {
+    fn parse(value: u8) -> Option<Self> {
         match value {
-            b' ' => Ok(Field::Empty),
-            b'.' => Ok(Field::Space),
-            b'#' => Ok(Field::Wall),
-            v => Err(Error::ParseField(v))
+            b' ' => Some(Field::Empty),
+            b'.' => Some(Field::Space),
+            b'#' => Some(Field::Wall),
+            _ => None
         }
     }
 }
 
+/// Step of the path: walk forward or turn.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Path {
+pub enum Path {
     Forward(usize),
     Left,
     Right,
 }
 
-impl FromStr for Path {
-    type Err = Error;
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "L" => Ok(Path::Left),
-            "R" => Ok(Path::Right),
-            ns => ns.parse().map(|n| Path::Forward(n)).map_err(|_| Error::ParsePath)
-        }
-    }
-}
+/// The board and the path to follow on it.
+pub type Notes = (Grid<Field>, Vec<Path>);
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Left,
-    Right,
-    Down,
-}
-
-impl From<Direction> for i32 {
-    fn from(d: Direction) -> Self {
-        use Direction::*;
-        match d {
-            Right => 0,
-            Down => 1,
-            Left => 2,
-            Up => 3,
-        }
+fn facing(d: Direction) -> i32 {
+    match d {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
     }
 }
 
-fn parse_path(path: &str) -> Result<Vec<Path>> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"(\d+)|(L)|(R)").unwrap();
+fn parse_path(line: Line) -> std::result::Result<Vec<Path>, ParseError> {
+    let mut path = Vec::new();
+    let mut rest = line.text;
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            'L' => { path.push(Path::Left); 1 },
+            'R' => { path.push(Path::Right); 1 },
+            '0'..='9' => {
+                let len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+                path.push(Path::Forward(line.parse(&rest[..len])?));
+                len
+            },
+            c => return Err(line.error(&rest[..c.len_utf8()], format!("unexpected {:?}, expected a number, L or R", c))),
+        };
+        rest = &rest[len..];
     }
-    let result: Option<Vec<Path>> = RE.find_iter(path).map(|part| part.as_str().parse().ok()).collect();
-    result.ok_or(Error::ParsePath)
+    Ok(path)
 }
 
-fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<(Vec<Vec<Field>>, Vec<Path>)> {
-    let grid_height = lines.iter().position(|s| s.as_ref().is_empty()).ok_or(Error::WrongGridFormat)?;
-    let max_width = lines.iter().take(grid_height).map(|s| s.as_ref().len()).max().unwrap();
-    
-    let grid: Option<Vec<Vec<Field>>> = lines.iter().take(grid_height).map(|row| {
-        row.as_ref()
-        .bytes()
-        .map(|b| b.try_into().ok())
-        .chain(std::iter::repeat(Some(Field::Empty)))
-        .take(max_width)
-        .collect()
-    }).collect();
-    Ok((grid.ok_or(Error::ParseGrid)?, parse_path(lines.last().unwrap().as_ref())?))
+/// The board, an empty line and the path.
+pub fn parse_input<S: AsRef<str>>(lines: &[S]) -> std::result::Result<Notes, ParseError> {
+    let grid_height = lines.iter().position(|s| s.as_ref().is_empty())
+        .ok_or_else(|| ParseError::eof(lines.len(), "expected an empty line after the board"))?;
+    let grid = Grid::parse_padded(&lines[..grid_height], Field::Empty, Field::parse)?;
+    if !grid.rows().next().is_some_and(|row| row.contains(&Field::Space)) {
+        return Err(ParseError::at(0, lines[0].as_ref(), "", "expected an open tile in the first row"));
+    }
+    let path = lines.get(grid_height + 1).ok_or_else(|| ParseError::eof(grid_height + 1, "expected the path"))?;
+    if let Some(extra) = lines.get(grid_height + 2) {
+        let extra = extra.as_ref();
+        return Err(ParseError::at(grid_height + 2, extra, extra, "unexpected line after the path"));
+    }
+    parse_path(Line::new(grid_height + 1, path.as_ref()))
+        .map(|path| (grid, path))
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<i32> {
-    fn move_to(grid: &Vec<Vec<Field>>, position: (usize, usize), dir: Direction) -> Option<(usize, usize)> {
+/// Final password when walking off an edge wraps around to the other side of the row or column.
+pub fn password((grid, path): &Notes) -> Result<i32> {
+    fn move_to(grid: &Grid<Field>, position: (usize, usize), dir: Direction) -> Option<(usize, usize)> {
         use crate::wrapper::*;
-        let n = grid.len();
-        let m = grid[0].len();
+        let n = grid.height();
+        let m = grid.width();
         let (mut wi, mut wj) = (Wrapper(position.0), Wrapper(position.1));
         loop {
             match dir {
@@ -121,52 +110,37 @@ pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<i32> {
                 }
             }
             let (i, j) = (wi.0, wj.0);
-            match grid[i][j] {
+            match grid[(i, j)] {
                 Field::Wall => return None,
                 Field::Space => return Some((i, j)),
                 _ => {} // continue
             }
         }
     }
-    let (grid, path) = parse_input(lines)?;
     let mut dir = Direction::Right;
-    let mut position = (0usize, grid[0].iter().position(|f| *f == Field::Space).unwrap());
+    let mut position = (0usize, grid.row(0).iter().position(|f| *f == Field::Space).unwrap());
     for p in path {
         match p {
             Path::Forward(n) => {
-                for _ in 0..n {
-                    if let Some(next_position) = move_to(&grid, position, dir) {
+                for _ in 0..*n {
+                    if let Some(next_position) = move_to(grid, position, dir) {
                         position = next_position;
                     } else {
                         break;
                     }
                 }
             },
-            Path::Left => {
-                dir = match dir {
-                    Direction::Right => Direction::Up,
-                    Direction::Up => Direction::Left,
-                    Direction::Left => Direction::Down,
-                    Direction::Down => Direction::Right,
-                };
-            },
-            Path::Right => {
-                dir = match dir {
-                    Direction::Right => Direction::Down,
-                    Direction::Down => Direction::Left,
-                    Direction::Left => Direction::Up,
-                    Direction::Up => Direction::Right,
-                };
-            }
+            Path::Left => dir = dir.turn_left(),
+            Path::Right => dir = dir.turn_right(),
         }
     }
-    let res = (position.0 as i32 + 1) * 1000 + (position.1 as i32 + 1) * 4 + i32::from(dir);
+    let res = (position.0 as i32 + 1) * 1000 + (position.1 as i32 + 1) * 4 + facing(dir);
     Ok(res)
 }
 
-#[allow(dead_code)]
-pub fn task2<S: AsRef<str>>(_lines: &[S]) -> Result<i32> {
-    Ok(0)
+/// The cube folding is not solved.
+pub fn task2(_notes: &Notes) -> Result<i32> {
+    Err(Error::Unsolved)
 }
 
 #[cfg(test)]
@@ -191,20 +165,29 @@ mod tests {
     #[test]
     fn test1() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(6032), task1(&lines));
+        assert_eq!(Ok(6032), password(&parse_input(&lines).unwrap()));
     }
 
     #[test]
     fn test2() {
-        let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok(0), task2(&lines));
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        assert_eq!(Err(Error::Unsolved), task2(&parse_input(&lines).unwrap()));
     }
 
     #[test]
     fn test_parse_path() {
         use Path::*;
         let path = "10R5LR10L4RL5";
-        let result = parse_path(path);
+        let result = parse_path(Line::new(0, path));
         assert_eq![Ok(vec![Forward(10), Right, Forward(5), Left, Right, Forward(10), Left, Forward(4), Right, Left, Forward(5)]), result]
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input(&["..#", "", "10R5X"]).unwrap_err();
+        assert_eq!((3, 5, 1), (error.line, error.column, error.len));
+        let error = parse_input(&["..#", ".x.", "", "10"]).unwrap_err();
+        assert_eq!((2, 2, "unexpected 'x'"), (error.line, error.column, error.message.as_str()));
+        assert_eq!("line 3: expected the path", parse_input(&["..#", ""]).unwrap_err().to_string());
+    }
 }
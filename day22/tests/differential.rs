@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use aoc_common::Solution;
+use day22::Day22;
+
+/// Walks tile by tile; walking off the board goes back along the row or column to its other end.
+fn reference(lines: &[String]) -> i32 {
+    let split = lines.iter().position(|line| line.is_empty()).unwrap();
+    let board = lines[..split].iter().enumerate()
+        .flat_map(|(row, line)| line.chars().enumerate().filter(|&(_, c)| c != ' ').map(move |(col, c)| ((row as i32, col as i32), c)))
+        .collect::<HashMap<_, _>>();
+    let deltas = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+    let mut position = (0, lines[0].find('.').unwrap() as i32);
+    let mut facing = 0;
+    let path = &lines[split + 1];
+    let mut steps = String::new();
+    for c in path.chars().chain(std::iter::once('.')) {
+        if c.is_ascii_digit() {
+            steps.push(c);
+            continue;
+        }
+        for _ in 0..steps.parse::<u32>().unwrap() {
+            let (dr, dc) = deltas[facing];
+            let mut next = (position.0 + dr, position.1 + dc);
+            if !board.contains_key(&next) {
+                next = position;
+                while board.contains_key(&(next.0 - dr, next.1 - dc)) {
+                    next = (next.0 - dr, next.1 - dc);
+                }
+            }
+            if board[&next] == '#' {
+                break;
+            }
+            position = next;
+        }
+        steps.clear();
+        match c {
+            'R' => facing = (facing + 1) % 4,
+            'L' => facing = (facing + 3) % 4,
+            _ => {},
+        }
+    }
+    1000 * (position.0 + 1) + 4 * (position.1 + 1) + facing as i32
+}
+
+/// The cube of part 2 is not solved, only part 1 is compared.
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day22::input, |lines| Day22::solve_part1(lines).ok(), |lines| Some(reference(lines)));
+}
@@ -0,0 +1,48 @@
+//! Day 23: Unstable Diffusion. Elves spread out over the ground.
+
+mod task;
+
+use aoc_common::{params, Part, Session, Solution, Stepper};
+
+pub use task::{empty_ground, first_still_round, parse_input, Error, Field, Ground};
+
+params! {
+    pub struct Rounds {
+        /// Rounds before the empty ground is counted in part 1.
+        rounds: usize = 10,
+    }
+}
+
+/// Empty ground after 10 rounds (part 1) and the first round without moves (part 2).
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = aoc_common::Grid<task::Field>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+    type Error = task::Error;
+    type Params = Rounds;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_input(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Rounds::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        task::first_still_round(input)
+    }
+
+    fn part1_with(input: &Self::Input, params: &Rounds) -> Result<Self::Answer1, Self::Error> {
+        task::empty_ground(input, params.rounds)
+    }
+
+    /// One step per round, part 1 stops after `rounds` rounds.
+    fn simulation(input: &Self::Input, part: Part, params: &Rounds) -> Result<Option<Box<dyn Session>>, Self::Error> {
+        Ok(Some(Box::new(Stepper::new(task::Ground::new(input, (part == Part::One).then_some(params.rounds))))))
+    }
+}
@@ -1,7 +1,11 @@
 use std::convert::TryFrom;
 use std::fmt::Display;
 use std::mem::swap;
-use std::iter;
+
+use aoc_common::geometry::{Compass, Direction};
+use aoc_common::grid::Grid;
+use aoc_common::render::{palette, Cell, Render, Rgb};
+use aoc_common::{ParseError, Simulation};
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
@@ -9,10 +13,13 @@ pub enum Error {
     Internal,
     #[error("Position error")]
     Position,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
+/// Ground tile, `Occupied` by an elf.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Field {
+pub enum Field {
     Empty,
     Occupied
 }
@@ -37,126 +44,84 @@ impl Display for Field {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    North,
-    South,
-    West,
-    East
-}
-
-type Grid = Vec<Vec<Field>>;
-macro_rules! n {
-    ($grid: expr) => {
-        $grid.len()
-    };
-}
-macro_rules! m {
-    ($grid: expr) => {
-        $grid[0].len()
-    };
-}
-
-fn parse_input<S: AsRef<str>>(lines: &[S]) -> Grid {
-    let n = lines.len();
-    let m = lines[0].as_ref().len();
-    let grid_iter = lines.iter().map(|row|
-        iter::repeat(Field::Empty).take(m)
-        .chain(row.as_ref().bytes().map(|b| b.try_into().expect("Expect . or #")))
-        .chain(iter::repeat(Field::Empty).take(m))
-        .collect()
-    );
-    iter::repeat(vec![Field::Empty; 3*m]).take(n).chain(grid_iter).chain(iter::repeat(vec![Field::Empty; 3*m]).take(n)).collect()
-}
-
-fn range(x: usize, n: usize) -> (usize, usize) {
-    ((x as i32 - 1).max(0) as usize, (x + 1).min(n - 1))
-}
-
-fn try_direction(grid: &Grid, d: Direction, i: usize, j: usize) -> bool {
-    let n = n!(grid);
-    let m = m!(grid);
-    let (i_from, i_to) = range(i, n);
-    let (j_from, j_to) = range(j, m);
-    match d {
-        Direction::North => {
-            i > 0 && grid[i - 1][j_from..=j_to].iter().all(|f| *f == Field::Empty)
-        },
-        Direction::South => {
-            i + 1 < n && grid[i + 1][j_from..=j_to].iter().all(|f| *f == Field::Empty)
-        },
-        Direction::West => {
-            j > 0 && grid[i_from..=i_to].iter().all(|f| f[j - 1] == Field::Empty)
-        },
-        Direction::East => {
-            j + 1 < m && grid[i_from..=i_to].iter().all(|f| f[j + 1] == Field::Empty)
+impl Cell for Field {
+    fn glyph(&self) -> char {
+        match self {
+            Field::Empty => '.',
+            Field::Occupied => '#',
         }
     }
-}
 
-fn is_alone(grid: &Grid, ii: usize, jj: usize) -> bool {
-    let (i_from, i_to) = range(ii, n!(grid));
-    let (j_from, j_to) = range(jj, m!(grid));
-    for i in i_from..=i_to {
-        for j in j_from..=j_to {
-            if i == ii && j == jj {
-                continue;
-            }
-            if grid[i][j] == Field::Occupied {
-                return false;
-            }
+    fn colour(&self) -> Rgb {
+        match self {
+            Field::Empty => palette::GROUND,
+            Field::Occupied => palette::ELF,
         }
     }
-    return true;
 }
 
-fn try_move(grid: &Grid, proposal: &Vec<Vec<Option<Direction>>>, d: Direction, ii: usize, jj: usize) -> Option<(usize, usize)> {
-    let (i, j) = match d {
-        Direction::North => (ii - 1, jj),
-        Direction::South => (ii + 1, jj),
-        Direction::East => (ii, jj + 1),
-        Direction::West => (ii, jj - 1),
-    };
-    let n = n!(grid);
-    let m = m!(grid);
-    if (i > 0 && i - 1 != ii && proposal[i - 1][j].map(|d| d == Direction::South).unwrap_or(false)) ||
-        (i + 1 < n && i + 1 != ii && proposal[i + 1][j].map(|d| d == Direction::North).unwrap_or(false)) ||
-        (j > 0 && j - 1 != jj && proposal[i][j - 1].map(|d| d == Direction::East).unwrap_or(false)) ||
-        (j + 1 < m && j + 1 != jj && proposal[i][j + 1].map(|d| d == Direction::West).unwrap_or(false))
-    {
-        None
-    } else {
-        Some((i, j))
+/// The scan is surrounded by an empty margin of its own size on every side.
+pub fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<Grid<Field>, ParseError> {
+    Ok(pad(&Grid::parse(lines, |b| b.try_into().ok())?))
+}
+
+/// Surrounds `scan` with an empty margin of its own size.
+fn pad(scan: &Grid<Field>) -> Grid<Field> {
+    let (n, m) = (scan.height(), scan.width());
+    let mut grid = Grid::new(3 * n, 3 * m, Field::Empty);
+    for ((i, j), &field) in scan.iter() {
+        grid[(n + i, m + j)] = field;
     }
+    grid
+}
+
+/// Elves on the border could walk out of the grid, `play` pads it first.
+fn on_border(grid: &Grid<Field>) -> bool {
+    let (n, m) = (grid.height(), grid.width());
+    grid.iter().any(|((i, j), &field)| field == Field::Occupied && (i == 0 || j == 0 || i + 1 == n || j + 1 == m))
+}
 
+/// The target cell and both diagonal cells next to it have to be free.
+fn try_direction(grid: &Grid<Field>, d: Direction, i: usize, j: usize) -> bool {
+    let Some(target) = grid.step((i, j), d) else { return false };
+    let ahead = Compass::from(d);
+    grid[target] == Field::Empty && [ahead.rotate_left(), ahead.rotate_right()].into_iter().all(
+        |c| grid.offset((i, j), c.delta()).is_none_or(|pos| grid[pos] == Field::Empty)
+    )
 }
 
-#[allow(unused)]
-fn print_grid(grid: &Grid) {
-    grid.iter().for_each(|row| {
-        row.iter().for_each(|f| { print!("{}", f); });
-        println!();
+fn is_alone(grid: &Grid<Field>, i: usize, j: usize) -> bool {
+    grid.neighbours8((i, j)).all(|pos| grid[pos] == Field::Empty)
+}
+
+/// The move fails when another elf proposes the same target, i.e. a neighbour of the target looks at it.
+fn try_move(grid: &Grid<Field>, proposal: &Grid<Option<Direction>>, d: Direction, i: usize, j: usize) -> Option<(usize, usize)> {
+    let target = grid.step((i, j), d)?;
+    let contested = Direction::ALL.into_iter().any(|side| {
+        grid.step(target, side).is_some_and(|from| from != (i, j) && proposal[from] == Some(side.opposite()))
     });
-    println!();
+    (!contested).then_some(target)
 }
 
-fn play(grid: &mut Grid, round: usize) -> bool {
-    let directions = [Direction::North, Direction::South, Direction::West, Direction::East];
-    let n = n!(grid);
-    let m = m!(grid);
-    let mut tmp_grid = vec![vec![Field::Empty; m]; n];
-    let mut proposal: Vec<Vec<Option<Direction>>> = vec![vec![None; m]; n];
+fn play(grid: &mut Grid<Field>, round: usize) -> bool {
+    let directions = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+    if on_border(grid) {
+        *grid = pad(grid);
+    }
+    let n = grid.height();
+    let m = grid.width();
+    let mut tmp_grid = Grid::new(n, m, Field::Empty);
+    let mut proposal = Grid::new(n, m, None);
     let mut moves = false;
     for i in 0..n {
         for j in 0..m {
-            match grid[i][j] {
+            match grid[(i, j)] {
                 Field::Occupied => {
-                    if !is_alone(&grid, i, j) {
+                    if !is_alone(grid, i, j) {
                         let next_direction = directions.iter().cycle().skip(round).take(4).find(
                             |&&d| try_direction(grid, d, i, j)
                         ).copied();
-                        proposal[i][j] = next_direction;
-                        moves = true;
+                        proposal[(i, j)] = next_direction;
                     }
                 },
                 Field::Empty => ()
@@ -165,16 +130,17 @@ fn play(grid: &mut Grid, round: usize) -> bool {
     }
     for i in 0..n {
         for j in 0..m {
-            match grid[i][j] {
+            match grid[(i, j)] {
                 Field::Occupied => {
-                    if let Some(prop) = proposal[i][j] {
-                        if let Some((pi, pj)) = try_move(grid, &proposal, prop, i, j) {
-                            tmp_grid[pi][pj] = Field::Occupied;
+                    if let Some(prop) = proposal[(i, j)] {
+                        if let Some(pos) = try_move(grid, &proposal, prop, i, j) {
+                            tmp_grid[pos] = Field::Occupied;
+                            moves = true;
                         } else {
-                            tmp_grid[i][j] = Field::Occupied;
+                            tmp_grid[(i, j)] = Field::Occupied;
                         }
                     } else {
-                        tmp_grid[i][j] = Field::Occupied;
+                        tmp_grid[(i, j)] = Field::Occupied;
                     }
                 },
                 Field::Empty => ()
@@ -185,26 +151,32 @@ fn play(grid: &mut Grid, round: usize) -> bool {
     moves
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<u32, Error> {
-    let mut grid = parse_input(lines);
-    let n = n!(grid);
-    let m = m!(grid);
-    for r in 0..10 {
+/// Empty ground tiles in the bounding rectangle of the elves after `rounds` rounds.
+pub fn empty_ground(grid: &Grid<Field>, rounds: usize) -> Result<u32, Error> {
+    let mut grid = grid.clone();
+    for r in 0..rounds {
         play(&mut grid, r);
     }
+    empty_in_bounds(&grid)
+}
+
+fn empty_in_bounds(grid: &Grid<Field>) -> Result<u32, Error> {
+    let n = grid.height();
+    let m = grid.width();
 
-    let i_from = grid.iter().position(|row| row.iter().any(|&f| f == Field::Occupied)).ok_or(Error::Position)?;
-    let i_to = n - grid.iter().rev().position(|row| row.iter().any(|&f| f == Field::Occupied)).ok_or(Error::Position)?;
+    let i_from = grid.rows().position(|row| row.contains(&Field::Occupied)).ok_or(Error::Position)?;
+    let i_to = n - grid.rows().rev().position(|row| row.contains(&Field::Occupied)).ok_or(Error::Position)?;
 
-    let j_from = (0..m).position(|i| grid.iter().any(|row| row[i] == Field::Occupied)).ok_or(Error::Position)?;
-    let j_to = m - (0..m).rev().position(|i| grid.iter().any(|row| row[i] == Field::Occupied)).ok_or(Error::Position)?;
-    Ok(grid.into_iter().skip(i_from).take(i_to - i_from).map(
-        |row| row.into_iter().skip(j_from).take(j_to - j_from).filter(|&f| f == Field::Empty).count()
+    let j_from = (0..m).position(|j| grid.column(j).any(|&f| f == Field::Occupied)).ok_or(Error::Position)?;
+    let j_to = m - (0..m).rev().position(|j| grid.column(j).any(|&f| f == Field::Occupied)).ok_or(Error::Position)?;
+    Ok(grid.rows().skip(i_from).take(i_to - i_from).map(
+        |row| row[j_from..j_to].iter().filter(|&&f| f == Field::Empty).count()
     ).sum::<usize>() as u32)
 }
 
-pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<usize, Error> {
-    let mut grid = parse_input(lines);
+/// First round where no elf moves.
+pub fn first_still_round(grid: &Grid<Field>) -> Result<usize, Error> {
+    let mut grid = grid.clone();
     for r in 0.. {
         if !play(&mut grid, r) {
             return Ok(r + 1);
@@ -213,6 +185,46 @@ pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<usize, Error> {
     Ok(0)
 }
 
+/// The elves spreading out, one step per round.
+#[derive(Debug, Clone)]
+pub struct Ground {
+    grid: Grid<Field>,
+    round: usize,
+    /// Rounds to play, all rounds until no elf moves when `None`.
+    rounds: Option<usize>,
+    still: bool,
+}
+
+impl Ground {
+    pub fn new(grid: &Grid<Field>, rounds: Option<usize>) -> Self {
+        Self { grid: grid.clone(), round: 0, rounds, still: false }
+    }
+}
+
+impl Simulation for Ground {
+    fn step(&mut self) -> bool {
+        if self.still || Some(self.round) == self.rounds {
+            return false;
+        }
+        self.still = !play(&mut self.grid, self.round);
+        self.round += 1;
+        true
+    }
+
+    fn status(&self) -> Vec<(&'static str, i64)> {
+        let empty = empty_in_bounds(&self.grid).unwrap_or_default();
+        vec![("round", self.round as i64), ("empty", empty as i64), ("still", self.still as i64)]
+    }
+}
+
+impl Render for Ground {
+    type Cell = Field;
+
+    fn render(&self) -> Grid<Field> {
+        self.grid.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,12 +245,54 @@ mod tests {
     #[test]
     fn test1() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(110), task1(&lines));
+        assert_eq!(Ok(110), empty_ground(&parse_input(&lines).unwrap(), 10));
     }
 
     #[test]
     fn test2() {
-        let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok(20), task2(&lines));
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        assert_eq!(Ok(20), first_still_round(&parse_input(&lines).unwrap()));
+    }
+
+    #[test]
+    fn test_margin() {
+        let grid = parse_input(&["#"]).unwrap();
+        assert_eq!((3, 3, Field::Occupied), (grid.height(), grid.width(), grid[(1, 1)]));
+        assert!(!on_border(&grid));
+        let mut edge = Grid::new(3, 3, Field::Empty);
+        edge[(0, 1)] = Field::Occupied;
+        assert!(on_border(&edge));
+        assert_eq!((9, 9, Field::Occupied), (pad(&edge).height(), pad(&edge).width(), pad(&edge)[(3, 4)]));
+        // the elves spread further than the first margin, on a grid that is never padded
+        // the outer ones would be stuck on the border
+        let grid = parse_input(&["#", "#", "#", "#"]).unwrap();
+        assert_eq!(Ok(4), first_still_round(&grid));
+    }
+
+    #[test]
+    fn test_spreading() {
+        assert_eq!(Ok(20), empty_ground(&parse_input(&["##.#.#"]).unwrap(), 10));
+        assert_eq!(Ok(2), empty_ground(&parse_input(&["##.#.#"]).unwrap(), 0));
+    }
+
+    #[test]
+    fn test_ground() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let mut ground = Ground::new(&parse_input(&lines).unwrap(), Some(10));
+        while ground.step() {}
+        assert_eq!(vec![("round", 10), ("empty", 110), ("still", 0)], ground.status());
+    }
+
+    #[test]
+    fn test_record() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let grid = parse_input(&lines).unwrap();
+        let dir = std::env::temp_dir().join(format!("day23-frames-{}", std::process::id()));
+        let mut sink = aoc_common::FrameSink::new(&dir, aoc_common::render::FrameFormat::Ppm).unwrap().with_every(5);
+        assert_eq!(20, aoc_common::simulation::record(&mut Ground::new(&grid, None), &mut sink));
+        assert_eq!(5, sink.finish().unwrap());
+        let image = std::fs::read(dir.join("frame_00004.ppm")).unwrap();
+        assert!(image.starts_with(format!("P6\n{} {}\n255\n", 4 * grid.width(), 4 * grid.height()).as_bytes()));
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
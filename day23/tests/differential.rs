@@ -0,0 +1,69 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::Solution;
+use day23::Day23;
+
+type Offset = (i32, i32);
+
+const NEIGHBOURS: [Offset; 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+/// North, south, west and east with the three cells checked for each.
+const PROPOSALS: [(Offset, [Offset; 3]); 4] = [
+    ((-1, 0), [(-1, -1), (-1, 0), (-1, 1)]),
+    ((1, 0), [(1, -1), (1, 0), (1, 1)]),
+    ((0, -1), [(-1, -1), (0, -1), (1, -1)]),
+    ((0, 1), [(-1, 1), (0, 1), (1, 1)]),
+];
+
+fn elves(lines: &[String]) -> HashSet<(i32, i32)> {
+    lines.iter().enumerate()
+        .flat_map(|(row, line)| line.char_indices().filter(|&(_, c)| c == '#').map(move |(col, _)| (row as i32, col as i32)))
+        .collect()
+}
+
+/// One round with the proposals starting at `first`, whether any elf moved.
+fn round(elves: &mut HashSet<(i32, i32)>, first: usize) -> bool {
+    let occupied = |elves: &HashSet<(i32, i32)>, (r, c): (i32, i32), (dr, dc): (i32, i32)| elves.contains(&(r + dr, c + dc));
+    let mut proposed = HashMap::new();
+    for &elf in elves.iter() {
+        if NEIGHBOURS.iter().all(|&d| !occupied(elves, elf, d)) {
+            continue;
+        }
+        if let Some(&((dr, dc), _)) = (0..4).map(|k| &PROPOSALS[(first + k) % 4]).find(|(_, checks)| checks.iter().all(|&d| !occupied(elves, elf, d))) {
+            proposed.insert(elf, (elf.0 + dr, elf.1 + dc));
+        }
+    }
+    let mut counts = HashMap::new();
+    for target in proposed.values() {
+        *counts.entry(*target).or_insert(0) += 1;
+    }
+    let mut moved = false;
+    for (elf, target) in proposed {
+        if counts[&target] == 1 {
+            elves.remove(&elf);
+            elves.insert(target);
+            moved = true;
+        }
+    }
+    moved
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day23::input, |lines| Day23::solve_part1(lines).ok(), |lines| {
+        let mut elves = elves(lines);
+        for first in 0..10 {
+            round(&mut elves, first % 4);
+        }
+        let rows = elves.iter().map(|e| e.0).max().unwrap() - elves.iter().map(|e| e.0).min().unwrap() + 1;
+        let cols = elves.iter().map(|e| e.1).max().unwrap() - elves.iter().map(|e| e.1).min().unwrap() + 1;
+        Some((rows * cols) as u32 - elves.len() as u32)
+    });
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day23::input, |lines| Day23::solve_part2(lines).ok(), |lines| {
+        let mut elves = elves(lines);
+        (0..).find(|&first| !round(&mut elves, first % 4)).map(|first| first + 1)
+    });
+}
@@ -0,0 +1,38 @@
+//! Day 24: Blizzard Basin.
+
+mod task;
+
+use aoc_common::{Part, Session, Solution, Stepper};
+
+pub use task::{fewest_minutes, fewest_minutes_with_snacks, parse_input, Error, Expedition, Field, Tile};
+
+/// Minutes to cross the valley (part 1) and to cross it three times (part 2).
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = aoc_common::Grid<task::Field>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = task::Error;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_input(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        task::fewest_minutes(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        task::fewest_minutes_with_snacks(input)
+    }
+
+    /// One step per minute, part 2 crosses the valley three times.
+    fn simulation(input: &Self::Input, part: Part, _params: &Self::Params) -> Result<Option<Box<dyn Session>>, Self::Error> {
+        let trips = if part == Part::One { 1 } else { 3 };
+        Ok(Some(Box::new(Stepper::new(task::Expedition::new(input, trips)))))
+    }
+}
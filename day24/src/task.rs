@@ -1,7 +1,12 @@
+use aoc_common::grid::{Grid, Pos};
+use aoc_common::render::{palette, Cell, Render, Rgb};
+use aoc_common::{ParseError, Simulation};
+use aoc_common::search;
 use bitflags::bitflags;
-use std::collections::{VecDeque, HashSet};
+use std::collections::HashSet;
 use std::fmt::Display;
-use std::mem::swap;
+use std::iter;
+use std::rc::Rc;
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
@@ -10,10 +15,13 @@ pub enum Error {
     Internal,
     #[error("EndLoop")]
     EndLoop,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
 }
 
 bitflags! {
-    struct Field: u8 {
+    /// Content of a tile: a wall or any set of blizzards.
+    pub struct Field: u8 {
         const EMPTY     = 0;
         const LEFT      = 1 << 0;
         const RIGHT     = 1 << 1;
@@ -23,12 +31,6 @@ bitflags! {
     }
 }
 
-impl Field {
-    fn clear(&mut self) {
-        self.bits = 0;
-    }
-}
-
 impl TryFrom<u8> for Field {
     type Error = Error;
     fn try_from(value: u8) -> Result<Self, Self::Error> {
@@ -64,121 +66,201 @@ impl Display for Field {
     }
 }
 
-type Grid = Vec<Vec<Field>>;
-
-fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<Grid, Error> {
-    Ok(lines.iter().map(|row| row.as_ref().bytes().map(|b| Field::try_from(b).unwrap()).collect()).collect())
+/// Frame cell: the content of a tile, or a position the expedition can be at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Field(Field),
+    Expedition,
 }
 
-fn update_state(grid: &mut Grid, tmp_grid: &mut Grid) {
-    let n = grid.len();
-    let m = grid[0].len();
-    tmp_grid[1..n-1].iter_mut().for_each(|row| row[1..m-1].iter_mut().for_each(|f| f.clear()));
-    for (i, row) in grid.iter().enumerate().skip(1).take(n - 2) {
-        for (j, &x) in row.iter().enumerate().skip(1).take(m - 2) {
-            if x.contains(Field::UP) {
-                let ni = if i == 1 { n - 2 } else { i - 1 };
-                tmp_grid[ni][j] |= Field::UP;
-            }
-            if x.contains(Field::DOWN) {
-                let ni = if i == n - 2 { 1 } else { i + 1 };
-                tmp_grid[ni][j] |= Field::DOWN;
-            }
-            if x.contains(Field::LEFT) {
-                let nj = if j == 1 { m - 2 } else { j - 1 };
-                tmp_grid[i][nj] |= Field::LEFT;
-            }
-            if x.contains(Field::RIGHT) {
-                let nj = if j == m - 2 { 1 } else { j + 1 };
-                tmp_grid[i][nj] |= Field::RIGHT;
-            }
+impl Cell for Tile {
+    fn glyph(&self) -> char {
+        match self {
+            Tile::Field(field) => field.to_string().chars().next().unwrap_or('.'),
+            Tile::Expedition => 'E',
         }
     }
-    swap(grid, tmp_grid)
-}
-
-#[allow(unused)]
-fn print_grid(grid: &Grid, x: usize, y: usize) {
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &field) in row.iter().enumerate() {
-            if (i, j) == (x, y) {
-                if field.bits().count_ones() > 0 {
-                    print!("X");
-                } else {
-                    print!("E");
-                }
-            } else {
-                print!("{}", field);
-            }
-        }
-        println!();
-    }
-    println!();
-}
-
-fn play(grid: &mut Grid, start: (usize, usize), finish: (usize, usize)) -> Result<i32, Error> {
-    let mut tmp_grid = grid.clone();
-    let mut q = VecDeque::new();
-    let mut visited = HashSet::new();
-    let (n, m) = (grid.len(), grid[0].len());
-    let mut current_step = -1;
-    q.push_back((start.0, start.1, 0));
-    while let Some((i, j, step)) = q.pop_front() {
-        if step != current_step {
-            update_state(grid, &mut tmp_grid);
-            current_step = step;
-        }
-        let mut visit = |x, y| {
-            visited.insert(((step + 1) % ((n - 2) as i32 * (m - 2) as i32), x, y))
-        };
-        if grid[i][j].is_empty() && visit(i, j) {
-            q.push_back((i, j, step + 1));
+
+    fn colour(&self) -> Rgb {
+        match self {
+            Tile::Expedition => palette::ACTIVE,
+            Tile::Field(field) if field.contains(Field::WALL) => palette::WALL,
+            Tile::Field(field) if field.is_empty() => palette::AIR,
+            Tile::Field(_) => palette::ICE,
         }
-        if i > 0 && grid[i - 1][j].is_empty() && visit(i - 1, j) {
-            if (i - 1, j) == finish {
-                return Ok(step + 1);
-            }
-            q.push_back((i - 1, j, step + 1));
+    }
+}
+
+/// The valley is surrounded by walls, the entrance is the second cell of the top row
+/// and the exit is the second to last cell of the bottom row.
+pub fn parse_input<S: AsRef<str>>(lines: &[S]) -> Result<Grid<Field>, ParseError> {
+    let grid = Grid::parse(lines, |b| Field::try_from(b).ok())?;
+    let (n, m) = (grid.height(), grid.width());
+    if n < 3 || m < 3 {
+        let index = n.saturating_sub(1);
+        return Err(ParseError::at(index, lines.get(index).map_or("", |l| l.as_ref()), "", "the valley must be at least 3x3"));
+    }
+    for (pos, message) in [((0, 1), "expected the entrance"), ((n - 1, m - 2), "expected the exit")] {
+        if grid[pos] != Field::EMPTY {
+            let line = lines[pos.0].as_ref();
+            return Err(ParseError::new(pos.0, line, pos.1..pos.1 + 1, message));
         }
-        if j > 0 && grid[i][j - 1].is_empty() && visit(i, j - 1) {
-            if (i, j - 1) == finish {
-                return Ok(step + 1);
-            }
-            q.push_back((i, j - 1, step + 1));
+    }
+    Ok(grid)
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Blizzards move in straight lines and wrap around, so the inner valley of `height` x `width`
+/// repeats after lcm(height, width) minutes and a cell is checked without simulating the valley.
+struct Valley {
+    grid: Grid<Field>,
+    height: usize,
+    width: usize,
+    period: usize,
+}
+
+impl Valley {
+    fn new(grid: Grid<Field>) -> Self {
+        let (height, width) = (grid.height() - 2, grid.width() - 2);
+        Self { grid, height, width, period: height / gcd(height, width) * width }
+    }
+
+    fn start(&self) -> Pos {
+        (0, 1)
+    }
+
+    fn finish(&self) -> Pos {
+        (self.height + 1, self.width)
+    }
+
+    fn is_free(&self, (i, j): Pos, time: usize) -> bool {
+        if self.grid[(i, j)].contains(Field::WALL) {
+            return false;
         }
-        if i + 1 < n && grid[i + 1][j].is_empty() && visit(i + 1, j) {
-            if (i + 1, j) == finish {
-                return Ok(step + 1);
-            }
-            q.push_back((i + 1, j, step + 1));
+        if i == 0 || i > self.height {
+            return true;
         }
-        if j + 1 < m && grid[i][j + 1].is_empty() && visit(i, j + 1) {
-            if (i, j + 1) == finish {
-                return Ok(step + 1);
+        let (h, w) = (self.height, self.width);
+        let (r, c) = (i - 1, j - 1);
+        !(self.grid[(i, (c + w - time % w) % w + 1)].contains(Field::RIGHT) ||
+            self.grid[(i, (c + time) % w + 1)].contains(Field::LEFT) ||
+            self.grid[((r + h - time % h) % h + 1, j)].contains(Field::DOWN) ||
+            self.grid[((r + time) % h + 1, j)].contains(Field::UP))
+    }
+
+    /// Blizzards at minute `time`.
+    fn at(&self, time: usize) -> Grid<Field> {
+        let (h, w) = (self.height, self.width);
+        let mut grid = self.grid.clone();
+        for i in 1..=h {
+            for j in 1..=w {
+                let (r, c) = (i - 1, j - 1);
+                grid[(i, j)] = self.grid[(i, (c + w - time % w) % w + 1)] & Field::RIGHT |
+                    self.grid[(i, (c + time) % w + 1)] & Field::LEFT |
+                    self.grid[((r + h - time % h) % h + 1, j)] & Field::DOWN |
+                    self.grid[((r + time) % h + 1, j)] & Field::UP;
             }
-            q.push_back((i, j + 1, step + 1));
         }
+        grid
+    }
+
+    /// Minutes needed to get from `start` to `finish` when leaving at minute `time`.
+    fn cross(&self, start: Pos, finish: Pos, time: usize) -> Result<usize, Error> {
+        let path = search::bfs(
+            [(start, time % self.period)],
+            |&(pos, time)| {
+                let time = (time + 1) % self.period;
+                iter::once(pos).chain(self.grid.neighbours4(pos))
+                    .filter(move |&next| self.is_free(next, time))
+                    .map(move |next| (next, time))
+            },
+            |&(pos, _)| pos == finish,
+        );
+        path.map(|path| path.cost).ok_or(Error::EndLoop)
     }
-    Err(Error::EndLoop)
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<i32, Error> {
-    let mut grid = parse_input(lines)?;
-    let n = grid.len();
-    let m = grid[0].len();
-    play(&mut grid, (0, 1), (n - 1, m - 2))
+/// Fewest minutes to reach the exit.
+pub fn fewest_minutes(grid: &Grid<Field>) -> Result<usize, Error> {
+    let valley = Valley::new(grid.clone());
+    valley.cross(valley.start(), valley.finish(), 0)
 }
 
-pub fn task2<S: AsRef<str>>(lines: &[S]) -> Result<i32, Error> {
-    let mut grid = parse_input(lines)?;
-    let n = grid.len();
-    let m = grid[0].len();
-    let a = play(&mut grid, (0, 1), (n - 1, m - 2))?;
-    let b = play(&mut grid, (n - 1, m - 2), (0, 1))?;
-    let c = play(&mut grid, (0, 1), (n - 1, m - 2))?;
+/// Fewest minutes to reach the exit, go back to the entrance and reach the exit again.
+pub fn fewest_minutes_with_snacks(grid: &Grid<Field>) -> Result<usize, Error> {
+    let valley = Valley::new(grid.clone());
+    let a = valley.cross(valley.start(), valley.finish(), 0)?;
+    let b = valley.cross(valley.finish(), valley.start(), a)?;
+    let c = valley.cross(valley.start(), valley.finish(), a + b)?;
     Ok(a + b + c)
 }
 
+/// Every position the expedition can be at, one step per minute, for `trips` crossings back and forth.
+#[derive(Clone)]
+pub struct Expedition {
+    valley: Rc<Valley>,
+    trips: usize,
+    /// Start and goal of the current trip.
+    ends: [Pos; 2],
+    reachable: HashSet<Pos>,
+    time: usize,
+    trip: usize,
+}
+
+impl Expedition {
+    pub fn new(grid: &Grid<Field>, trips: usize) -> Self {
+        let valley = Valley::new(grid.clone());
+        let ends = [valley.start(), valley.finish()];
+        Self { valley: Rc::new(valley), trips, ends, reachable: HashSet::from([ends[0]]), time: 0, trip: 0 }
+    }
+
+    pub fn minutes(&self) -> usize {
+        self.time
+    }
+
+    /// Steps until the last trip is done, `EndLoop` when the expedition gets stuck.
+    pub fn finish(&mut self) -> Result<usize, Error> {
+        while self.step() {}
+        if self.trip < self.trips { Err(Error::EndLoop) } else { Ok(self.time) }
+    }
+}
+
+impl Simulation for Expedition {
+    fn step(&mut self) -> bool {
+        if self.trip == self.trips || self.reachable.is_empty() {
+            return false;
+        }
+        self.time += 1;
+        self.reachable = self.reachable.iter()
+            .flat_map(|&pos| iter::once(pos).chain(self.valley.grid.neighbours4(pos)))
+            .filter(|&next| self.valley.is_free(next, self.time))
+            .collect();
+        if self.reachable.contains(&self.ends[1]) {
+            self.trip += 1;
+            self.ends.reverse();
+            self.reachable = HashSet::from([self.ends[0]]);
+        }
+        true
+    }
+
+    fn status(&self) -> Vec<(&'static str, i64)> {
+        vec![("minute", self.time as i64), ("trip", self.trip as i64), ("positions", self.reachable.len() as i64)]
+    }
+}
+
+impl Render for Expedition {
+    type Cell = Tile;
+
+    fn render(&self) -> Grid<Tile> {
+        let mut frame = self.valley.at(self.time).map(|&field| Tile::Field(field));
+        self.reachable.iter().for_each(|&pos| frame[pos] = Tile::Expedition);
+        frame
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,12 +275,45 @@ mod tests {
     #[test]
     fn test1() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok(18), task1(&lines));
+        assert_eq!(Ok(18), fewest_minutes(&parse_input(&lines).unwrap()));
     }
 
     #[test]
     fn test2() {
-        let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok(54), task2(&lines));
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        assert_eq!(Ok(54), fewest_minutes_with_snacks(&parse_input(&lines).unwrap()));
+    }
+
+    #[test]
+    fn test_blizzards() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let valley = Valley::new(parse_input(&lines).unwrap());
+        let minute1 = "#.######\n#.>3.<.#\n#<..<<.#\n#>2.22.#\n#>v..^<#\n######.#\n";
+        let frame = valley.at(1).map(|&field| Tile::Field(field));
+        assert_eq!(minute1, aoc_common::render::ascii(&frame));
+        assert_eq!(valley.grid, valley.at(valley.period));
+    }
+
+    #[test]
+    fn test_record() {
+        let lines = DATA.split('\n').collect::<Vec<_>>();
+        let grid = parse_input(&lines).unwrap();
+        let dir = std::env::temp_dir().join(format!("day24-frames-{}", std::process::id()));
+        let mut sink = aoc_common::FrameSink::new(&dir, aoc_common::render::FrameFormat::Ascii).unwrap();
+        assert_eq!(18, aoc_common::simulation::record(&mut Expedition::new(&grid, 1), &mut sink));
+        assert_eq!(19, sink.finish().unwrap());
+        let first = std::fs::read_to_string(dir.join("frame_00000.txt")).unwrap();
+        assert!(first.starts_with("#E######\n"));
+        assert_eq!(Ok(54), Expedition::new(&grid, 3).finish());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input(&["#.###", "#.<.#", "###x#"]).unwrap_err();
+        assert_eq!((3, 4, "unexpected 'x'"), (error.line, error.column, error.message.as_str()));
+        let error = parse_input(&["#.###", "#.<.#", "#####"]).unwrap_err();
+        assert_eq!((3, 4, "expected the exit"), (error.line, error.column, error.message.as_str()));
+        assert_eq!("the valley must be at least 3x3", parse_input(&["#.#"]).unwrap_err().message);
     }
 }
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use aoc_common::Solution;
+use day24::Day24;
+
+struct Valley {
+    walls: HashSet<(i32, i32)>,
+    blizzards: Vec<((i32, i32), (i32, i32))>,
+    height: i32,
+    width: i32,
+}
+
+impl Valley {
+    fn parse(lines: &[String]) -> Self {
+        let mut valley = Valley { walls: HashSet::new(), blizzards: Vec::new(), height: lines.len() as i32 - 2, width: lines[0].len() as i32 - 2 };
+        for (r, line) in lines.iter().enumerate() {
+            for (c, tile) in line.chars().enumerate() {
+                let position = (r as i32, c as i32);
+                match tile {
+                    '#' => { valley.walls.insert(position); },
+                    '>' => valley.blizzards.push((position, (0, 1))),
+                    '<' => valley.blizzards.push((position, (0, -1))),
+                    'v' => valley.blizzards.push((position, (1, 0))),
+                    '^' => valley.blizzards.push((position, (-1, 0))),
+                    _ => {},
+                }
+            }
+        }
+        valley
+    }
+
+    /// Moves every blizzard one step, wrapping inside the walls.
+    fn step(&mut self) {
+        for ((r, c), (dr, dc)) in self.blizzards.iter_mut() {
+            *r = (*r - 1 + *dr).rem_euclid(self.height) + 1;
+            *c = (*c - 1 + *dc).rem_euclid(self.width) + 1;
+        }
+    }
+
+    /// Every reachable position minute by minute, until `finish` or a time limit.
+    fn cross(&mut self, start: (i32, i32), finish: (i32, i32), time: &mut usize) -> Option<()> {
+        let limit = *time + (self.height * self.width * (self.height * self.width + 2)) as usize;
+        let mut positions = HashSet::from([start]);
+        while !positions.contains(&finish) {
+            if *time > limit {
+                return None;
+            }
+            self.step();
+            *time += 1;
+            let blizzards = self.blizzards.iter().map(|&(position, _)| position).collect::<HashSet<_>>();
+            positions = positions.iter()
+                .flat_map(|&(r, c)| [(r, c), (r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)])
+                .filter(|&(r, c)| r >= 0 && r <= self.height + 1 && !self.walls.contains(&(r, c)) && !blizzards.contains(&(r, c)))
+                .collect();
+        }
+        Some(())
+    }
+}
+
+fn reference(lines: &[String], trips: usize) -> Option<usize> {
+    let mut valley = Valley::parse(lines);
+    let (entrance, exit) = ((0, 1), (valley.height + 1, valley.width));
+    let mut time = 0;
+    for trip in 0..trips {
+        let (start, finish) = if trip % 2 == 0 { (entrance, exit) } else { (exit, entrance) };
+        valley.cross(start, finish, &mut time)?;
+    }
+    Some(time)
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(1000, aoc_gen::day24::input, |lines| Day24::solve_part1(lines).ok(), |lines| reference(lines, 1));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(1000, aoc_gen::day24::input, |lines| Day24::solve_part2(lines).ok(), |lines| reference(lines, 3));
+}
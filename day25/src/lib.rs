@@ -0,0 +1,33 @@
+//! Day 25: Full of Hot Air. SNAFU numbers are written in balanced base 5 with the digits `=-012`.
+
+mod task;
+mod tools;
+
+use aoc_common::Solution;
+
+pub use task::{decrypt, encrypt, parse_numbers, snafu_sum, Error};
+
+/// Sum of the fuel requirements in SNAFU (part 1).
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<i64>;
+    type Answer1 = String;
+    type Answer2 = String;
+    type Error = task::Error;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(task::parse_numbers(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        task::snafu_sum(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        task::task2(input)
+    }
+}
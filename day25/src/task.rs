@@ -1,37 +1,44 @@
 use super::tools;
+use aoc_common::parse::{self, Line, ParseError};
+
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     #[error("Internal error")]
     #[allow(unused)]
     Internal,
-    #[error("Parse error")]
-    ParseError,
+    #[error(transparent)]
+    Parse(#[from] ParseError),
     #[error("Srialize error")]
     SerializeError(#[from] std::string::FromUtf8Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-fn decrypt(s: &str) -> Result<i64> {
-    let mapped: Option<Vec<i64>> = s.bytes().rev().map(|b| {
-        match b {
-            b'0' => Some(0),
-            b'1' => Some(1),
-            b'2' => Some(2),
-            b'-' => Some(-1),
-            b'=' => Some(-2),
-            _ => None
+/// Value of the SNAFU number on the line.
+pub fn decrypt(line: Line) -> std::result::Result<i64, ParseError> {
+    let mapped = line.text.char_indices().rev().map(|(i, c)| {
+        match c {
+            '0' => Ok(0),
+            '1' => Ok(1),
+            '2' => Ok(2),
+            '-' => Ok(-1),
+            '=' => Ok(-2),
+            c => Err(line.error(&line.text[i..i + c.len_utf8()], format!("unexpected SNAFU digit {:?}", c)))
         }
-    }).collect();
+    }).collect::<std::result::Result<Vec<i64>, _>>()?;
+
+    Ok(mapped.iter().fold((0, 1), |(res, mult), d| {
+        (res + d * mult, mult * 5)
+    }).0)
+}
 
-    mapped.map(|m| 
-        m.iter().fold((0, 1), |(res, mult), d| {
-            (res + d * mult, mult * 5)
-        }).0
-    ).ok_or(Error::ParseError)
+/// SNAFU numbers, one per line.
+pub fn parse_numbers<S: AsRef<str>>(lines: &[S]) -> std::result::Result<Vec<i64>, ParseError> {
+    parse::lines(lines).map(decrypt).collect()
 }
 
-fn encrypt(x: i64) -> Result<String> {
+/// SNAFU representation of a non-negative number.
+pub fn encrypt(x: i64) -> Result<String> {
     if x == 0 {
         return Ok(String::from("0"));
     }
@@ -47,11 +54,12 @@ fn encrypt(x: i64) -> Result<String> {
     Ok(String::from_utf8(result)?)
 }
 
-pub fn task1<S: AsRef<str>>(lines: &[S]) -> Result<String> {
-    encrypt(lines.iter().map(|s| decrypt(s.as_ref()).unwrap()).sum())
+/// Sum of the numbers in SNAFU.
+pub fn snafu_sum(numbers: &[i64]) -> Result<String> {
+    encrypt(numbers.iter().sum())
 }
 
-pub fn task2<S: AsRef<str>>(_lines: &[S]) -> Result<String> {
+pub fn task2(_numbers: &[i64]) -> Result<String> {
     Ok(String::from("ho-ho-ho"))
 }
 
@@ -79,7 +87,7 @@ mod tests {
     #[test]
     fn test_decrypt() {
         for (x, s) in ENC_DATA {
-            assert_eq!(Ok(*x), decrypt(s));
+            assert_eq!(Ok(*x), decrypt(Line::new(0, s)));
         }
     }
 
@@ -93,12 +101,17 @@ mod tests {
     #[test]
     fn test1() {
         let lines = DATA.split('\n').collect::<Vec<_>>();
-        assert_eq!(Ok("2=-1=0".to_string()), task1(&lines));
+        assert_eq!(Ok("2=-1=0".to_string()), snafu_sum(&parse_numbers(&lines).unwrap()));
     }
 
     #[test]
     fn test2() {
-        let lines = DATA.split('\n').map(|s| s.trim()).collect::<Vec<_>>();
-        assert_eq!(Ok("ho-ho-ho".to_string()), task2(&lines));
+        assert_eq!(Ok("ho-ho-ho".to_string()), task2(&[]));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_numbers(&["1=-0-2", "12131"]).unwrap_err();
+        assert_eq!((2, 4, "unexpected SNAFU digit '3'"), (error.line, error.column, error.message.as_str()));
     }
 }
@@ -0,0 +1,27 @@
+use aoc_common::Solution;
+use day25::Day25;
+
+fn decode(snafu: &str) -> i128 {
+    snafu.chars().fold(0, |total, digit| total * 5 + "=-012".find(digit).unwrap() as i128 - 2)
+}
+
+/// Base 5 digits, every digit above 2 borrows from the next one.
+fn encode(mut x: i128) -> String {
+    let mut digits = Vec::new();
+    while x != 0 {
+        let digit = x % 5;
+        digits.push(['0', '1', '2', '=', '-'][digit as usize]);
+        x = x / 5 + (digit > 2) as i128;
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    digits.iter().rev().collect()
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day25::input, |lines| Day25::solve_part1(lines).ok(), |lines| {
+        Some(encode(lines.iter().map(|line| decode(line)).sum()))
+    });
+}
@@ -0,0 +1,140 @@
+//! Day 2: Rock Paper Scissors strategy guide.
+
+use aoc_common::parse::{self, ParseError};
+use aoc_common::Solution;
+
+/// A hand shape.
+#[derive(Debug, Clone, Copy)]
+pub enum Suefa {
+    Rock,
+    Paper,
+    Scissors
+}
+
+/// The opponent's shape and the second column: 0, 1 or 2 for `X`, `Y` or `Z`.
+pub type Round = (Suefa, usize);
+
+/// Rounds of the strategy guide, `A B C` for the opponent and `X Y Z` for the second column.
+pub fn parse_rounds<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Round>, ParseError> {
+    parse::lines(lines).map(|line| {
+        let (player1, player2) = line.split_once(line.text, " ")?;
+        let tool1 = match player1 {
+            "A" => Suefa::Rock,
+            "B" => Suefa::Paper,
+            "C" => Suefa::Scissors,
+            _ => return Err(line.error(player1, "expected A, B or C")),
+        };
+        let column = ["X", "Y", "Z"].iter().position(|&c| c == player2)
+            .ok_or_else(|| line.error(player2, "expected X, Y or Z"))?;
+        Ok((tool1, column))
+    }).collect()
+}
+
+/// Score for playing the shape.
+pub fn tool_score(suefa: Suefa) -> u32 {
+    match suefa {
+        Suefa::Rock => 1,
+        Suefa::Paper => 2,
+        Suefa::Scissors => 3
+    }
+}
+
+/// Outcome of a round for the first player.
+#[derive(Debug, Clone, Copy)]
+pub enum FightResult {
+    Lose,
+    Draw,
+    Win
+}
+
+/// Outcome of `a` played against `b`.
+pub fn fight(a: Suefa, b: Suefa) -> FightResult {
+    use Suefa::*;
+    use FightResult::*;
+    match (a, b) {
+        (Rock, Rock) => Draw,
+        (Rock, Paper) => Lose,
+        (Rock, Scissors) => Win,
+        (Paper, Rock) => Win,
+        (Paper, Paper) => Draw,
+        (Paper, Scissors) => Lose,
+        (Scissors, Rock) => Lose,
+        (Scissors, Paper) => Win,
+        (Scissors, Scissors) => Draw
+    }
+}
+
+/// Score for the outcome of a round.
+pub fn fight_score(fight_result: FightResult) -> u32 {
+    match fight_result {
+        FightResult::Lose => 0,
+        FightResult::Draw => 3,
+        FightResult::Win => 6
+    }
+}
+
+fn calc_score(games: &[Round]) -> u32 {
+    let mut total = 0;
+    for &(tool1, column) in games {
+        let tool2 = [Suefa::Rock, Suefa::Paper, Suefa::Scissors][column];
+        let score = tool_score(tool2) + fight_score(fight(tool2, tool1));
+        total += score;
+    }
+    total
+}
+
+fn win_startegy(games: &[Round]) -> u32 {
+    let mut total = 0;
+    for &(tool1, column) in games {
+        let result = [FightResult::Lose, FightResult::Draw, FightResult::Win][column];
+        let tool2 = [Suefa::Rock, Suefa::Paper, Suefa::Scissors].into_iter()
+            .find(|&tool2| fight_score(fight(tool2, tool1)) == fight_score(result))
+            .unwrap();
+        total += tool_score(tool2) + fight_score(result);
+    }
+    total
+}
+
+/// Total score when the second column is the shape to play (part 1) or the outcome to reach (part 2).
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<Round>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = ParseError;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        parse_rounds(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(calc_score(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(win_startegy(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = vec!["A Y".to_owned(), "B X".to_owned(), "C Z".to_owned()];
+        let rounds = parse_rounds(&data).unwrap();
+        assert_eq!(calc_score(&rounds), 15);
+        assert_eq!(win_startegy(&rounds), 12);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_rounds(&["A Y".to_owned(), "B W".to_owned()]).unwrap_err();
+        assert_eq!((2, 3, "expected X, Y or Z"), (error.line, error.column, error.message.as_str()));
+        assert_eq!(1, parse_rounds(&["AY".to_owned()]).unwrap_err().line);
+    }
+}
@@ -0,0 +1,28 @@
+use aoc_common::Solution;
+use day2_2::Day2;
+
+/// Score of every round spelled out: the shape played plus 0, 3 or 6 for the outcome.
+fn round_score(round: &str, part: u8) -> u32 {
+    match (part, round) {
+        (1, "A X") => 1 + 3, (1, "A Y") => 2 + 6, (1, "A Z") => 3,
+        (1, "B X") => 1, (1, "B Y") => 2 + 3, (1, "B Z") => 3 + 6,
+        (1, "C X") => 1 + 6, (1, "C Y") => 2, (1, "C Z") => 3 + 3,
+        // X loses, Y draws, Z wins
+        (_, "A X") => 3, (_, "A Y") => 1 + 3, (_, "A Z") => 2 + 6,
+        (_, "B X") => 1, (_, "B Y") => 2 + 3, (_, "B Z") => 3 + 6,
+        (_, "C X") => 2, (_, "C Y") => 3 + 3, (_, "C Z") => 1 + 6,
+        _ => panic!("unexpected round {}", round),
+    }
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day2::input, |lines| Day2::solve_part1(lines).ok(),
+        |lines| Some(lines.iter().map(|round| round_score(round, 1)).sum()));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day2::input, |lines| Day2::solve_part2(lines).ok(),
+        |lines| Some(lines.iter().map(|round| round_score(round, 2)).sum()));
+}
@@ -0,0 +1,134 @@
+//! Day 3: Rucksack Reorganization.
+
+use aoc_common::parse::{self, ParseError};
+use aoc_common::Solution;
+use std::collections::HashSet;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("{0}")]
+    Solve(&'static str),
+}
+
+/// Priority of an item: `a`-`z` are 1-26, `A`-`Z` are 27-52.
+pub fn priority_map(b: u8) -> Option<u8> {
+    match b {
+        b'a'..=b'z' => Some(b - b'a' + 1),
+        b'A'..=b'Z' => Some(b - b'A' + 27),
+        _ => None
+    }
+}
+
+/// Every rucksack as the priorities of its items.
+pub fn parse_rucksacks<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Vec<u8>>, ParseError> {
+    parse::lines(lines).map(|line| {
+        line.text.char_indices().map(|(i, c)| {
+            u8::try_from(c).ok().and_then(priority_map)
+                .ok_or_else(|| line.error(&line.text[i..i + c.len_utf8()], format!("unexpected {:?}, expected a letter", c)))
+        }).collect()
+    }).collect()
+}
+
+fn unique_chars(s: &[u8]) -> HashSet<u8> {
+    s.iter().cloned().collect()
+}
+
+fn intersect(a: HashSet<u8>, b: HashSet<u8>) -> HashSet<u8> {
+    a.intersection(&b).cloned().collect()
+}
+
+fn compare(a: &[u8], b: &[u8]) -> Option<u8> {
+    intersect(unique_chars(a), unique_chars(b)).into_iter().next()
+}
+
+/// Sum of priorities of the item found in both compartments of every rucksack.
+pub fn process(data: &[Vec<u8>]) -> Result<u32, &'static str> {
+    let mut total = 0;
+    for row in data {
+        let m = row.len() / 2;
+        let uniq = compare(&row[..m], &row[m..]).ok_or("uniq char not found")?;
+        total += uniq as u32;
+    }
+    Ok(total)
+}
+
+/// Sum of priorities of the item shared by every group of three rucksacks.
+pub fn count_badges(data: &[Vec<u8>]) -> Result<u32, &'static str> {
+    if !data.len().is_multiple_of(3) {
+        return Err("the number of rucksacks is not a multiple of 3");
+    }
+    let mut total = 0;
+    for parts in data.chunks(3) {
+        let h1 = intersect(unique_chars(&parts[0]), unique_chars(&parts[1]));
+        let h2 = intersect(h1, unique_chars(&parts[2]));
+        if h2.len() > 1 {
+            return Err("too many items after intersection");
+        }
+        total += h2.into_iter().next().ok_or("set is empty")? as u32;
+    }
+    Ok(total)
+}
+
+/// Priorities of misplaced items and of group badges.
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<Vec<u8>>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = Error;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(parse_rucksacks(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        process(input).map_err(Error::Solve)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        count_badges(input).map_err(Error::Solve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = &[
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(Ok(157), process(&parse_rucksacks(data).unwrap()));
+    }
+    #[test]
+    fn test2() {
+        let data = &[
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(Ok(70), count_badges(&parse_rucksacks(data).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_rucksacks(&["abc".to_string(), "ab1c".to_string()]).unwrap_err();
+        assert_eq!((2, 3, 1), (error.line, error.column, error.len));
+    }
+
+}
@@ -0,0 +1,32 @@
+use aoc_common::Solution;
+use day3::Day3;
+
+fn priority(item: char) -> u32 {
+    let items = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    items.find(item).unwrap() as u32 + 1
+}
+
+/// Tries every item type.
+fn common(rucksacks: &[&str]) -> char {
+    let mut found = ('a'..='z').chain('A'..='Z').filter(|&item| rucksacks.iter().all(|r| r.contains(item)));
+    let item = found.next().unwrap();
+    assert_eq!(None, found.next(), "more than one common item");
+    item
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day3::input, |lines| Day3::solve_part1(lines).ok(), |lines| {
+        Some(lines.iter().map(|line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            priority(common(&[left, right]))
+        }).sum())
+    });
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day3::input, |lines| Day3::solve_part2(lines).ok(), |lines| {
+        Some(lines.chunks(3).map(|group| priority(common(&[&group[0], &group[1], &group[2]]))).sum())
+    });
+}
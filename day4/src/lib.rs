@@ -0,0 +1,107 @@
+//! Day 4: Camp Cleanup. Every line is a pair of section ranges like `2-4,6-8`.
+
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::Solution;
+
+/// Inclusive range of section ids.
+pub type Range = (u32, u32);
+
+/// Whether `r1` fully contains `r2`.
+pub fn is_contain(r1: (u32, u32), r2: (u32, u32)) -> bool {
+    r1.0 <= r2.0 && r1.1 >= r2.1
+}
+
+/// Whether `r2` starts or ends inside `r1`, check both orders for any overlap.
+pub fn is_intersect(r1: (u32, u32), r2: (u32, u32)) -> bool {
+    (r1.0 <= r2.0 && r2.0 <= r1.1) || (r1.0 <= r2.1 && r2.1 <= r1.1)
+}
+
+fn parse_range(line: &Line, s: &str) -> Result<Range, ParseError> {
+    let (from, to) = line.split_once(s, "-")?;
+    Ok((line.parse(from)?, line.parse(to)?))
+}
+
+fn split_range(line: Line) -> Result<(Range, Range), ParseError> {
+    let (s1, s2) = line.split_once(line.text, ",")?;
+    Ok((parse_range(&line, s1)?, parse_range(&line, s2)?))
+}
+
+/// Pairs of ranges, one per line.
+pub fn parse_pairs<S: AsRef<str>>(lines: &[S]) -> Result<Vec<(Range, Range)>, ParseError> {
+    parse::lines(lines).map(split_range).collect()
+}
+
+/// Number of pairs where `compare` holds in either order.
+pub fn overlaps<F>(data: &[(Range, Range)], compare: F) -> i32
+where F: Fn(Range, Range) -> bool {
+    let mut total = 0;
+    for &(range1, range2) in data {
+        if compare(range1, range2) || compare(range2, range1) {
+            total += 1;
+        }
+    }
+    total
+}
+
+/// Pairs where one range contains the other (part 1) and pairs that overlap at all (part 2).
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<(Range, Range)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+    type Error = ParseError;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        parse_pairs(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(overlaps(input, is_contain))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(overlaps(input, is_intersect))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = &[
+            "2-4,6-8",
+            "2-3,4-5",
+            "5-7,7-9",
+            "2-8,3-7",
+            "6-6,4-6",
+            "2-6,4-8",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(2, overlaps(&parse_pairs(data).unwrap(), is_contain));
+    }
+    #[test]
+    fn test2() {
+        let data = &[
+            "2-4,6-8",
+            "2-3,4-5",
+            "5-7,7-9",
+            "2-8,3-7",
+            "6-6,4-6",
+            "2-6,4-8",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        assert_eq!(4, overlaps(&parse_pairs(data).unwrap(), is_intersect));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_pairs(&["2-4,6-8".to_string(), "2-4,6-x".to_string()]).unwrap_err();
+        assert_eq!((2, 7, 1), (error.line, error.column, error.len));
+        let error = parse_pairs(&["2-4;6-8".to_string()]).unwrap_err();
+        assert_eq!("expected \",\"", error.message);
+    }
+
+}
@@ -0,0 +1,30 @@
+use std::collections::HashSet;
+
+use aoc_common::Solution;
+use day4::Day4;
+
+/// Both ranges of every pair as sets of sections.
+fn pairs(lines: &[String]) -> Vec<(HashSet<u32>, HashSet<u32>)> {
+    let sections = |range: &str| {
+        let (start, end) = range.split_once('-').unwrap();
+        (start.parse().unwrap()..=end.parse().unwrap()).collect::<HashSet<u32>>()
+    };
+    lines.iter().map(|line| {
+        let (left, right) = line.split_once(',').unwrap();
+        (sections(left), sections(right))
+    }).collect()
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day4::input, |lines| Day4::solve_part1(lines).ok(), |lines| {
+        Some(pairs(lines).iter().filter(|(a, b)| a.is_subset(b) || b.is_subset(a)).count() as i32)
+    });
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day4::input, |lines| Day4::solve_part2(lines).ok(), |lines| {
+        Some(pairs(lines).iter().filter(|(a, b)| !a.is_disjoint(b)).count() as i32)
+    });
+}
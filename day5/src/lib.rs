@@ -0,0 +1,133 @@
+//! Day 5: Supply Stacks.
+
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::{scan, Solution};
+
+/// Number of crates, source and target stack, both 0-based.
+pub type Move = (usize, usize, usize);
+
+fn transpose<S: AsRef<str>>(input: &[S], size: usize) -> Vec<Vec<u8>> {
+    let mut result = vec![Vec::new(); size];
+    for row in input.iter().rev() {
+        let r = row.as_ref().as_bytes();
+        for (i, stack) in result.iter_mut().enumerate() {
+            match r.get(i*4 + 1) {
+                Some(b' ') | None => {},
+                Some(&crate_id) => stack.push(crate_id),
+            }
+        }
+    }
+    result
+}
+
+fn parse_move(line: Line, count: usize) -> Result<Move, ParseError> {
+    let (n, from, to): (usize, &str, &str) = scan!("move {} from {} to {}", &line)?;
+    let stack = |s: &str| -> Result<usize, ParseError> {
+        match line.parse::<usize>(s)? {
+            i @ 1.. if i <= count => Ok(i - 1),
+            _ => Err(line.error(s, format!("expected a stack from 1 to {}", count))),
+        }
+    };
+    Ok((n, stack(from)?, stack(to)?))
+}
+
+/// Stacks from bottom to top and the rearrangement procedure.
+pub fn process_input<S: AsRef<str>>(input: &[S]) -> Result<(Vec<Vec<u8>>, Vec<Move>), ParseError> {
+    let split = input.iter().position(|row| row.as_ref().is_empty())
+        .ok_or_else(|| ParseError::eof(input.len(), "expected an empty line after the stacks"))?;
+    let mut lines = parse::lines(input);
+    let count_line = lines.nth(split.checked_sub(1).ok_or_else(|| ParseError::eof(split, "expected the stacks"))?).unwrap();
+    let last = count_line.text.split_ascii_whitespace().last()
+        .ok_or_else(|| count_line.error(count_line.text, "expected stack numbers"))?;
+    let count = count_line.parse::<usize>(last)?;
+    let stack = transpose(&input[..split - 1], count);
+    let moves = lines.skip(1).map(|line| parse_move(line, count)).collect::<Result<_, _>>()?;
+    Ok((stack, moves))
+}
+
+/// Top crates after moving crates one at a time.
+pub fn crate_mover_9000(mut stack: Vec<Vec<u8>>, moves: &[Move]) -> String {
+    for (count, from, to) in moves {
+        for _ in 0..*count {
+            if !stack[*from].is_empty() {
+                let val = stack[*from].pop().unwrap();
+                stack[*to].push(val);
+            }
+        }
+    }
+    stack.into_iter().map(|s| *s.last().unwrap_or(&0x20) as char).collect()
+}
+
+/// Top crates after moving several crates at once.
+pub fn crate_mover_9001(mut stack: Vec<Vec<u8>>, moves: &[Move]) -> String {
+    for (count, from, to) in moves {
+        let mut tmp = Vec::new();
+        for _ in 0..*count {
+            if !stack[*from].is_empty() {
+                let val = stack[*from].pop().unwrap();
+                tmp.push(val);
+            }
+        }
+        while let Some(val) = tmp.pop() {
+            stack[*to].push(val);
+        }
+    }
+    stack.into_iter().map(|s| *s.last().unwrap_or(&0x20) as char).collect()
+}
+
+/// Top crates with the CrateMover 9000 (part 1) and 9001 (part 2).
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = (Vec<Vec<u8>>, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
+    type Error = ParseError;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        process_input(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(crate_mover_9000(input.0.clone(), &input.1))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(crate_mover_9001(input.0.clone(), &input.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = vec![
+            "[D]        ",
+            "[N] [C]    ",
+            "[Z] [M] [P]",
+            " 1   2   3 ",
+            "",
+            "move 1 from 2 to 1",
+            "move 3 from 1 to 3",
+            "move 2 from 2 to 1",
+            "move 1 from 1 to 2",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let (stack, moves) = process_input(&data).unwrap();
+        assert_eq!("ZMN", crate_mover_9000(stack, &moves));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let data = [" 1   2 ", "", "move 1 from 2 to 1", "move 1 from 3 to 1"].map(String::from);
+        let error = process_input(&data).unwrap_err();
+        assert_eq!((4, 13, "expected a stack from 1 to 2"), (error.line, error.column, error.message.as_str()));
+        let error = process_input(&data[..1]).unwrap_err();
+        assert_eq!("line 2: expected an empty line after the stacks", error.to_string());
+        let error = process_input(&data[1..]).unwrap_err();
+        assert_eq!("line 1: expected the stacks", error.to_string());
+    }
+}
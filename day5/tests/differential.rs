@@ -0,0 +1,38 @@
+use aoc_common::Solution;
+use day5::Day5;
+
+/// Moves crates by index arithmetic on the drawing; `at_once` keeps the order of the moved crates.
+fn reference(lines: &[String], at_once: bool) -> String {
+    let split = lines.iter().position(|line| line.is_empty()).unwrap();
+    let count = lines[split - 1].split_whitespace().count();
+    let mut stacks = vec![Vec::new(); count];
+    for line in lines[..split - 1].iter().rev() {
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            match line.as_bytes().get(4 * i + 1) {
+                Some(&c) if c != b' ' => stack.push(c as char),
+                _ => {},
+            }
+        }
+    }
+    for line in &lines[split + 1..] {
+        let words = line.split(' ').collect::<Vec<_>>();
+        let (n, from, to) = (words[1].parse::<usize>().unwrap(), words[3].parse::<usize>().unwrap() - 1, words[5].parse::<usize>().unwrap() - 1);
+        let rest = stacks[from].len() - n;
+        let mut moved = stacks[from].split_off(rest);
+        if !at_once {
+            moved.reverse();
+        }
+        stacks[to].extend(moved);
+    }
+    stacks.iter().map(|stack| stack.last().copied().unwrap_or(' ')).collect()
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day5::input, |lines| Day5::solve_part1(lines).ok(), |lines| Some(reference(lines, false)));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day5::input, |lines| Day5::solve_part2(lines).ok(), |lines| Some(reference(lines, true)));
+}
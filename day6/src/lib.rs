@@ -0,0 +1,106 @@
+//! Day 6: Tuning Trouble.
+
+use aoc_common::{params, Solution};
+use std::collections::HashSet;
+
+fn is_uniq(s: &[u8]) -> bool {
+    let mut set = HashSet::new();
+    s.iter().all(|b| set.insert(b))
+}
+
+/// Number of characters processed when the last `n` were all different.
+pub fn find_mark(s: &str, n: usize) -> Option<usize> {
+    let row = s.as_bytes();
+    row.windows(n).enumerate().find(|(_, v)| is_uniq(v)).map(|(i, _)| i + n)
+}
+
+fn task1(data: &[String], n: usize) -> Result<Vec<usize>, &'static str> {
+    if n == 0 {
+        return Err("marker size must be positive");
+    }
+    data.iter().map(|s| find_mark(s, n).ok_or("no marker found")).collect()
+}
+
+params! {
+    /// Distinct characters that make a marker.
+    pub struct Markers {
+        packet: usize = 4,
+        message: usize = 14,
+    }
+}
+
+/// Start-of-packet (4 distinct characters) and start-of-message (14) markers.
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Error = &'static str;
+    type Params = Markers;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(lines.iter().map(|line| line.as_ref().to_string()).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Markers::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Markers::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Markers) -> Result<Self::Answer1, Self::Error> {
+        task1(input, params.packet)?.first().copied().ok_or("empty input")
+    }
+
+    fn part2_with(input: &Self::Input, params: &Markers) -> Result<Self::Answer2, Self::Error> {
+        task1(input, params.message)?.first().copied().ok_or("empty input")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = &[
+            "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            "nppdvjthqldpwncqszvftbrmjlhg",
+            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let res = task1(data, 4).unwrap();
+        assert_eq!(5, res[0]);
+        assert_eq!(6, res[1]);
+        assert_eq!(10, res[2]);
+        assert_eq!(11, res[3]);
+    }
+    #[test]
+    fn test2() {
+        let data = &[
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            "nppdvjthqldpwncqszvftbrmjlhg",
+            "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+            "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let res = task1(data, 14).unwrap();
+        assert_eq!(19, res[0]);
+        assert_eq!(23, res[1]);
+        assert_eq!(23, res[2]);
+        assert_eq!(29, res[3]);
+        assert_eq!(26, res[4]);
+        assert_eq!(Err("no marker found"), task1(&["abcabc".to_string()], 4));
+    }
+
+    #[test]
+    fn test_params() {
+        let input = Day6::parse(&Day6::example()).unwrap();
+        assert_eq!(Ok(19), Day6::part1_with(&input, &Markers { packet: 14, ..Default::default() }));
+        assert!(Day6::part1_with(&input, &Markers { packet: 0, message: 0 }).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use std::collections::HashSet;
+
+use aoc_common::Solution;
+use day6::Day6;
+
+/// Characters read when the last `n` of them are first all different.
+fn reference(stream: &str, n: usize) -> usize {
+    let chars = stream.chars().collect::<Vec<_>>();
+    (n..=chars.len()).find(|&end| chars[end - n..end].iter().collect::<HashSet<_>>().len() == n).unwrap()
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day6::input, |lines| Day6::solve_part1(lines).ok(), |lines| Some(reference(&lines[0], 4)));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day6::input, |lines| Day6::solve_part2(lines).ok(), |lines| Some(reference(&lines[0], 14)));
+}
@@ -0,0 +1,218 @@
+//! Day 7: No Space Left On Device. The input is a terminal session of `cd` and `ls`.
+
+use aoc_common::parse::{self, Line, ParseError};
+use aoc_common::{params, Solution};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+    #[error("cd .. from the root directory")]
+    AboveRoot,
+}
+
+/// One line of the terminal session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandLine {
+    List,
+    Cd(String),
+    Dir(String),
+    File(String, u32),
+}
+
+fn parse_command(line: Line) -> Result<CommandLine, ParseError> {
+    if let Some(command) = line.text.strip_prefix("$ ") {
+        match command.split_once(' ') {
+            None if command == "ls" => Ok(CommandLine::List),
+            Some(("cd", dir)) => Ok(CommandLine::Cd(dir.to_string())),
+            _ => Err(line.error(command, "expected \"ls\" or \"cd <dir>\"")),
+        }
+    } else {
+        match line.split_once(line.text, " ")? {
+            ("dir", name) => Ok(CommandLine::Dir(name.to_string())),
+            (size, name) => Ok(CommandLine::File(name.to_string(), line.parse(size)?)),
+        }
+    }
+}
+
+pub fn parse_commands<S: AsRef<str>>(lines: &[S]) -> Result<Vec<CommandLine>, ParseError> {
+    parse::lines(lines).map(parse_command).collect()
+}
+
+/// Sum of the sizes of directories of at most `limit`.
+pub fn small_directories(lines: &[CommandLine], limit: u32) -> Result<u32, Error> {
+    let mut path = vec![String::from("/")];
+    let mut sizes = BTreeMap::new();
+    let make_dir = |path: &[String]| -> String {path.iter().map(|s| s.as_str()).collect()};
+    for cmd in lines {
+        match cmd {
+            CommandLine::Cd(dir) => {
+                match dir.as_str() {
+                    ".." => {
+                        if path.len() == 1 {
+                            return Err(Error::AboveRoot);
+                        }
+                        path.pop();
+                    },
+                    "/" => {path = vec![String::from("/")];},
+                    name => {
+                        path.push(format!("{}/", name));
+                        // directories without files count too
+                        sizes.entry(make_dir(&path)).or_insert(0);
+                    },
+                }
+            },
+            CommandLine::List => {
+            },
+            CommandLine::Dir(_name) => {},
+            CommandLine::File(_name, size) => {
+                for i in 1..=path.len() {
+                    let dir_name = make_dir(&path[..i]);
+                    *sizes.entry(dir_name).or_insert(0u32) += *size;
+                }
+            }
+        }
+    }
+
+    Ok(sizes.into_values().filter(|size| size <= &limit).sum())
+}
+
+/// Size of the smallest directory that frees `needed` space on a disk of `disk`.
+pub fn directory_to_delete(lines: &[CommandLine], disk: u32, needed: u32) -> Result<u32, Error> {
+    let max_size = disk.saturating_sub(needed);
+    let mut path = vec![String::from("/")];
+    let mut sizes: BTreeMap<String, u32> = BTreeMap::new();
+    let make_dir = |path: &[String]| -> String {path.iter().map(|s| s.as_str()).collect()};
+    for cmd in lines {
+        match cmd {
+            CommandLine::Cd(dir) => {
+                match dir.as_str() {
+                    ".." => {
+                        if path.len() == 1 {
+                            return Err(Error::AboveRoot);
+                        }
+                        path.pop();
+                    },
+                    "/" => {path = vec![String::from("/")];},
+                    name => {
+                        path.push(format!("{}/", name));
+                        // directories without files count too
+                        sizes.entry(make_dir(&path)).or_insert(0);
+                    },
+                }
+            },
+            CommandLine::List => {
+            },
+            CommandLine::Dir(_name) => {},
+            CommandLine::File(_name, size) => {
+                for i in 1..=path.len() {
+                    let dir_name = make_dir(&path[..i]);
+                    *sizes.entry(dir_name).or_default() += *size;
+                }
+            }
+        }
+    }
+
+    let total_size = sizes.get("/").copied().unwrap_or(0);
+    let need_size = total_size.saturating_sub(max_size);
+    Ok(sizes.into_values().filter(|x| x >= &need_size).min().unwrap_or(0))
+}
+
+params! {
+    pub struct Disk {
+        /// Largest directory counted in part 1.
+        small: u32 = 100000,
+        disk: u32 = 70000000,
+        /// Free space the update needs.
+        needed: u32 = 30000000,
+    }
+}
+
+/// Small directories (part 1) and the directory to delete (part 2).
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<CommandLine>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = Error;
+    type Params = Disk;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        Ok(parse_commands(lines)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Disk::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Disk::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Disk) -> Result<Self::Answer1, Self::Error> {
+        small_directories(input, params.small)
+    }
+
+    fn part2_with(input: &Self::Input, params: &Disk) -> Result<Self::Answer2, Self::Error> {
+        directory_to_delete(input, params.disk, params.needed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = &[
+            "$ cd /",
+            "$ ls",
+            "dir a",
+            "14848514 b.txt",
+            "8504156 c.dat",
+            "dir d",
+            "$ cd a",
+            "$ ls",
+            "dir e",
+            "29116 f",
+            "2557 g",
+            "62596 h.lst",
+            "$ cd e",
+            "$ ls",
+            "584 i",
+            "$ cd ..",
+            "$ cd ..",
+            "$ cd d",
+            "$ ls",
+            "4060174 j",
+            "8033020 d.log",
+            "5626152 d.ext",
+            "7214296 k",
+        ].into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let commands = parse_commands(data).unwrap();
+        assert_eq!(Ok(95437), small_directories(&commands, 100000));
+        assert_eq!(Ok(24933642), directory_to_delete(&commands, 70000000, 30000000));
+        assert_eq!(Ok(584), small_directories(&commands, 90000));
+        assert_eq!(Ok(94853), directory_to_delete(&commands, 70000000, 21700000));
+    }
+
+    #[test]
+    fn test_empty_directory() {
+        let data = ["$ cd /", "$ ls", "dir a", "100 b", "$ cd a", "$ ls"].map(String::from);
+        assert_eq!(Ok(0), directory_to_delete(&parse_commands(&data).unwrap(), 70000000, 30000000));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let data = ["$ cd /", "$ rm x", "12 a", "1x2 b"].map(String::from);
+        let error = parse_commands(&data).unwrap_err();
+        assert_eq!((2, 3, 4), (error.line, error.column, error.len));
+        let error = parse_commands(&data[2..]).unwrap_err();
+        assert_eq!((2, 1, 3), (error.line, error.column, error.len));
+        assert_eq!(Err(Error::AboveRoot), small_directories(&[CommandLine::Cd("..".to_string())], 100000));
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::Solution;
+use day7::Day7;
+
+/// Size of every directory, by full path, summing the files below it.
+fn directory_sizes(lines: &[String]) -> Vec<u32> {
+    let mut cwd = Vec::new();
+    let mut directories = HashSet::from([Vec::new()]);
+    let mut files = HashMap::new();
+    for line in lines {
+        let words = line.split(' ').collect::<Vec<_>>();
+        match words[..] {
+            ["$", "cd", "/"] => cwd.clear(),
+            ["$", "cd", ".."] => { cwd.pop(); },
+            ["$", "cd", name] => {
+                cwd.push(name.to_string());
+                directories.insert(cwd.clone());
+            },
+            ["$", "ls"] | ["dir", _] => {},
+            [size, name] => {
+                let mut path = cwd.clone();
+                path.push(name.to_string());
+                files.insert(path, size.parse::<u32>().unwrap());
+            },
+            _ => panic!("unexpected line {}", line),
+        }
+    }
+    directories.iter()
+        .map(|directory| files.iter().filter(|(path, _)| path.starts_with(directory)).map(|(_, size)| size).sum())
+        .collect()
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(1000, aoc_gen::day7::input, |lines| Day7::solve_part1(lines).ok(), |lines| {
+        Some(directory_sizes(lines).into_iter().filter(|&size| size <= 100000).sum())
+    });
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(1000, aoc_gen::day7::input, |lines| Day7::solve_part2(lines).ok(), |lines| {
+        let sizes = directory_sizes(lines);
+        let used = *sizes.iter().max().unwrap() as i64;
+        let needed = 30000000 - (70000000 - used);
+        sizes.into_iter().filter(|&size| size as i64 >= needed).min()
+    });
+}
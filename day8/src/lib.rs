@@ -0,0 +1,108 @@
+//! Day 8: Treetop Tree House. The input is a grid of tree heights.
+
+use aoc_common::grid::Grid;
+use aoc_common::ParseError;
+use aoc_common::Solution;
+
+/// Tree heights, 0-9.
+pub fn parse_heights<S: AsRef<str>>(lines: &[S]) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(lines, |b| b.is_ascii_digit().then(|| b - b'0'))
+}
+
+/// Number of trees visible from outside the grid.
+pub fn visible_trees(grid: &Grid<u8>) -> u32 {
+    fn scan<'a>(trees: impl Iterator<Item = (usize, &'a u8)>, mut mark: impl FnMut(usize)) {
+        let mut highest = None;
+        for (k, &tree) in trees {
+            if highest < Some(tree) {
+                highest = Some(tree);
+                mark(k);
+            }
+        }
+    }
+    let mut visible = Grid::new(grid.height(), grid.width(), false);
+    for i in 0..grid.height() {
+        scan(grid.row(i).iter().enumerate(), |j| visible[(i, j)] = true);
+        scan(grid.row(i).iter().enumerate().rev(), |j| visible[(i, j)] = true);
+    }
+    for j in 0..grid.width() {
+        scan(grid.column(j).enumerate(), |i| visible[(i, j)] = true);
+        scan(grid.column(j).enumerate().rev(), |i| visible[(i, j)] = true);
+    }
+    visible.values().filter(|&&v| v).count() as u32
+}
+
+/// Highest scenic score: the product of viewing distances in the four directions.
+pub fn scenic_score(grid: &Grid<u8>) -> u32 {
+    fn viewing_distance<'a>(height: u8, trees: impl Iterator<Item = &'a u8>) -> u32 {
+        let mut count = 0;
+        for &tree in trees {
+            count += 1;
+            if tree >= height {
+                break;
+            }
+        }
+        count
+    }
+    let vision_rate = |(i, j)| {
+        let height = grid[(i, j)];
+        let row = grid.row(i);
+        viewing_distance(height, grid.column(j).take(i).rev()) *
+        viewing_distance(height, grid.column(j).skip(i + 1)) *
+        viewing_distance(height, row[..j].iter().rev()) *
+        viewing_distance(height, row[j + 1..].iter())
+    };
+    grid.positions().map(vision_rate).max().unwrap_or(0)
+}
+
+/// Visible trees (part 1) and the best scenic score (part 2).
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Grid<u8>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = ParseError;
+    type Params = ();
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        parse_heights(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Ok(visible_trees(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Ok(scenic_score(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = &[
+            "30373",
+            "25512",
+            "65332",
+            "33549",
+            "35390",
+        ];
+        assert_eq!(21, visible_trees(&parse_heights(data).unwrap()));
+    }
+    #[test]
+    fn test2() {
+        let data = &[
+            "30373",
+            "25512",
+            "65332",
+            "33549",
+            "35390",
+        ];
+        assert_eq!(8, scenic_score(&parse_heights(data).unwrap()));
+    }
+}
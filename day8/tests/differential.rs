@@ -0,0 +1,39 @@
+use aoc_common::Solution;
+use day8::Day8;
+
+/// Trees seen from `(i, j)` in every direction and whether the view reaches the edge.
+fn views(grid: &[Vec<u8>], i: usize, j: usize) -> Vec<(u32, bool)> {
+    let (n, m) = (grid.len() as i32, grid[0].len() as i32);
+    [(-1, 0), (1, 0), (0, -1), (0, 1)].into_iter().map(|(di, dj)| {
+        let (mut r, mut c, mut seen) = (i as i32 + di, j as i32 + dj, 0);
+        while r >= 0 && r < n && c >= 0 && c < m {
+            seen += 1;
+            if grid[r as usize][c as usize] >= grid[i][j] {
+                return (seen, false);
+            }
+            r += di;
+            c += dj;
+        }
+        (seen, true)
+    }).collect()
+}
+
+fn trees(lines: &[String]) -> impl Iterator<Item = Vec<(u32, bool)>> {
+    let grid = lines.iter().map(|line| line.bytes().collect::<Vec<_>>()).collect::<Vec<_>>();
+    let (n, m) = (grid.len(), grid[0].len());
+    (0..n * m).map(move |k| views(&grid, k / m, k % m))
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day8::input, |lines| Day8::solve_part1(lines).ok(), |lines| {
+        Some(trees(lines).filter(|views| views.iter().any(|&(_, edge)| edge)).count() as u32)
+    });
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day8::input, |lines| Day8::solve_part2(lines).ok(), |lines| {
+        trees(lines).map(|views| views.iter().map(|&(seen, _)| seen).product()).max()
+    });
+}
@@ -0,0 +1,125 @@
+//! Day 9: Rope Bridge.
+
+use aoc_common::parse::{self, ParseError};
+use aoc_common::{params, Direction, Point2, Solution};
+use std::collections::HashSet;
+
+/// Moves of the head, `R 4` is four steps to the right.
+pub fn parse_moves<S: AsRef<str>>(lines: &[S]) -> Result<Vec<(Direction, u32)>, ParseError> {
+    parse::lines(lines).map(|line| {
+        let (direction, n) = line.split_once(line.text, " ")?;
+        let direction = match direction {
+            "U" | "R" | "D" | "L" => Direction::try_from(direction.as_bytes()[0] as char).ok(),
+            _ => None,
+        }.ok_or_else(|| line.error(direction, "expected U, R, D or L"))?;
+        Ok((direction, line.parse(n)?))
+    }).collect()
+}
+
+/// Number of positions visited by the tail of a rope of `len` knots, a rope has at least one.
+pub fn tail_positions(moves: &[(Direction, u32)], len: usize) -> u32 {
+    let len = len.max(1);
+    let mut rope: Vec<Point2> = vec![Point2::default(); len];
+    let mut visited = HashSet::from([rope[len - 1]]);
+    for &(direction, n) in moves {
+        for _ in 0..n {
+            rope[0] += direction.delta();
+            for i in 1..len {
+                if rope[i - 1].chebyshev(rope[i]) <= 1 {
+                    break;
+                }
+                let step = (rope[i - 1] - rope[i]).signum();
+                rope[i] += step;
+            }
+            visited.insert(rope[len - 1]);
+        }
+    }
+    visited.len() as u32
+}
+
+params! {
+    /// Knots of the rope in each part.
+    pub struct Knots {
+        knots1: usize = 2,
+        knots2: usize = 10,
+    }
+}
+
+/// Tail positions of a rope of 2 (part 1) and 10 knots (part 2).
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    type Input = Vec<(Direction, u32)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Error = ParseError;
+    type Params = Knots;
+
+    fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self::Input, Self::Error> {
+        parse_moves(lines)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, Self::Error> {
+        Self::part1_with(input, &Knots::default())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, Self::Error> {
+        Self::part2_with(input, &Knots::default())
+    }
+
+    fn part1_with(input: &Self::Input, params: &Knots) -> Result<Self::Answer1, Self::Error> {
+        Ok(tail_positions(input, params.knots1))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Knots) -> Result<Self::Answer2, Self::Error> {
+        Ok(tail_positions(input, params.knots2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test1() {
+        let data = &[
+            "R 4",
+            "U 4",
+            "L 3",
+            "D 1",
+            "R 4",
+            "D 1",
+            "L 5",
+            "R 2",
+        ];
+        assert_eq!(13, tail_positions(&parse_moves(data).unwrap(), 2));
+    }
+    #[test]
+    fn test2() {
+        let data = &[
+            "R 5",
+            "U 8",
+            "L 8",
+            "D 3",
+            "R 17",
+            "D 10",
+            "L 25",
+            "U 20",
+        ];
+        assert_eq!(36, tail_positions(&parse_moves(data).unwrap(), 10));
+    }
+    #[test]
+    fn test_params() {
+        let input = Day9::parse(&Day9::example()).unwrap();
+        assert_eq!(Ok(1), Day9::part1_with(&input, &Knots { knots1: 10, knots2: 2 }));
+        assert_eq!(Ok(13), Day9::part2_with(&input, &Knots { knots1: 10, knots2: 2 }));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_moves(&["R 4", "X 4"]).unwrap_err();
+        assert_eq!((2, 1, "expected U, R, D or L"), (error.line, error.column, error.message.as_str()));
+        assert_eq!(3, parse_moves(&["R -4"]).unwrap_err().column);
+    }
+}
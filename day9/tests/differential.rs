@@ -0,0 +1,40 @@
+use std::collections::HashSet;
+
+use aoc_common::Solution;
+use day9::Day9;
+
+/// Moves the head one step at a time, every knot follows the previous one.
+fn reference(lines: &[String], knots: usize) -> u32 {
+    let mut rope = vec![(0i32, 0i32); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+    for line in lines {
+        let (direction, steps) = line.split_once(' ').unwrap();
+        let (dx, dy) = match direction {
+            "U" => (0, -1),
+            "R" => (1, 0),
+            "D" => (0, 1),
+            _ => (-1, 0),
+        };
+        for _ in 0..steps.parse::<u32>().unwrap() {
+            rope[0] = (rope[0].0 + dx, rope[0].1 + dy);
+            for k in 1..knots {
+                let (x, y) = (rope[k - 1].0 - rope[k].0, rope[k - 1].1 - rope[k].1);
+                if x.abs() > 1 || y.abs() > 1 {
+                    rope[k] = (rope[k].0 + x.signum(), rope[k].1 + y.signum());
+                }
+            }
+            visited.insert(rope[knots - 1]);
+        }
+    }
+    visited.len() as u32
+}
+
+#[test]
+fn part1() {
+    aoc_gen::check(2000, aoc_gen::day9::input, |lines| Day9::solve_part1(lines).ok(), |lines| Some(reference(lines, 2)));
+}
+
+#[test]
+fn part2() {
+    aoc_gen::check(2000, aoc_gen::day9::input, |lines| Day9::solve_part2(lines).ok(), |lines| Some(reference(lines, 10)));
+}